@@ -0,0 +1,171 @@
+use anyhow::Result;
+use axum::{
+    extract::{Path, Query, State, ws::{Message, WebSocket, WebSocketUpgrade}},
+    http::StatusCode,
+    response::{IntoResponse, Json, Response, sse::{Event, KeepAlive, Sse}},
+    routing::get,
+    Router,
+};
+use futures_util::{Stream, StreamExt};
+use serde::Deserialize;
+use serde_json::json;
+use std::convert::Infallible;
+use tokio::sync::broadcast::error::RecvError;
+use tokio_stream::wrappers::{BroadcastStream, errors::BroadcastStreamRecvError};
+use tower_http::cors::CorsLayer;
+use tracing::{info, warn, debug};
+
+use crate::event_store::EventHub;
+use crate::metrics::metrics;
+use crate::types::Platform;
+
+const DEFAULT_LIMIT: usize = 50;
+const MAX_LIMIT: usize = 500;
+
+#[derive(Debug, Deserialize)]
+struct LaunchQuery {
+    limit: Option<usize>,
+    platform: Option<Platform>,
+}
+
+#[derive(Debug, Deserialize)]
+struct EventQuery {
+    limit: Option<usize>,
+}
+
+/// REST + streaming API for the Next.js dashboard.
+pub struct ApiServer {
+    bind_addr: String,
+    events: EventHub,
+}
+
+impl ApiServer {
+    pub fn new(bind_addr: &str, events: EventHub) -> Self {
+        Self {
+            bind_addr: bind_addr.to_string(),
+            events,
+        }
+    }
+
+    pub fn router(events: EventHub) -> Router {
+        Router::new()
+            .route("/api/launches", get(get_launches))
+            .route("/api/events", get(get_events))
+            .route("/api/tokens/{mint}", get(get_token))
+            .route("/api/creators/{address}", get(get_creator))
+            .route("/api/stats", get(get_stats))
+            .route("/api/stream/ws", get(stream_ws))
+            .route("/api/stream/sse", get(stream_sse))
+            .route("/metrics", get(get_metrics))
+            // Dashboard runs on a different origin (next dev on :3000)
+            .layer(CorsLayer::permissive())
+            .with_state(events)
+    }
+
+    pub async fn run(self) -> Result<()> {
+        let listener = tokio::net::TcpListener::bind(&self.bind_addr).await?;
+        info!("🌐 API server listening on http://{}", listener.local_addr()?);
+
+        axum::serve(listener, Self::router(self.events)).await?;
+        Ok(())
+    }
+}
+
+// ========================================================================
+// REST HANDLERS
+// ========================================================================
+
+async fn get_launches(State(events): State<EventHub>, Query(query): Query<LaunchQuery>) -> impl IntoResponse {
+    let limit = query.limit.unwrap_or(DEFAULT_LIMIT).min(MAX_LIMIT);
+    Json(events.read(|store| store.recent_launches(query.platform, limit)))
+}
+
+async fn get_events(State(events): State<EventHub>, Query(query): Query<EventQuery>) -> impl IntoResponse {
+    let limit = query.limit.unwrap_or(DEFAULT_LIMIT).min(MAX_LIMIT);
+    Json(events.read(|store| store.recent_events(limit)))
+}
+
+async fn get_token(State(events): State<EventHub>, Path(mint): Path<String>) -> Response {
+    match events.read(|store| store.token(&mint)) {
+        Some(record) => Json(record).into_response(),
+        None => (StatusCode::NOT_FOUND, Json(json!({ "error": "token not found", "mint": mint }))).into_response(),
+    }
+}
+
+async fn get_creator(State(events): State<EventHub>, Path(address): Path<String>) -> Response {
+    match events.read(|store| store.creator(&address).cloned()) {
+        Some(profile) => Json(profile).into_response(),
+        None => (StatusCode::NOT_FOUND, Json(json!({ "error": "creator not found", "creator": address }))).into_response(),
+    }
+}
+
+async fn get_stats(State(events): State<EventHub>) -> impl IntoResponse {
+    Json(events.read(|store| store.stats()))
+}
+
+async fn get_metrics() -> impl IntoResponse {
+    (
+        [(axum::http::header::CONTENT_TYPE, "text/plain; version=0.0.4")],
+        metrics().render(),
+    )
+}
+
+// ========================================================================
+// STREAMING HANDLERS
+// ========================================================================
+
+async fn stream_ws(State(events): State<EventHub>, ws: WebSocketUpgrade) -> Response {
+    ws.on_upgrade(move |socket| forward_events_to_socket(socket, events))
+}
+
+async fn forward_events_to_socket(mut socket: WebSocket, events: EventHub) {
+    let mut receiver = events.subscribe();
+    debug!("🔌 Dashboard WebSocket client connected");
+
+    loop {
+        tokio::select! {
+            event = receiver.recv() => {
+                match event {
+                    Ok(event) => {
+                        let payload = match serde_json::to_string(&event) {
+                            Ok(payload) => payload,
+                            Err(e) => {
+                                warn!("⚠️ Failed to serialize event: {}", e);
+                                continue;
+                            }
+                        };
+                        if socket.send(Message::Text(payload.into())).await.is_err() {
+                            break;
+                        }
+                    },
+                    Err(RecvError::Lagged(skipped)) => {
+                        warn!("⚠️ WebSocket client lagging, skipped {} events", skipped);
+                    },
+                    Err(RecvError::Closed) => break,
+                }
+            }
+            incoming = socket.recv() => {
+                match incoming {
+                    Some(Ok(Message::Close(_))) | None | Some(Err(_)) => break,
+                    _ => {} // Ignore client messages
+                }
+            }
+        }
+    }
+
+    debug!("🔌 Dashboard WebSocket client disconnected");
+}
+
+async fn stream_sse(State(events): State<EventHub>) -> Sse<impl Stream<Item = Result<Event, Infallible>>> {
+    let stream = BroadcastStream::new(events.subscribe()).filter_map(|event| async move {
+        match event {
+            Ok(event) => Event::default().event("monitor_event").json_data(&event).ok().map(Ok),
+            Err(BroadcastStreamRecvError::Lagged(skipped)) => {
+                warn!("⚠️ SSE client lagging, skipped {} events", skipped);
+                None
+            },
+        }
+    });
+
+    Sse::new(stream).keep_alive(KeepAlive::default())
+}
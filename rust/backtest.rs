@@ -0,0 +1,511 @@
+use anyhow::{anyhow, bail, Context, Result};
+use futures_util::{stream, StreamExt};
+use serde::Serialize;
+use serde_json::{json, Value};
+use std::collections::{BTreeMap, HashMap};
+use std::fs::File;
+use std::io::{BufRead, BufReader};
+use std::path::Path;
+use tokio::time::{sleep, Duration};
+use tracing::{info, warn, debug};
+
+use crate::config::{BacktestArgs, BacktestConfig, Commitment, Config};
+use crate::event_store::{EventHub, PlatformStats};
+use crate::rpc_scheduler::{Priority, RpcScheduler};
+use crate::ws_recording::{read_frames, Frame, FrameSource, RecordingWriter};
+use crate::types::{PUMP_FUN_PROGRAM_ID, RAYDIUM_PROGRAM_ID};
+
+// Nominal slot duration for the first guesses of the time -> slot search
+const MS_PER_SLOT: f64 = 400.0;
+const TIME_SEARCH_STEPS: usize = 8;
+// A guessed slot this close (in block time) to the target is good enough
+const TIME_SEARCH_TOLERANCE_SECS: i64 = 2;
+// Start the walk a little past the estimated end slot; later transactions are filtered out
+const TIME_SEARCH_MARGIN_SLOTS: u64 = 150;
+const PROGRESS_EVERY: u64 = 1000;
+
+// ========================================================================
+// RANGE
+// ========================================================================
+
+/// Unix seconds, or a UTC date and time (`2024-05-01T14:00`, seconds and a trailing `Z` optional).
+pub fn parse_time(value: &str) -> Result<u64, String> {
+    let value = value.trim();
+    if let Ok(secs) = value.parse::<u64>() {
+        return Ok(secs);
+    }
+
+    let invalid = || format!("expected unix seconds or YYYY-MM-DDTHH:MM[:SS], got `{}`", value);
+    let (date, time) = value.trim_end_matches('Z').split_once(['T', ' ']).ok_or_else(invalid)?;
+    let numbers = |text: &str, separator: char| -> Result<Vec<u64>, String> {
+        text.split(separator).map(|part| part.parse::<u64>().map_err(|_| invalid())).collect()
+    };
+    let (date, time) = (numbers(date, '-')?, numbers(time, ':')?);
+    let [year, month, day] = date[..] else { return Err(invalid()) };
+    let (hour, minute, second) = match time[..] {
+        [hour, minute] => (hour, minute, 0),
+        [hour, minute, second] => (hour, minute, second),
+        _ => return Err(invalid()),
+    };
+    if year < 1970 || !(1..=12).contains(&month) || day == 0 || day > days_in_month(year, month)
+        || hour > 23 || minute > 59 || second > 59 {
+        return Err(invalid());
+    }
+
+    Ok(days_since_epoch(year, month, day) * 86_400 + hour * 3600 + minute * 60 + second)
+}
+
+fn days_in_month(year: u64, month: u64) -> u64 {
+    match month {
+        2 if year.is_multiple_of(4) && (!year.is_multiple_of(100) || year.is_multiple_of(400)) => 29,
+        2 => 28,
+        4 | 6 | 9 | 11 => 30,
+        _ => 31,
+    }
+}
+
+// Civil date -> days since 1970-01-01 (years counted from March, so leap days come last)
+fn days_since_epoch(year: u64, month: u64, day: u64) -> u64 {
+    let year = if month <= 2 { year - 1 } else { year };
+    let era = year / 400;
+    let year_of_era = year - era * 400;
+    let day_of_year = (153 * ((month + 9) % 12) + 2) / 5 + day - 1;
+    let day_of_era = year_of_era * 365 + year_of_era / 4 - year_of_era / 100 + day_of_year;
+    era * 146_097 + day_of_era - 719_468
+}
+
+/// What a backtest covers. Both ends are inclusive; an open end means "up to now".
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(tag = "by", rename_all = "snake_case")]
+pub enum BacktestRange {
+    Slots { from: u64, to: Option<u64> },
+    // Unix seconds of block time
+    Time { from: u64, to: Option<u64> },
+    // Archives without a range are replayed whole
+    All,
+}
+
+impl BacktestRange {
+    pub fn from_args(args: &BacktestArgs) -> Result<Self> {
+        let range = match (args.from_slot, args.from) {
+            (Some(from), _) => BacktestRange::Slots { from, to: args.to_slot },
+            (None, Some(from)) => BacktestRange::Time { from, to: args.to },
+            (None, None) => BacktestRange::All,
+        };
+        if let BacktestRange::Slots { from, to: Some(to) } | BacktestRange::Time { from, to: Some(to) } = range {
+            if from > to {
+                bail!("backtest range ends before it starts ({} > {})", from, to);
+            }
+        }
+        Ok(range)
+    }
+
+    /// Newer than the range. Unknown slots / times are never outside it.
+    fn is_after(&self, slot: Option<u64>, time_secs: Option<u64>) -> bool {
+        match *self {
+            BacktestRange::Slots { to: Some(to), .. } => slot.is_some_and(|slot| slot > to),
+            BacktestRange::Time { to: Some(to), .. } => time_secs.is_some_and(|time| time > to),
+            _ => false,
+        }
+    }
+
+    /// Older than the range.
+    fn is_before(&self, slot: Option<u64>, time_secs: Option<u64>) -> bool {
+        match *self {
+            BacktestRange::Slots { from, .. } => slot.is_some_and(|slot| slot < from),
+            BacktestRange::Time { from, .. } => time_secs.is_some_and(|time| time < from),
+            BacktestRange::All => false,
+        }
+    }
+
+    pub fn contains(&self, slot: Option<u64>, time_secs: Option<u64>) -> bool {
+        !self.is_after(slot, time_secs) && !self.is_before(slot, time_secs)
+    }
+
+    /// Recorded frames are placed by their notification slot, or arrival time for time ranges.
+    pub fn contains_frame(&self, frame: &Frame) -> bool {
+        self.contains(frame_slot(frame), Some(frame.at_ms / 1000))
+    }
+}
+
+/// Slot of a logs notification or fetched transaction.
+pub fn frame_slot(frame: &Frame) -> Option<u64> {
+    let value: Value = serde_json::from_str(&frame.text).ok()?;
+    value.pointer("/params/result/context/slot")
+        .or_else(|| value.pointer("/result/slot"))
+        .and_then(Value::as_u64)
+}
+
+/// (slot, time in ms) of the first and last frames in `range`, to anchor the block clock on.
+pub fn clock_anchors(path: &Path, range: BacktestRange) -> Result<Vec<(u64, u64)>> {
+    let mut anchors = Vec::new();
+    for frame in read_frames(path)?.filter(|frame| range.contains_frame(frame)) {
+        if let Some(slot) = frame_slot(&frame) {
+            anchors.truncate(1);
+            anchors.push((slot, frame.at_ms));
+        }
+    }
+    Ok(anchors)
+}
+
+// ========================================================================
+// HISTORY FETCHING
+// ========================================================================
+
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct FetchStats {
+    // In range, across the watched programs
+    pub signatures: u64,
+    // Failed on chain - never fetched, the monitors skip them anyway
+    pub failed_transactions: u64,
+    pub fetched_transactions: u64,
+    pub not_found: u64,
+    pub fetch_errors: u64,
+    // Hit `max_transactions` - the oldest part of the range is missing
+    pub truncated: bool,
+    pub first_slot: Option<u64>,
+    pub last_slot: Option<u64>,
+}
+
+struct HistoricalTransaction {
+    signature: String,
+    slot: u64,
+    sources: Vec<FrameSource>,
+}
+
+/// Walks `getSignaturesForAddress` for the watched programs and fetches each
+/// transaction, writing them as a recording the monitors can replay.
+pub struct HistoryFetcher {
+    settings: BacktestConfig,
+    rpc: RpcScheduler,
+    commitment: Commitment,
+    programs: Vec<(FrameSource, &'static str)>,
+}
+
+impl HistoryFetcher {
+    pub fn new(config: &Config) -> Result<Self> {
+        let mut programs = Vec::new();
+        if config.platforms.pump_fun {
+            programs.push((FrameSource::PumpFun, PUMP_FUN_PROGRAM_ID));
+        }
+        if config.platforms.raydium {
+            programs.push((FrameSource::Raydium, RAYDIUM_PROGRAM_ID));
+        }
+
+        Ok(Self {
+            settings: config.backtest.clone(),
+            rpc: RpcScheduler::shared(config)?
+                .with_timeout(Duration::from_secs(config.backtest.request_timeout_secs))
+                .with_retries(config.backtest.max_retries),
+            // History isn't served at "processed"
+            commitment: match config.commitment {
+                Commitment::Finalized => Commitment::Finalized,
+                _ => Commitment::Confirmed,
+            },
+            programs,
+        })
+    }
+
+    /// Every successful transaction of the watched programs in `range`, oldest first, as a recording at `path`.
+    pub async fn fetch(&self, range: BacktestRange, path: &Path) -> Result<FetchStats> {
+        if range == BacktestRange::All {
+            bail!("backtesting from the RPC needs --from-slot or --from");
+        }
+        let before = self.start_signature(range).await?;
+        let mut stats = FetchStats::default();
+
+        // One list per program, oldest first, merged by slot (a transaction can mention both)
+        let mut transactions: Vec<HistoricalTransaction> = Vec::new();
+        let mut positions: HashMap<String, usize> = HashMap::new();
+        for (source, program) in &self.programs {
+            let (signatures, truncated) = self.signatures(program, range, before.clone(), &mut stats).await?;
+            info!("🕰️ {} {} transactions in range{}", signatures.len(), source_name(*source), if truncated { " (truncated)" } else { "" });
+            stats.truncated |= truncated;
+            for (signature, slot) in signatures.into_iter().rev() {
+                match positions.get(&signature) {
+                    Some(&position) => transactions[position].sources.push(*source),
+                    None => {
+                        positions.insert(signature.clone(), transactions.len());
+                        transactions.push(HistoricalTransaction { signature, slot, sources: vec![*source] });
+                    },
+                }
+            }
+        }
+        transactions.sort_by_key(|transaction| transaction.slot);
+        stats.signatures = transactions.len() as u64;
+        stats.first_slot = transactions.first().map(|transaction| transaction.slot);
+        stats.last_slot = transactions.last().map(|transaction| transaction.slot);
+
+        // `buffered` keeps the order while fetching several at once
+        let mut writer = RecordingWriter::create(path)?;
+        let mut responses = stream::iter(&transactions)
+            .map(|transaction| async move { (transaction, self.call("getTransaction", self.transaction_params(&transaction.signature)).await) })
+            .buffered(self.settings.max_concurrent_fetches.max(1));
+        while let Some((transaction, response)) = responses.next().await {
+            match response {
+                Ok(Value::Null) => stats.not_found += 1,
+                Ok(result) => {
+                    stats.fetched_transactions += 1;
+                    for frame in transaction_frames(&transaction.signature, &result, &transaction.sources) {
+                        writer.write(&frame)?;
+                    }
+                },
+                Err(e) => {
+                    stats.fetch_errors += 1;
+                    warn!("⚠️ Fetching {} failed: {:#}", transaction.signature, e);
+                },
+            }
+            let done = stats.fetched_transactions + stats.not_found + stats.fetch_errors;
+            if done % PROGRESS_EVERY == 0 {
+                info!("🕰️ Fetched {}/{} transactions", done, transactions.len());
+            }
+        }
+        let frames = writer.finish()?;
+        info!("🕰️ Archived {} frames to {}", frames, path.display());
+        Ok(stats)
+    }
+
+    /// Signature to walk back from: the first one of the block after the range, so the whole last slot is included.
+    async fn start_signature(&self, range: BacktestRange) -> Result<Option<String>> {
+        let end_slot = match range {
+            BacktestRange::Slots { to: Some(to), .. } => to,
+            BacktestRange::Time { to: Some(to), .. } => self.slot_at_time(to).await? + TIME_SEARCH_MARGIN_SLOTS,
+            _ => return Ok(None),
+        };
+        let Some(block) = self.next_block(end_slot + 1).await? else {
+            return Ok(None);
+        };
+        let block = self.call("getBlock", json!([block, {
+            "commitment": self.commitment.as_str(),
+            "transactionDetails": "signatures",
+            "rewards": false,
+            "maxSupportedTransactionVersion": 0,
+        }])).await?;
+        Ok(block.get("signatures").and_then(|s| s.get(0)).and_then(|s| s.as_str()).map(str::to_string))
+    }
+
+    /// First slot at or after `slot` that has a block (slots can be skipped).
+    async fn next_block(&self, slot: u64) -> Result<Option<u64>> {
+        let blocks = self.call("getBlocksWithLimit", json!([slot, 1, { "commitment": self.commitment.as_str() }])).await?;
+        Ok(blocks.get(0).and_then(|b| b.as_u64()))
+    }
+
+    /// Approximate slot produced at `secs`, by stepping from the current slot on block times.
+    async fn slot_at_time(&self, secs: u64) -> Result<u64> {
+        let tip = self.call("getSlot", json!([{ "commitment": self.commitment.as_str() }])).await?
+            .as_u64().ok_or_else(|| anyhow!("getSlot returned no slot"))?;
+
+        let mut slot = tip;
+        for _ in 0..TIME_SEARCH_STEPS {
+            let block = self.next_block(slot).await?.unwrap_or(slot);
+            let Some(block_time) = self.call("getBlockTime", json!([block])).await?.as_i64() else {
+                break;
+            };
+            let offset_secs = secs as i64 - block_time;
+            if offset_secs.abs() <= TIME_SEARCH_TOLERANCE_SECS {
+                return Ok(block);
+            }
+            let guess = block as f64 + offset_secs as f64 * 1000.0 / MS_PER_SLOT;
+            slot = (guess.max(0.0) as u64).min(tip);
+        }
+        Ok(slot)
+    }
+
+    /// In-range successful signatures of `program`, newest first. Stops at `max_transactions`.
+    async fn signatures(&self, program: &str, range: BacktestRange, mut before: Option<String>, stats: &mut FetchStats) -> Result<(Vec<(String, u64)>, bool)> {
+        let mut signatures = Vec::new();
+        loop {
+            let mut options = json!({ "limit": self.settings.page_size, "commitment": self.commitment.as_str() });
+            if let Some(before) = &before {
+                options["before"] = json!(before);
+            }
+            let page = self.call("getSignaturesForAddress", json!([program, options])).await?;
+            let entries = page.as_array().ok_or_else(|| anyhow!("getSignaturesForAddress returned no list"))?;
+
+            for entry in entries {
+                let (Some(signature), Some(slot)) = (entry.get("signature").and_then(|s| s.as_str()), entry.get("slot").and_then(|s| s.as_u64())) else {
+                    continue;
+                };
+                let block_time = entry.get("blockTime").and_then(|t| t.as_u64());
+                if range.is_before(Some(slot), block_time) {
+                    return Ok((signatures, false));
+                }
+                if range.is_after(Some(slot), block_time) {
+                    continue;
+                }
+                if entry.get("err").is_some_and(|err| !err.is_null()) {
+                    stats.failed_transactions += 1;
+                    continue;
+                }
+                signatures.push((signature.to_string(), slot));
+                if signatures.len() >= self.settings.max_transactions {
+                    warn!("⚠️ {} has more than {} transactions in range - stopping at slot {}", program, self.settings.max_transactions, slot);
+                    return Ok((signatures, true));
+                }
+            }
+
+            if entries.len() < self.settings.page_size {
+                return Ok((signatures, false));
+            }
+            before = entries.last().and_then(|e| e.get("signature")).and_then(|s| s.as_str()).map(str::to_string);
+            debug!("🕰️ {} signatures so far for {}", signatures.len(), program);
+        }
+    }
+
+    fn transaction_params(&self, signature: &str) -> Value {
+        // jsonParsed, as the Raydium monitor fetches them live
+        json!([signature, {
+            "encoding": "jsonParsed",
+            "commitment": self.commitment.as_str(),
+            "maxSupportedTransactionVersion": 0,
+        }])
+    }
+
+    /// `result` of the call. The scheduler retries 429s, 5xx and transport errors.
+    async fn call(&self, method: &str, params: Value) -> Result<Value> {
+        self.rpc.result(Priority::Background, method, params).await.with_context(|| format!("{} failed", method))
+    }
+}
+
+fn source_name(source: FrameSource) -> &'static str {
+    match source {
+        FrameSource::PumpFun => "Pump.fun",
+        _ => "Raydium",
+    }
+}
+
+/// Frames as the live monitors would have seen a transaction: a `logsNotification`
+/// per source, preceded for Raydium by the `getTransaction` response it looks up.
+pub fn transaction_frames(signature: &str, result: &Value, sources: &[FrameSource]) -> Vec<Frame> {
+    let at_ms = result.get("blockTime").and_then(|t| t.as_u64()).unwrap_or(0) * 1000;
+    let meta = result.get("meta");
+    let notification = json!({
+        "jsonrpc": "2.0",
+        "method": "logsNotification",
+        "params": {
+            "result": {
+                "context": { "slot": result.get("slot") },
+                "value": {
+                    "signature": signature,
+                    "err": meta.and_then(|m| m.get("err")),
+                    "logs": meta.and_then(|m| m.get("logMessages")).cloned().unwrap_or_else(|| json!([])),
+                }
+            },
+            "subscription": 0
+        }
+    });
+
+    let mut frames = Vec::new();
+    for source in sources {
+        if *source == FrameSource::Raydium {
+            let response = json!({ "jsonrpc": "2.0", "result": result, "id": 1 });
+            frames.push(Frame { at_ms, source: FrameSource::RaydiumTransaction, signature: Some(signature.to_string()), text: response.to_string() });
+        }
+        frames.push(Frame { at_ms, source: *source, signature: None, text: notification.to_string() });
+    }
+    frames
+}
+
+// ========================================================================
+// SUMMARY
+// ========================================================================
+
+#[derive(Debug, Clone, Serialize)]
+pub struct BacktestSummary {
+    pub range: BacktestRange,
+    // Archive read, or the one saved from the RPC fetch
+    pub archive: Option<String>,
+    // RPC fetch only
+    pub history: Option<FetchStats>,
+    pub pump_fun_frames: u64,
+    pub raydium_frames: u64,
+    // Everything the pipeline published, before filters and rules
+    pub events_produced: u64,
+    pub pump_fun: PlatformStats,
+    pub raydium: PlatformStats,
+    pub tokens: usize,
+    // Lines in the output file, by event type
+    pub events_written: u64,
+    pub written_by_type: BTreeMap<String, u64>,
+    pub elapsed_secs: f64,
+}
+
+/// Number of JSON lines in `path` by their `type`.
+pub fn count_output(path: &Path) -> Result<(u64, BTreeMap<String, u64>)> {
+    let mut total = 0;
+    let mut by_type = BTreeMap::new();
+    let Ok(file) = File::open(path) else {
+        return Ok((0, by_type));
+    };
+    for line in BufReader::new(file).lines() {
+        let line = line?;
+        let Ok(event) = serde_json::from_str::<Value>(&line) else { continue };
+        let kind = event.get("type").and_then(|t| t.as_str()).unwrap_or("unknown");
+        *by_type.entry(kind.to_string()).or_insert(0) += 1;
+        total += 1;
+    }
+    Ok((total, by_type))
+}
+
+/// Wait until nothing has been published for `quiet`, so enrichers finish the last launches.
+pub async fn wait_until_quiet(events: &EventHub, quiet: Duration) {
+    let step = Duration::from_millis(250);
+    let mut last = events.read(|store| store.stats().total_events);
+    let mut unchanged = Duration::ZERO;
+    while unchanged < quiet {
+        sleep(step).await;
+        let total = events.read(|store| store.stats().total_events);
+        // Enrichers still working through their queues aren't done, however quiet the hub is
+        if total == last && events.backlog() == 0 {
+            unchanged += step;
+        } else {
+            last = total;
+            unchanged = Duration::ZERO;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_unix_seconds_and_utc_dates() {
+        assert_eq!(parse_time("1700000000"), Ok(1_700_000_000));
+        assert_eq!(parse_time("1970-01-01T00:00"), Ok(0));
+        assert_eq!(parse_time("2023-11-14T22:13:20Z"), Ok(1_700_000_000));
+        assert_eq!(parse_time("2024-02-29 14:00"), Ok(1_709_215_200));
+        assert!(parse_time("2023-02-29T00:00").is_err());
+        assert!(parse_time("2024-05-01T24:00").is_err());
+        assert!(parse_time("yesterday").is_err());
+    }
+
+    #[test]
+    fn ranges_are_inclusive_and_open_ended() {
+        let slots = BacktestRange::Slots { from: 10, to: Some(20) };
+        assert!(slots.contains(Some(10), None) && slots.contains(Some(20), None));
+        assert!(slots.is_before(Some(9), None) && slots.is_after(Some(21), None));
+        // Time ranges ignore slots, and unknown times stay in
+        let time = BacktestRange::Time { from: 100, to: None };
+        assert!(time.contains(Some(1), Some(1_000_000)) && time.contains(Some(1), None));
+        assert!(time.is_before(Some(1_000), Some(99)));
+    }
+
+    #[test]
+    fn raydium_transactions_come_with_their_fetch_response() {
+        let result = json!({
+            "slot": 42,
+            "blockTime": 1_700_000_000,
+            "meta": { "err": null, "logMessages": ["Program log: Instruction: buy_exact_in"] },
+        });
+        let frames = transaction_frames("sig", &result, &[FrameSource::PumpFun, FrameSource::Raydium]);
+        let sources: Vec<FrameSource> = frames.iter().map(|frame| frame.source).collect();
+        assert_eq!(sources, [FrameSource::PumpFun, FrameSource::RaydiumTransaction, FrameSource::Raydium]);
+        assert!(frames.iter().all(|frame| frame.at_ms == 1_700_000_000_000 && frame_slot(frame) == Some(42)));
+
+        let notification: Value = serde_json::from_str(&frames[0].text).unwrap();
+        assert_eq!(notification["params"]["result"]["value"]["signature"], "sig");
+        assert_eq!(notification["params"]["result"]["value"]["logs"][0], "Program log: Instruction: buy_exact_in");
+        assert_eq!(frames[1].signature.as_deref(), Some("sig"));
+    }
+}
@@ -0,0 +1,183 @@
+use anyhow::Result;
+use serde_json::json;
+use std::sync::{Arc, Mutex};
+use tokio::time::{sleep, Duration, Instant};
+use tracing::{debug, info, warn};
+
+use crate::config::Config;
+use crate::rpc_scheduler::{Priority, RpcScheduler};
+use crate::types::{EventTiming, TxStatus};
+
+// Nominal Solana slot duration until we have measured one
+const DEFAULT_MS_PER_SLOT: f64 = 400.0;
+// Refresh the anchor this often from getBlockTime
+const REFRESH_INTERVAL_SECS: u64 = 20;
+// Ask for a slightly older slot so the block is available on every provider
+const ANCHOR_SLOT_LAG: u64 = 32;
+// Need at least this many slots between anchors to trust a measured rate
+const MIN_SLOTS_FOR_RATE: u64 = 100;
+
+#[derive(Debug, Clone, Copy)]
+struct SlotAnchor {
+    slot: u64,
+    time_ms: u64,
+}
+
+#[derive(Debug, Default)]
+struct ClockState {
+    anchor: Option<SlotAnchor>,
+    ms_per_slot: Option<f64>,
+    latest_slot: u64,
+}
+
+/// Maps slots to on-chain time: `getBlockTime` anchors plus a measured slot rate.
+#[derive(Clone)]
+pub struct BlockClock {
+    rpc: RpcScheduler,
+    state: Arc<Mutex<ClockState>>,
+}
+
+impl BlockClock {
+    pub fn new(config: &Config) -> Result<Self> {
+        Ok(Self {
+            rpc: RpcScheduler::shared(config)?.with_timeout(Duration::from_secs(5)),
+            state: Arc::new(Mutex::new(ClockState::default())),
+        })
+    }
+
+    /// Timing for a notification received right now at `slot`.
+    pub fn timing(&self, slot: Option<u64>) -> EventTiming {
+        self.timing_at(slot, now_ms())
+    }
+
+    /// Timing for a notification received at `detected_at_ms` - a recorded frame's time when replaying.
+    pub fn timing_at(&self, slot: Option<u64>, detected_at_ms: u64) -> EventTiming {
+        let block_time_ms = slot.and_then(|slot| {
+            let mut state = self.state.lock().unwrap_or_else(|poisoned| poisoned.into_inner());
+            state.latest_slot = state.latest_slot.max(slot);
+            estimate_time_ms(&state, slot)
+        });
+
+        EventTiming {
+            slot,
+            block_time_ms,
+            detected_at_ms,
+            // Monitors stamp their own commitment
+            status: TxStatus::default(),
+        }
+    }
+
+    /// Keep the anchor fresh. Runs until the process exits.
+    pub async fn run(self) {
+        info!("⏱️ Block clock started (anchor refresh every {}s)", REFRESH_INTERVAL_SECS);
+
+        loop {
+            let latest_slot = self.lock_state().latest_slot;
+            if latest_slot > ANCHOR_SLOT_LAG {
+                let slot = latest_slot - ANCHOR_SLOT_LAG;
+                match self.fetch_block_time(slot).await {
+                    Ok(Some(block_time)) => self.update_anchor(slot, block_time as u64 * 1000),
+                    Ok(None) => debug!("⏱️ No block time for slot {}", slot),
+                    Err(e) => warn!("⚠️ getBlockTime failed for slot {}: {}", slot, e),
+                }
+            }
+
+            sleep(Duration::from_secs(REFRESH_INTERVAL_SECS)).await;
+        }
+    }
+
+    async fn fetch_block_time(&self, slot: u64) -> Result<Option<i64>> {
+        let response = self.rpc.call(Priority::Launch, "getBlockTime", json!([slot])).await?;
+        Ok(response.get("result").and_then(|r| r.as_i64()))
+    }
+
+    /// Anchor on a known block time. Backtests call this instead of `run`, which asks the RPC for the current one.
+    pub fn anchor(&self, slot: u64, time_ms: u64) {
+        self.update_anchor(slot, time_ms);
+    }
+
+    fn update_anchor(&self, slot: u64, time_ms: u64) {
+        let mut state = self.lock_state();
+
+        // Measure the real slot rate between anchors that are far enough apart
+        if let Some(previous) = state.anchor {
+            if slot >= previous.slot + MIN_SLOTS_FOR_RATE && time_ms > previous.time_ms {
+                let rate = (time_ms - previous.time_ms) as f64 / (slot - previous.slot) as f64;
+                state.ms_per_slot = Some(rate);
+                debug!("⏱️ Measured slot duration: {:.1}ms", rate);
+            } else if slot < previous.slot + MIN_SLOTS_FOR_RATE {
+                return; // Keep the older anchor so the next one spans enough slots
+            }
+        }
+
+        state.anchor = Some(SlotAnchor { slot, time_ms });
+    }
+
+    fn lock_state(&self) -> std::sync::MutexGuard<'_, ClockState> {
+        self.state.lock().unwrap_or_else(|poisoned| poisoned.into_inner())
+    }
+}
+
+fn estimate_time_ms(state: &ClockState, slot: u64) -> Option<u64> {
+    let anchor = state.anchor?;
+    let ms_per_slot = state.ms_per_slot.unwrap_or(DEFAULT_MS_PER_SLOT);
+    let delta_slots = slot as f64 - anchor.slot as f64;
+    let estimate = anchor.time_ms as f64 + delta_slots * ms_per_slot;
+    (estimate > 0.0).then_some(estimate as u64)
+}
+
+/// "Now" on the clock events are stamped with: wall time live, recorded time
+/// in a replay or backtest. Follows the newest event seen and runs on with wall
+/// time in between, so deadlines set from event times still come due.
+#[derive(Debug, Default)]
+pub struct EventClock {
+    latest: Option<(u64, Instant)>,
+}
+
+impl EventClock {
+    pub fn observe(&mut self, timing: &EventTiming) {
+        if self.latest.is_none() || timing.detected_at_ms > self.now_ms() {
+            self.latest = Some((timing.detected_at_ms, Instant::now()));
+        }
+    }
+
+    pub fn now_ms(&self) -> u64 {
+        match self.latest {
+            Some((at_ms, seen)) => at_ms + seen.elapsed().as_millis() as u64,
+            None => now_ms(),
+        }
+    }
+}
+
+pub fn now_ms() -> u64 {
+    std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .map(|d| d.as_millis() as u64)
+        .unwrap_or(0)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn detected(at_ms: u64) -> EventTiming {
+        EventTiming { slot: None, block_time_ms: None, detected_at_ms: at_ms, status: TxStatus::Processed }
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn event_clock_follows_recorded_time() {
+        let mut clock = EventClock::default();
+        clock.observe(&detected(1_000_000));
+        assert_eq!(clock.now_ms(), 1_000_000);
+
+        tokio::time::advance(Duration::from_millis(250)).await;
+        assert_eq!(clock.now_ms(), 1_000_250);
+
+        // An older event does not pull the clock back
+        clock.observe(&detected(900_000));
+        assert_eq!(clock.now_ms(), 1_000_250);
+
+        clock.observe(&detected(2_000_000));
+        assert_eq!(clock.now_ms(), 2_000_000);
+    }
+}
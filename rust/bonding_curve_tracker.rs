@@ -0,0 +1,544 @@
+use anyhow::{anyhow, Result};
+use base64::{Engine as _, engine::general_purpose};
+use futures_util::{SinkExt, StreamExt, stream::SplitSink};
+use serde_json::{json, Value};
+use solana_sdk::pubkey::Pubkey;
+use std::collections::{HashMap, HashSet};
+use std::str::FromStr;
+use tokio::net::TcpStream;
+use tokio::sync::broadcast::error::RecvError;
+use tokio::sync::mpsc;
+use tokio::time::{interval, sleep, Duration, Instant, MissedTickBehavior};
+use tokio_tungstenite::{connect_async, tungstenite::protocol::Message, MaybeTlsStream, WebSocketStream};
+use tracing::{info, warn, error, debug};
+
+use crate::block_clock::BlockClock;
+use crate::config::{Commitment, Config, CurveConfig, PlatformToggles};
+use crate::event_store::{EventFeed, EventHub};
+use crate::metrics::metrics;
+use crate::rpc_scheduler::{Priority, RpcScheduler};
+use crate::launchlab_curve::{self, LaunchLabCurve, PoolState};
+use crate::pump_curve::{bonding_curve_address, global_address, BondingCurveAccount, GlobalAccount};
+use crate::types::{BondingCurveState, EventTiming, MonitorEvent, Platform};
+
+// How often idle curves are swept
+const SWEEP_INTERVAL_SECS: u64 = 30;
+// How long an unreadable LaunchLab config is left alone before it's fetched again
+const CONFIG_RETRY_SECS: u64 = 60;
+
+type WsWrite = SplitSink<WebSocketStream<MaybeTlsStream<TcpStream>>, Message>;
+
+// ========================================================================
+// TRACKER
+// ========================================================================
+
+struct TrackedCurve {
+    platform: Platform,
+    // Bonding curve PDA, or pool PDA for LaunchLab
+    bonding_curve: String,
+    subscription: Option<u64>,
+    last_update: Instant,
+}
+
+// A LaunchLab config account read in the background
+enum FetchedConfig {
+    Global(Pubkey, Result<launchlab_curve::GlobalConfig>),
+    Platform(Pubkey, Result<launchlab_curve::PlatformConfig>),
+}
+
+/// Follows each new launch's bonding curve (pump.fun) or pool (LaunchLab) over
+/// one WebSocket and publishes `Curve` events with the live reserves.
+pub struct BondingCurveTracker {
+    ws_url: String,
+    rpc: RpcScheduler,
+    // Curve parameters and fees - mainnet defaults until the account is read
+    global: GlobalAccount,
+    // LaunchLab pool configs and platform configs, read once per address
+    launchlab_configs: HashMap<Pubkey, launchlab_curve::GlobalConfig>,
+    launchlab_platforms: HashMap<Pubkey, launchlab_curve::PlatformConfig>,
+    // Config reads run off the WebSocket loop and report back here
+    fetched_tx: mpsc::UnboundedSender<FetchedConfig>,
+    fetched_rx: mpsc::UnboundedReceiver<FetchedConfig>,
+    fetching: HashSet<Pubkey>,
+    // config address -> when its last read failed
+    failed_configs: HashMap<Pubkey, Instant>,
+    // mint -> latest pool update still waiting on its configs
+    awaiting_configs: HashMap<String, (PoolState, EventTiming)>,
+    commitment: Commitment,
+    platforms: PlatformToggles,
+    settings: CurveConfig,
+    events: EventHub,
+    block_clock: BlockClock,
+    receiver: EventFeed,
+    // mint -> curve
+    curves: HashMap<String, TrackedCurve>,
+    // request id -> mint, until the subscription id arrives
+    pending: HashMap<u64, String>,
+    // subscription id -> mint
+    subscriptions: HashMap<u64, String>,
+    next_request_id: u64,
+}
+
+impl BondingCurveTracker {
+    /// Subscribes to the hub immediately so no launch is missed before `run`.
+    pub fn new(config: &Config, events: EventHub, feed: EventFeed, block_clock: BlockClock) -> Result<Self> {
+        let (fetched_tx, fetched_rx) = mpsc::unbounded_channel();
+        Ok(Self {
+            ws_url: config.ws_url(),
+            rpc: RpcScheduler::shared(config)?.with_timeout(Duration::from_secs(5)),
+            global: GlobalAccount::default(),
+            launchlab_configs: HashMap::new(),
+            launchlab_platforms: HashMap::new(),
+            fetched_tx,
+            fetched_rx,
+            fetching: HashSet::new(),
+            failed_configs: HashMap::new(),
+            awaiting_configs: HashMap::new(),
+            commitment: config.commitment,
+            platforms: config.platforms.clone(),
+            settings: config.curves.clone(),
+            receiver: feed,
+            events,
+            block_clock,
+            curves: HashMap::new(),
+            pending: HashMap::new(),
+            subscriptions: HashMap::new(),
+            next_request_id: 1,
+        })
+    }
+
+    pub async fn run(mut self) {
+        info!("📈 Bonding curve tracker started (max {} curves)", self.settings.max_tracked);
+
+        loop {
+            if self.platforms.pump_fun {
+                if let Err(e) = self.refresh_global().await {
+                    warn!("⚠️ Couldn't read pump.fun Global account, using defaults: {}", e);
+                }
+            }
+
+            match self.run_connection().await {
+                Ok(()) => info!("🔌 Curve tracker WebSocket closed"),
+                Err(e) => error!("❌ Curve tracker WebSocket error: {}", e),
+            }
+
+            // Subscription ids die with the connection - curves are resubscribed on reconnect
+            self.pending.clear();
+            self.subscriptions.clear();
+            for curve in self.curves.values_mut() {
+                curve.subscription = None;
+            }
+
+            sleep(Duration::from_secs(self.settings.reconnect_delay_secs)).await;
+        }
+    }
+
+    async fn refresh_global(&mut self) -> Result<()> {
+        let data = fetch_account(&self.rpc, &global_address()).await?;
+        self.global = GlobalAccount::decode(&data)?;
+        debug!("📈 Pump.fun fees: {} bps", self.global.total_fee_bps());
+        Ok(())
+    }
+
+    /// Curve type and fees for a LaunchLab pool, cached per config account.
+    /// `None` while a config is still being read - the fetch is started here
+    /// and the pool update is quoted once it lands.
+    fn launchlab_curve(&mut self, pool: &PoolState) -> Option<LaunchLabCurve> {
+        let config = self.launchlab_configs.get(&pool.global_config).cloned();
+        if config.is_none() {
+            self.spawn_fetch(pool.global_config, false);
+        }
+        // Platform fee is optional for quoting - after a failed read, fall back to protocol fee only
+        let platform = self.launchlab_platforms.get(&pool.platform_config).cloned();
+        let platform_settled = platform.is_some() || self.failed_configs.contains_key(&pool.platform_config);
+        if !platform_settled {
+            self.spawn_fetch(pool.platform_config, true);
+        }
+
+        match config {
+            Some(config) if platform_settled => Some(LaunchLabCurve::new(pool.clone(), &config, platform.as_ref())),
+            _ => None,
+        }
+    }
+
+    fn spawn_fetch(&mut self, address: Pubkey, platform: bool) {
+        let backing_off = self.failed_configs.get(&address)
+            .is_some_and(|failed| failed.elapsed() < Duration::from_secs(CONFIG_RETRY_SECS));
+        if backing_off || !self.fetching.insert(address) {
+            return;
+        }
+        let rpc = self.rpc.clone();
+        let fetched = self.fetched_tx.clone();
+        tokio::spawn(async move {
+            let data = fetch_account(&rpc, &address).await;
+            let _ = fetched.send(if platform {
+                FetchedConfig::Platform(address, data.and_then(|data| launchlab_curve::PlatformConfig::decode(&data)))
+            } else {
+                FetchedConfig::Global(address, data.and_then(|data| launchlab_curve::GlobalConfig::decode(&data)))
+            });
+        });
+    }
+
+    async fn handle_fetched(&mut self, write: &mut WsWrite, fetched: FetchedConfig) -> Result<()> {
+        let address = match fetched {
+            FetchedConfig::Global(address, Ok(config)) => {
+                debug!("📈 LaunchLab config {}: {:?} curve, {} ppm fee", address, config.curve_type, config.trade_fee_rate);
+                self.launchlab_configs.insert(address, config);
+                address
+            },
+            FetchedConfig::Platform(address, Ok(platform)) => {
+                debug!("📈 LaunchLab platform {}: {} ({} ppm fee)", address, platform.name, platform.fee_rate);
+                self.launchlab_platforms.insert(address, platform);
+                address
+            },
+            FetchedConfig::Global(address, Err(e)) => {
+                metrics().decode_error(Platform::Raydium, &e);
+                warn!("⚠️ Couldn't read LaunchLab config {}, retrying in {}s: {}", address, CONFIG_RETRY_SECS, e);
+                self.failed_configs.insert(address, Instant::now());
+                address
+            },
+            FetchedConfig::Platform(address, Err(e)) => {
+                warn!("⚠️ Couldn't read LaunchLab platform config {}, retrying in {}s: {}", address, CONFIG_RETRY_SECS, e);
+                self.failed_configs.insert(address, Instant::now());
+                address
+            },
+        };
+        self.fetching.remove(&address);
+        if self.launchlab_configs.contains_key(&address) || self.launchlab_platforms.contains_key(&address) {
+            self.failed_configs.remove(&address);
+        }
+
+        // Quote whatever was waiting on this account
+        let waiting: Vec<String> = self.awaiting_configs.iter()
+            .filter(|(_, (pool, _))| pool.global_config == address || pool.platform_config == address)
+            .map(|(mint, _)| mint.clone())
+            .collect();
+        for mint in waiting {
+            let Some((pool, timing)) = self.awaiting_configs.remove(&mint) else { continue };
+            match self.launchlab_curve(&pool) {
+                Some(curve) => self.publish_launchlab(write, &mint, &curve, timing).await?,
+                None => {
+                    self.awaiting_configs.insert(mint, (pool, timing));
+                },
+            }
+        }
+        Ok(())
+    }
+
+    async fn run_connection(&mut self) -> Result<()> {
+        let (ws_stream, _) = connect_async(&self.ws_url).await?;
+        let (mut write, mut read) = ws_stream.split();
+
+        let mints: Vec<String> = self.curves.keys().cloned().collect();
+        for mint in mints {
+            self.subscribe(&mut write, &mint).await?;
+        }
+
+        let mut sweep = interval(Duration::from_secs(SWEEP_INTERVAL_SECS));
+        sweep.set_missed_tick_behavior(MissedTickBehavior::Skip);
+
+        loop {
+            tokio::select! {
+                message = read.next() => match message {
+                    Some(Ok(Message::Text(text))) => {
+                        if let Err((platform, e)) = self.handle_message(&mut write, &text).await {
+                            metrics().decode_error(platform, &e);
+                            warn!("⚠️ Error processing curve update: {}", e);
+                        }
+                    },
+                    Some(Ok(Message::Close(_))) | None => return Ok(()),
+                    Some(Ok(_)) => {},
+                    Some(Err(e)) => return Err(e.into()),
+                },
+                event = self.receiver.recv() => match event {
+                    Ok(event) => self.handle_event(&mut write, &event).await?,
+                    Err(RecvError::Lagged(skipped)) => warn!("⚠️ Curve tracker lagging, skipped {} events", skipped),
+                    Err(RecvError::Closed) => return Ok(()),
+                },
+                Some(fetched) = self.fetched_rx.recv() => self.handle_fetched(&mut write, fetched).await?,
+                _ = sweep.tick() => self.sweep_idle(&mut write).await?,
+            }
+        }
+    }
+
+    async fn handle_event(&mut self, write: &mut WsWrite, event: &MonitorEvent) -> Result<()> {
+        match event {
+            MonitorEvent::Launch(launch) => {
+                self.start_tracking(write, &launch.contract_address, launch.platform).await
+            },
+            MonitorEvent::Completion(_) | MonitorEvent::Migration(_) | MonitorEvent::Retraction(_)
+                if self.curves.contains_key(event.mint()) =>
+            {
+                self.stop_tracking(write, event.mint()).await
+            },
+            _ => Ok(()),
+        }
+    }
+
+    async fn start_tracking(&mut self, write: &mut WsWrite, mint: &str, platform: Platform) -> Result<()> {
+        let enabled = match platform {
+            Platform::PumpFun => self.platforms.pump_fun,
+            Platform::Raydium => self.platforms.raydium,
+        };
+        if !enabled || self.curves.contains_key(mint) {
+            return Ok(());
+        }
+        // One bad mint mustn't take the socket (and every other curve) down with it
+        let Ok(mint_key) = Pubkey::from_str(mint) else {
+            debug!("📈 Not tracking unparsable mint {}", mint);
+            return Ok(());
+        };
+
+        // At the cap: newest launches matter most, drop the curve that has been quiet longest
+        if self.curves.len() >= self.settings.max_tracked {
+            let idlest = self.curves.iter()
+                .min_by_key(|(_, curve)| curve.last_update)
+                .map(|(mint, _)| mint.clone());
+            match idlest {
+                Some(idlest) => self.stop_tracking(write, &idlest).await?,
+                None => return Ok(()), // max_tracked = 0
+            }
+        }
+
+        let bonding_curve = match platform {
+            Platform::PumpFun => bonding_curve_address(&mint_key),
+            // SOL-quoted pools only; other quote mints never get an account update and idle out
+            Platform::Raydium => launchlab_curve::sol_pool_address(&mint_key),
+        };
+        self.curves.insert(mint.to_string(), TrackedCurve {
+            platform,
+            bonding_curve: bonding_curve.to_string(),
+            subscription: None,
+            last_update: Instant::now(),
+        });
+        self.report_size(platform);
+
+        self.subscribe(write, mint).await
+    }
+
+    async fn stop_tracking(&mut self, write: &mut WsWrite, mint: &str) -> Result<()> {
+        self.awaiting_configs.remove(mint);
+        let Some(curve) = self.curves.remove(mint) else { return Ok(()) };
+        self.report_size(curve.platform);
+        debug!("📈 Stopped tracking curve for {}", mint);
+
+        // Still-pending subscribes are unsubscribed when their id arrives
+        if let Some(subscription) = curve.subscription {
+            self.subscriptions.remove(&subscription);
+            self.unsubscribe(write, subscription).await?;
+        }
+        Ok(())
+    }
+
+    fn report_size(&self, platform: Platform) {
+        let tracked = self.curves.values().filter(|curve| curve.platform == platform).count();
+        metrics().cache_size(platform, "tracked_curves", tracked);
+    }
+
+    async fn sweep_idle(&mut self, write: &mut WsWrite) -> Result<()> {
+        let idle_timeout = Duration::from_secs(self.settings.idle_timeout_secs);
+        let idle: Vec<String> = self.curves.iter()
+            .filter(|(_, curve)| curve.last_update.elapsed() >= idle_timeout)
+            .map(|(mint, _)| mint.clone())
+            .collect();
+
+        for mint in idle {
+            self.stop_tracking(write, &mint).await?;
+        }
+        Ok(())
+    }
+
+    async fn subscribe(&mut self, write: &mut WsWrite, mint: &str) -> Result<()> {
+        let Some(curve) = self.curves.get(mint) else { return Ok(()) };
+        let id = self.next_request_id;
+        self.next_request_id += 1;
+
+        let request = json!({
+            "jsonrpc": "2.0",
+            "id": id,
+            "method": "accountSubscribe",
+            "params": [
+                curve.bonding_curve,
+                {
+                    "encoding": "base64",
+                    "commitment": self.commitment.as_str()
+                }
+            ]
+        });
+        write.send(Message::Text(request.to_string())).await?;
+        self.pending.insert(id, mint.to_string());
+        Ok(())
+    }
+
+    async fn unsubscribe(&mut self, write: &mut WsWrite, subscription: u64) -> Result<()> {
+        let id = self.next_request_id;
+        self.next_request_id += 1;
+
+        let request = json!({
+            "jsonrpc": "2.0",
+            "id": id,
+            "method": "accountUnsubscribe",
+            "params": [subscription]
+        });
+        write.send(Message::Text(request.to_string())).await?;
+        Ok(())
+    }
+
+    async fn handle_message(&mut self, write: &mut WsWrite, message: &str) -> Result<(), (Platform, anyhow::Error)> {
+        let data: Value = serde_json::from_str(message).map_err(|e| (Platform::PumpFun, e.into()))?;
+
+        // Subscribe/unsubscribe responses
+        if let Some(id) = data.get("id").and_then(|id| id.as_u64()) {
+            return self.handle_response(write, id, &data).await.map_err(|e| (Platform::PumpFun, e));
+        }
+
+        if data.get("method").and_then(|m| m.as_str()) != Some("accountNotification") {
+            return Ok(());
+        }
+
+        let subscription = data.get("params").and_then(|p| p.get("subscription")).and_then(|s| s.as_u64());
+        let Some(mint) = subscription.and_then(|s| self.subscriptions.get(&s)).cloned() else { return Ok(()) };
+        let Some(platform) = self.curves.get(&mint).map(|curve| curve.platform) else { return Ok(()) };
+
+        self.handle_notification(write, &mint, platform, &data["params"]).await.map_err(|e| (platform, e))
+    }
+
+    async fn handle_response(&mut self, write: &mut WsWrite, id: u64, data: &Value) -> Result<()> {
+        let Some(mint) = self.pending.remove(&id) else { return Ok(()) };
+        if let Some(error) = data.get("error") {
+            warn!("⚠️ accountSubscribe failed for {}: {}", mint, error);
+            if let Some(curve) = self.curves.remove(&mint) {
+                self.report_size(curve.platform);
+            }
+            return Ok(());
+        }
+        let subscription = data.get("result").and_then(|r| r.as_u64())
+            .ok_or_else(|| anyhow!("missing subscription id"))?;
+
+        match self.curves.get_mut(&mint) {
+            Some(curve) => {
+                curve.subscription = Some(subscription);
+                self.subscriptions.insert(subscription, mint);
+            },
+            // Stopped tracking while the subscribe was in flight
+            None => self.unsubscribe(write, subscription).await?,
+        }
+        Ok(())
+    }
+
+    async fn handle_notification(&mut self, write: &mut WsWrite, mint: &str, platform: Platform, params: &Value) -> Result<()> {
+        let result = params.get("result").ok_or_else(|| anyhow!("missing result"))?;
+        let slot = result.get("context").and_then(|c| c.get("slot")).and_then(|s| s.as_u64());
+        let timing = self.block_clock.timing(slot);
+
+        // Account closed
+        let value = result.get("value").filter(|v| !v.is_null());
+        let Some(value) = value else {
+            return self.stop_tracking(write, mint).await;
+        };
+
+        let encoded = value.get("data").and_then(|d| d.get(0)).and_then(|d| d.as_str())
+            .ok_or_else(|| anyhow!("missing account data"))?;
+        let data = general_purpose::STANDARD.decode(encoded)?;
+
+        let Some(bonding_curve) = self.curves.get(mint).map(|curve| curve.bonding_curve.clone()) else { return Ok(()) };
+        let state = match platform {
+            Platform::PumpFun => pump_state((mint.to_string(), bonding_curve, timing), &BondingCurveAccount::decode(&data)?, &self.global),
+            Platform::Raydium => {
+                let pool = PoolState::decode(&data)?;
+                match self.launchlab_curve(&pool) {
+                    Some(curve) => launchlab_state((mint.to_string(), bonding_curve, timing), &curve),
+                    None => {
+                        // Newer update supersedes whatever was already waiting
+                        self.awaiting_configs.insert(mint.to_string(), (pool, timing));
+                        return Ok(());
+                    },
+                }
+            },
+        };
+        self.publish_state(write, mint, state).await
+    }
+
+    async fn publish_launchlab(&mut self, write: &mut WsWrite, mint: &str, curve: &LaunchLabCurve, timing: EventTiming) -> Result<()> {
+        let Some(bonding_curve) = self.curves.get(mint).map(|curve| curve.bonding_curve.clone()) else { return Ok(()) };
+        let state = launchlab_state((mint.to_string(), bonding_curve, timing), curve);
+        self.publish_state(write, mint, state).await
+    }
+
+    async fn publish_state(&mut self, write: &mut WsWrite, mint: &str, state: BondingCurveState) -> Result<()> {
+        let platform = state.platform;
+        if let Some(curve) = self.curves.get_mut(mint) {
+            curve.last_update = Instant::now();
+        }
+        debug!("📈 [{}] {:.1}% | CA: {} | Real SOL: {}", state.platform, state.progress_pct, mint, state.real_sol_reserves);
+        let complete = state.complete;
+        self.events.publish(MonitorEvent::Curve(state));
+
+        if complete {
+            info!("🏁 [{}] Curve complete, no longer tracking | CA: {}", platform, mint);
+            self.stop_tracking(write, mint).await?;
+        }
+        Ok(())
+    }
+}
+
+async fn fetch_account(rpc: &RpcScheduler, address: &Pubkey) -> Result<Vec<u8>> {
+    let response = rpc.call(Priority::Launch, "getAccountInfo", json!([
+        address.to_string(), { "encoding": "base64" }
+    ])).await?;
+    let encoded = response
+        .get("result")
+        .and_then(|r| r.get("value"))
+        .and_then(|v| v.get("data"))
+        .and_then(|d| d.get(0))
+        .and_then(|d| d.as_str())
+        .ok_or_else(|| anyhow!("missing account data for {}", address))?;
+
+    Ok(general_purpose::STANDARD.decode(encoded)?)
+}
+
+// (mint, bonding curve / pool address, timing)
+type CurveIdentity = (String, String, EventTiming);
+
+fn pump_state((mint, bonding_curve, timing): CurveIdentity, account: &BondingCurveAccount, global: &GlobalAccount) -> BondingCurveState {
+    let derived = account.metrics(global);
+    BondingCurveState {
+        mint,
+        bonding_curve,
+        virtual_token_reserves: account.virtual_token_reserves,
+        virtual_sol_reserves: account.virtual_sol_reserves,
+        real_token_reserves: account.real_token_reserves,
+        real_sol_reserves: account.real_sol_reserves,
+        token_total_supply: account.token_total_supply,
+        complete: account.complete,
+        progress_pct: derived.progress_pct,
+        price_sol: derived.price_sol,
+        market_cap_sol: derived.market_cap_sol,
+        sol_to_complete: derived.sol_to_complete,
+        platform: Platform::PumpFun,
+        timestamp: timing.detected_at_ms / 1000,
+        timing,
+    }
+}
+
+// LaunchLab counts tokens sold up from zero; pump.fun counts tokens left down
+fn launchlab_state((mint, bonding_curve, timing): CurveIdentity, curve: &LaunchLabCurve) -> BondingCurveState {
+    let pool = &curve.pool;
+    BondingCurveState {
+        mint,
+        bonding_curve,
+        virtual_token_reserves: pool.virtual_a,
+        virtual_sol_reserves: pool.virtual_b,
+        real_token_reserves: curve.remaining_a(),
+        real_sol_reserves: pool.real_b,
+        token_total_supply: pool.supply,
+        complete: pool.status != launchlab_curve::PoolStatus::Trading,
+        progress_pct: curve.progress_pct(),
+        price_sol: curve.price(),
+        market_cap_sol: curve.market_cap(),
+        sol_to_complete: curve.quote_to_migrate(),
+        platform: Platform::Raydium,
+        timestamp: timing.detected_at_ms / 1000,
+        timing,
+    }
+}
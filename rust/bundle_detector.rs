@@ -0,0 +1,513 @@
+use anyhow::Result;
+use serde_json::{json, Value};
+use std::collections::{BTreeMap, HashMap, HashSet};
+use tokio::sync::broadcast::error::RecvError;
+use tokio::time::{interval, Duration, MissedTickBehavior};
+use tracing::{info, warn, debug};
+
+use crate::block_clock::EventClock;
+use crate::config::{BundleConfig, Commitment, Config};
+use crate::event_store::{EventFeed, EventHub};
+use crate::pump_curve::GlobalAccount;
+use crate::rpc_scheduler::{Priority, RpcScheduler};
+use crate::types::{
+    BundleReport, BundleWallet, EventTiming, FundingCluster, MonitorEvent, Platform, TokenLaunch, TradeEvent,
+};
+
+const CHECK_INTERVAL_MS: u64 = 250;
+
+// ========================================================================
+// EARLY BUYS
+// ========================================================================
+
+struct EarlyTrade {
+    wallet: String,
+    slot: Option<u64>,
+    signature: String,
+    // Positive for buys, negative for sells
+    token_delta: i128,
+}
+
+struct PendingLaunch {
+    mint: String,
+    creator: Option<String>,
+    platform: Platform,
+    launch_slot: Option<u64>,
+    launch_signature: String,
+    // pump.fun launches are the create transaction. A LaunchLab "launch" is the first
+    // buy we fetched, so its tx/slot say nothing about being next to the creator
+    marks_create: bool,
+    deadline_ms: u64,
+    timing: EventTiming,
+    trades: Vec<EarlyTrade>,
+}
+
+impl PendingLaunch {
+    fn new(launch: &TokenLaunch, window_ms: u64) -> Self {
+        Self {
+            mint: launch.contract_address.clone(),
+            creator: launch.creator.clone(),
+            platform: launch.platform,
+            launch_slot: launch.timing.slot,
+            launch_signature: launch.signature.clone(),
+            marks_create: launch.platform == Platform::PumpFun,
+            deadline_ms: launch.timing.detected_at_ms + window_ms,
+            timing: launch.timing.clone(),
+            trades: Vec::new(),
+        }
+    }
+
+    /// Trades in the create transaction, or within `early_slots` of the create slot.
+    fn push(&mut self, trade: &TradeEvent, early_slots: u64) {
+        let Some(wallet) = &trade.trader else { return };
+        let same_tx = trade.signature == self.launch_signature;
+        let in_window = match (self.launch_slot, trade.timing.slot) {
+            (Some(launch_slot), Some(slot)) => slot <= launch_slot + early_slots,
+            _ => same_tx,
+        };
+        if !in_window {
+            return;
+        }
+        let amount = trade.token_amount.unwrap_or(0) as i128;
+        self.trades.push(EarlyTrade {
+            wallet: wallet.clone(),
+            slot: trade.timing.slot,
+            signature: trade.signature.clone(),
+            token_delta: if trade.is_buy { amount } else { -amount },
+        });
+    }
+
+    /// Per-wallet view of the early trades, buyers only.
+    fn wallets(&self) -> Vec<BundleWallet> {
+        let mut wallets: BTreeMap<&str, BundleWallet> = BTreeMap::new();
+        let mut held: HashMap<&str, i128> = HashMap::new();
+        for trade in &self.trades {
+            *held.entry(&trade.wallet).or_default() += trade.token_delta;
+            if trade.token_delta <= 0 {
+                continue;
+            }
+            let wallet = wallets.entry(&trade.wallet).or_insert_with(|| BundleWallet {
+                wallet: trade.wallet.clone(),
+                slot: trade.slot,
+                same_tx: false,
+                same_slot: false,
+                is_creator: self.creator.as_deref() == Some(trade.wallet.as_str()),
+                token_amount: 0,
+                supply_pct: None,
+                funder: None,
+            });
+            wallet.slot = match (wallet.slot, trade.slot) {
+                (Some(a), Some(b)) => Some(a.min(b)),
+                (a, b) => a.or(b),
+            };
+            if self.marks_create {
+                wallet.same_tx |= trade.signature == self.launch_signature;
+                wallet.same_slot |= self.launch_slot.is_some() && trade.slot == self.launch_slot;
+            }
+        }
+        for wallet in wallets.values_mut() {
+            wallet.token_amount = held.get(wallet.wallet.as_str()).copied().unwrap_or(0).max(0) as u64;
+        }
+        wallets.into_values().collect()
+    }
+}
+
+// ========================================================================
+// SCORING
+// ========================================================================
+
+pub struct ScoredBundle {
+    pub wallets: Vec<BundleWallet>,
+    pub clusters: Vec<FundingCluster>,
+    pub bundled_supply_pct: f64,
+    pub bundle_score: f64,
+}
+
+/// Wallets in the create slot/tx, plus anything sharing a funder with them, as one bundle.
+pub fn score_bundle(
+    launch_wallets: Vec<BundleWallet>,
+    funders: &HashMap<String, String>,
+    creator: Option<&str>,
+    supply: u64,
+) -> ScoredBundle {
+    let pct = |amount: u64| (supply > 0).then(|| amount as f64 / supply as f64 * 100.0);
+    let mut wallets = launch_wallets;
+    for wallet in &mut wallets {
+        wallet.funder = funders.get(&wallet.wallet).cloned();
+        wallet.supply_pct = pct(wallet.token_amount);
+    }
+
+    // Group by funder; a wallet funded by the creator joins the creator's group
+    let mut groups: BTreeMap<&str, Vec<&BundleWallet>> = BTreeMap::new();
+    for wallet in &wallets {
+        if let Some(funder) = &wallet.funder {
+            groups.entry(funder).or_default().push(wallet);
+        }
+    }
+    let clusters: Vec<FundingCluster> = groups
+        .into_iter()
+        .filter_map(|(funder, members)| {
+            let creator_funded = creator == Some(funder);
+            let mut addresses: Vec<String> = members.iter().map(|w| w.wallet.clone()).collect();
+            let mut amount: u64 = members.iter().map(|w| w.token_amount).sum();
+            if creator_funded {
+                if let Some(creator_wallet) = wallets.iter().find(|w| w.is_creator && !addresses.contains(&w.wallet)) {
+                    addresses.push(creator_wallet.wallet.clone());
+                    amount += creator_wallet.token_amount;
+                }
+            }
+            (addresses.len() >= 2 || creator_funded).then(|| FundingCluster {
+                funder: funder.to_string(),
+                creator_funded,
+                wallets: addresses,
+                supply_pct: pct(amount),
+            })
+        })
+        .collect();
+
+    let mut bundle: HashSet<&str> = wallets.iter().filter(|w| w.same_tx || w.same_slot).map(|w| w.wallet.as_str()).collect();
+    for cluster in &clusters {
+        if cluster.creator_funded || cluster.wallets.iter().any(|w| bundle.contains(w.as_str())) {
+            bundle.extend(cluster.wallets.iter().map(String::as_str));
+        }
+    }
+    let bundled_amount: u64 = wallets.iter().filter(|w| bundle.contains(w.wallet.as_str())).map(|w| w.token_amount).sum();
+    let bundled_supply_pct = pct(bundled_amount).unwrap_or(0.0);
+
+    // Snipers next to the dev, how much they took, whether they share a wallet, and who got into the create tx
+    let others_in_slot = wallets.iter().filter(|w| !w.is_creator && (w.same_slot || w.same_tx)).count() as f64;
+    let clustered = clusters.iter().map(|c| c.wallets.len()).sum::<usize>() as f64;
+    let others_in_tx = wallets.iter().any(|w| !w.is_creator && w.same_tx);
+    let bundle_score = 35.0 * (others_in_slot / 4.0).min(1.0)
+        + 35.0 * (bundled_supply_pct / 25.0).min(1.0)
+        + 20.0 * (clustered / 3.0).min(1.0)
+        + if others_in_tx { 10.0 } else { 0.0 };
+
+    ScoredBundle { wallets, clusters, bundled_supply_pct, bundle_score }
+}
+
+// ========================================================================
+// FUNDING SOURCES
+// ========================================================================
+
+#[derive(Clone)]
+struct FundingResolver {
+    rpc: RpcScheduler,
+    commitment: Commitment,
+    lookback: usize,
+}
+
+impl FundingResolver {
+    /// Sender of the first SOL transfer into the wallet, within the last `lookback` signatures.
+    /// Fresh sniper wallets have few transactions, so that's usually their funding.
+    async fn funder_of(&self, wallet: &str) -> Result<Option<String>> {
+        let signatures = self.rpc.result(Priority::Launch, "getSignaturesForAddress", json!([
+            wallet, { "limit": self.lookback, "commitment": self.commitment.as_str() }
+        ])).await?;
+        let Some(oldest) = signatures.as_array().and_then(|s| s.last()).and_then(|s| s.get("signature")).and_then(|s| s.as_str()) else {
+            return Ok(None);
+        };
+
+        let transaction = self.rpc.result(Priority::Launch, "getTransaction", json!([
+            oldest, { "encoding": "jsonParsed", "commitment": self.commitment.as_str(), "maxSupportedTransactionVersion": 0 }
+        ])).await?;
+        Ok(sol_funder(&transaction, wallet))
+    }
+}
+
+// System transfer into `wallet`, top-level or inner
+fn sol_funder(transaction: &Value, wallet: &str) -> Option<String> {
+    let top = transaction.get("transaction")?.get("message")?.get("instructions")?.as_array()?;
+    let inner = transaction.get("meta")
+        .and_then(|meta| meta.get("innerInstructions"))
+        .and_then(|inner| inner.as_array())
+        .into_iter()
+        .flatten()
+        .filter_map(|group| group.get("instructions").and_then(|i| i.as_array()))
+        .flatten();
+    top.iter().chain(inner).find_map(|instruction| {
+        if instruction.get("program")?.as_str()? != "system" {
+            return None;
+        }
+        let parsed = instruction.get("parsed")?;
+        let kind = parsed.get("type")?.as_str()?;
+        if kind != "transfer" && kind != "transferWithSeed" {
+            return None;
+        }
+        let info = parsed.get("info")?;
+        if info.get("destination")?.as_str()? != wallet {
+            return None;
+        }
+        info.get("source")?.as_str().filter(|source| *source != wallet).map(str::to_string)
+    })
+}
+
+// ========================================================================
+// DETECTOR
+// ========================================================================
+
+/// Groups the buys in the first slots after each launch and publishes a `Bundle` report with a score.
+pub struct BundleDetector {
+    settings: BundleConfig,
+    resolver: FundingResolver,
+    events: EventHub,
+    receiver: EventFeed,
+    pending: HashMap<String, PendingLaunch>,
+    // Deadlines run on event time, so backtests score launches like live ones
+    clock: EventClock,
+}
+
+impl BundleDetector {
+    pub fn new(config: &Config, events: EventHub, feed: EventFeed) -> Result<Self> {
+        Ok(Self {
+            resolver: FundingResolver {
+                rpc: RpcScheduler::shared(config)?.with_timeout(Duration::from_secs(5)),
+                // getTransaction doesn't accept "processed"
+                commitment: match config.commitment {
+                    Commitment::Finalized => Commitment::Finalized,
+                    _ => Commitment::Confirmed,
+                },
+                lookback: config.bundles.funding_lookback,
+            },
+            settings: config.bundles.clone(),
+            receiver: feed,
+            events,
+            pending: HashMap::new(),
+            clock: EventClock::default(),
+        })
+    }
+
+    pub async fn run(mut self) {
+        info!(
+            "🎯 Bundle detector started (first {} slots, score ≥ {:.0} = bundled)",
+            self.settings.early_slots, self.settings.score_threshold
+        );
+
+        let mut ticker = interval(Duration::from_millis(CHECK_INTERVAL_MS));
+        ticker.set_missed_tick_behavior(MissedTickBehavior::Delay);
+
+        loop {
+            tokio::select! {
+                event = self.receiver.recv() => match event {
+                    Ok(event) => self.handle_event(&event),
+                    Err(RecvError::Lagged(skipped)) => warn!("⚠️ Bundle detector lagging, skipped {} events", skipped),
+                    Err(RecvError::Closed) => break,
+                },
+                _ = ticker.tick() => self.evaluate_due(self.clock.now_ms()),
+            }
+        }
+    }
+
+    fn handle_event(&mut self, event: &MonitorEvent) {
+        if matches!(event, MonitorEvent::Launch(_) | MonitorEvent::Trade(_)) {
+            self.clock.observe(event.timing());
+        }
+        match event {
+            MonitorEvent::Launch(launch) => self.track(launch),
+            MonitorEvent::Trade(trade) => {
+                if let Some(pending) = self.pending.get_mut(&trade.mint) {
+                    pending.push(trade, self.settings.early_slots);
+                }
+            },
+            MonitorEvent::Retraction(retraction) => {
+                if let Some(pending) = self.pending.get_mut(&retraction.mint) {
+                    pending.trades.retain(|trade| trade.signature != retraction.signature);
+                }
+            },
+            _ => {},
+        }
+    }
+
+    fn track(&mut self, launch: &TokenLaunch) {
+        if self.pending.len() >= self.settings.max_pending || self.pending.contains_key(&launch.contract_address) {
+            return;
+        }
+        let window_ms = self.settings.window_secs * 1000;
+        self.pending.insert(launch.contract_address.clone(), PendingLaunch::new(launch, window_ms));
+    }
+
+    fn evaluate_due(&mut self, now_ms: u64) {
+        let due: Vec<String> = self.pending.iter()
+            .filter(|(_, pending)| pending.deadline_ms <= now_ms)
+            .map(|(mint, _)| mint.clone())
+            .collect();
+        for mint in due {
+            let Some(mut pending) = self.pending.remove(&mint) else { continue };
+            if pending.trades.is_empty() {
+                continue;
+            }
+            // LaunchLab creators arrive with the identity, after the launch
+            let record = self.events.read(|store| store.token(&mint));
+            if pending.creator.is_none() {
+                pending.creator = record.as_ref().and_then(|r| r.launch.creator.clone());
+            }
+            let supply = record.as_ref()
+                .and_then(|r| r.identity.as_ref().map(|i| i.supply).or(r.curve.as_ref().map(|c| c.token_total_supply)))
+                .filter(|supply| *supply > 0)
+                .unwrap_or(GlobalAccount::default().token_total_supply);
+
+            tokio::spawn(evaluate(pending, supply, now_ms, self.resolver.clone(), self.settings.clone(), self.events.clone()));
+        }
+    }
+}
+
+async fn evaluate(pending: PendingLaunch, supply: u64, due_ms: u64, resolver: FundingResolver, settings: BundleConfig, events: EventHub) {
+    let wallets = pending.wallets();
+    if wallets.is_empty() {
+        return;
+    }
+
+    // Slot/tx buyers first - they decide whether it's a bundle
+    let mut funders = HashMap::new();
+    if settings.resolve_funding {
+        let mut lookups: Vec<&BundleWallet> = wallets.iter().filter(|w| !w.is_creator).collect();
+        lookups.sort_by_key(|w| !(w.same_tx || w.same_slot));
+        for wallet in lookups.into_iter().take(settings.max_funding_lookups) {
+            match resolver.funder_of(&wallet.wallet).await {
+                Ok(Some(funder)) => { funders.insert(wallet.wallet.clone(), funder); },
+                Ok(None) => {},
+                Err(e) => debug!("⚠️ Funding lookup failed for {}: {}", wallet.wallet, e),
+            }
+        }
+    }
+
+    let scored = score_bundle(wallets, &funders, pending.creator.as_deref(), supply);
+    let report = BundleReport {
+        mint: pending.mint,
+        creator: pending.creator,
+        launch_slot: pending.launch_slot,
+        same_slot_buyers: scored.wallets.iter().filter(|w| w.same_slot).count() as u32,
+        same_tx_buyers: scored.wallets.iter().filter(|w| w.same_tx).count() as u32,
+        wallets: scored.wallets,
+        clusters: scored.clusters,
+        bundled_supply_pct: scored.bundled_supply_pct,
+        bundle_score: scored.bundle_score,
+        bundled: scored.bundle_score >= settings.score_threshold,
+        platform: pending.platform,
+        timestamp: due_ms / 1000,
+        timing: pending.timing,
+    };
+
+    let message = format!(
+        "[{}] CA: {} | Score: {:.0} | {} same-slot / {} same-tx buyers | {} clusters | {:.1}% of supply",
+        report.platform, report.mint, report.bundle_score, report.same_slot_buyers, report.same_tx_buyers,
+        report.clusters.len(), report.bundled_supply_pct
+    );
+    if report.bundled {
+        warn!("📦 Bundled launch {}", message);
+    } else {
+        debug!("📦 {}", message);
+    }
+    events.publish(MonitorEvent::Bundle(report));
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn launch() -> TokenLaunch {
+        TokenLaunch {
+            contract_address: "mint".to_string(),
+            name: None,
+            symbol: None,
+            creator: Some("dev".to_string()),
+            uri: None,
+            signature: "create".to_string(),
+            platform: Platform::PumpFun,
+            timestamp: 0,
+            timing: EventTiming { slot: Some(100), detected_at_ms: 0, ..Default::default() },
+        }
+    }
+
+    fn buy(wallet: &str, signature: &str, slot: u64, amount: u64) -> TradeEvent {
+        TradeEvent {
+            mint: "mint".to_string(),
+            trader: Some(wallet.to_string()),
+            is_buy: true,
+            sol_amount: None,
+            token_amount: Some(amount),
+            signature: signature.to_string(),
+            platform: Platform::PumpFun,
+            timestamp: 0,
+            timing: EventTiming { slot: Some(slot), ..Default::default() },
+        }
+    }
+
+    const SUPPLY: u64 = 1_000_000;
+
+    #[test]
+    fn same_slot_buyers_with_shared_funder_are_bundled() {
+        let mut pending = PendingLaunch::new(&launch(), 5_000);
+        pending.push(&buy("dev", "create", 100, 50_000), 2);
+        pending.push(&buy("a", "tx-a", 100, 60_000), 2);
+        pending.push(&buy("b", "tx-b", 100, 60_000), 2);
+        pending.push(&buy("c", "tx-c", 101, 30_000), 2);
+        pending.push(&buy("d", "tx-d", 100, 50_000), 2);
+        // Outside the early window
+        pending.push(&buy("late", "tx-late", 110, 500_000), 2);
+
+        let funders = HashMap::from([
+            ("a".to_string(), "funder".to_string()),
+            ("b".to_string(), "funder".to_string()),
+            ("c".to_string(), "dev".to_string()),
+        ]);
+        let scored = score_bundle(pending.wallets(), &funders, Some("dev"), SUPPLY);
+
+        assert_eq!(scored.wallets.len(), 5);
+        assert!(scored.wallets.iter().find(|w| w.wallet == "dev").unwrap().same_tx);
+        assert_eq!(scored.clusters.len(), 2);
+        // Every early wallet is in the bundle: dev, a, b, d in slot, c via the creator's funding
+        assert!((scored.bundled_supply_pct - 25.0).abs() < 1e-9);
+        assert!(scored.bundle_score >= 80.0, "score {}", scored.bundle_score);
+    }
+
+    #[test]
+    fn lone_dev_buy_scores_low() {
+        let mut pending = PendingLaunch::new(&launch(), 5_000);
+        pending.push(&buy("dev", "create", 100, 20_000), 2);
+        pending.push(&buy("x", "tx-x", 102, 10_000), 2);
+
+        let scored = score_bundle(pending.wallets(), &HashMap::new(), Some("dev"), SUPPLY);
+        assert!(scored.clusters.is_empty());
+        assert!((scored.bundled_supply_pct - 2.0).abs() < 1e-9);
+        assert!(scored.bundle_score < 10.0, "score {}", scored.bundle_score);
+    }
+
+    #[test]
+    fn launchlab_first_buyer_is_not_taken_for_the_create() {
+        let raydium = |mut trade: TradeEvent| {
+            trade.platform = Platform::Raydium;
+            trade
+        };
+        // Detected from its first fetched buy, by a sniper rather than the creator
+        let launch = TokenLaunch { platform: Platform::Raydium, signature: "tx-a".to_string(), ..launch() };
+        let mut pending = PendingLaunch::new(&launch, 5_000);
+        pending.push(&raydium(buy("a", "tx-a", 100, 60_000)), 2);
+        pending.push(&raydium(buy("b", "tx-b", 100, 60_000)), 2);
+        pending.push(&raydium(buy("c", "tx-c", 101, 60_000)), 2);
+
+        let scored = score_bundle(pending.wallets(), &HashMap::new(), Some("dev"), SUPPLY);
+        assert_eq!(scored.wallets.len(), 3);
+        assert!(scored.wallets.iter().all(|w| !w.same_tx && !w.same_slot));
+        assert_eq!(scored.bundled_supply_pct, 0.0);
+        assert_eq!(scored.bundle_score, 0.0);
+
+        // Shared funding still groups them
+        let funders = HashMap::from([("a".to_string(), "funder".to_string()), ("b".to_string(), "funder".to_string())]);
+        let scored = score_bundle(pending.wallets(), &funders, Some("dev"), SUPPLY);
+        assert_eq!(scored.clusters.len(), 1);
+        assert!(scored.bundle_score > 0.0);
+    }
+
+    #[test]
+    fn finds_funding_transfer() {
+        let transaction = json!({
+            "transaction": { "message": { "instructions": [
+                { "program": "system", "parsed": { "type": "transfer", "info": { "source": "funder", "destination": "sniper", "lamports": 1 } } }
+            ]}},
+            "meta": { "innerInstructions": [] }
+        });
+        assert_eq!(sol_funder(&transaction, "sniper").as_deref(), Some("funder"));
+        assert_eq!(sol_funder(&transaction, "someone"), None);
+    }
+}
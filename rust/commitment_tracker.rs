@@ -0,0 +1,206 @@
+use anyhow::{anyhow, Result};
+use serde_json::{json, Value};
+use std::collections::HashMap;
+use tokio::sync::broadcast::error::RecvError;
+use tokio::time::{interval, Duration, Instant, MissedTickBehavior};
+use tracing::{info, warn, debug};
+
+use crate::block_clock::now_ms;
+use crate::config::{Config, LifecycleConfig};
+use crate::event_store::{EventFeed, EventHub};
+use crate::rpc_scheduler::{Priority, RpcScheduler};
+use crate::types::{EventTiming, MonitorEvent, Platform, RetractReason, Retraction, StatusUpdate, TxStatus};
+
+// getSignatureStatuses accepts at most 256 signatures per call
+const MAX_SIGNATURES_PER_REQUEST: usize = 256;
+
+struct TrackedSignature {
+    status: TxStatus,
+    first_seen: Instant,
+    // Every (mint, platform) emitted from this transaction
+    mints: Vec<(String, Platform)>,
+}
+
+/// Follows signatures emitted below the target commitment and publishes
+/// `Status` updates as they advance, or a `Retraction` if they never land.
+pub struct CommitmentTracker {
+    rpc: RpcScheduler,
+    settings: LifecycleConfig,
+    target: TxStatus,
+    events: EventHub,
+    receiver: EventFeed,
+    tracked: HashMap<String, TrackedSignature>,
+}
+
+impl CommitmentTracker {
+    /// Subscribes immediately so nothing published before `run` is missed.
+    pub fn new(config: &Config, events: EventHub, feed: EventFeed) -> Result<Self> {
+        Ok(Self {
+            rpc: RpcScheduler::shared(config)?.with_timeout(Duration::from_secs(5)),
+            settings: config.lifecycle.clone(),
+            target: config.lifecycle.track_until.into(),
+            receiver: feed,
+            events,
+            tracked: HashMap::new(),
+        })
+    }
+
+    pub async fn run(mut self) {
+        info!("🧭 Commitment tracker started (following signatures until {})", self.target.label());
+
+        let mut ticker = interval(Duration::from_millis(self.settings.poll_interval_ms.max(100)));
+        ticker.set_missed_tick_behavior(MissedTickBehavior::Skip);
+
+        loop {
+            tokio::select! {
+                event = self.receiver.recv() => match event {
+                    Ok(event) => self.track(&event),
+                    Err(RecvError::Lagged(skipped)) => warn!("⚠️ Commitment tracker lagging, skipped {} events", skipped),
+                    Err(RecvError::Closed) => break,
+                },
+                _ = ticker.tick() => {
+                    if !self.tracked.is_empty() {
+                        self.poll().await;
+                    }
+                }
+            }
+        }
+    }
+
+    fn track(&mut self, event: &MonitorEvent) {
+        if event.is_lifecycle() || event.timing().status >= self.target {
+            return;
+        }
+        let Some(signature) = event.signature() else { return };
+
+        if !self.tracked.contains_key(signature) && self.tracked.len() >= self.settings.max_tracked_signatures {
+            warn!("⚠️ Commitment tracker full ({} signatures) - not tracking {}", self.tracked.len(), signature);
+            return;
+        }
+
+        let tracked = self.tracked.entry(signature.to_string()).or_insert_with(|| TrackedSignature {
+            status: event.timing().status,
+            first_seen: Instant::now(),
+            mints: Vec::new(),
+        });
+        let mint = (event.mint().to_string(), event.platform());
+        if !tracked.mints.contains(&mint) {
+            tracked.mints.push(mint);
+        }
+    }
+
+    async fn poll(&mut self) {
+        let signatures: Vec<String> = self.tracked.keys().cloned().collect();
+
+        for chunk in signatures.chunks(MAX_SIGNATURES_PER_REQUEST) {
+            match self.fetch_statuses(chunk).await {
+                Ok(statuses) => {
+                    for (signature, status) in chunk.iter().zip(statuses) {
+                        self.apply_status(signature, &status);
+                    }
+                },
+                Err(e) => {
+                    warn!("⚠️ getSignatureStatuses failed: {}", e);
+                    return;
+                },
+            }
+        }
+    }
+
+    async fn fetch_statuses(&self, signatures: &[String]) -> Result<Vec<Value>> {
+        let result = self.rpc.result(Priority::Launch, "getSignatureStatuses", json!([
+            signatures, { "searchTransactionHistory": false }
+        ])).await?;
+
+        let statuses = result
+            .get("value")
+            .and_then(|v| v.as_array())
+            .ok_or_else(|| anyhow!("missing result.value"))?;
+        if statuses.len() != signatures.len() {
+            return Err(anyhow!("expected {} statuses, got {}", signatures.len(), statuses.len()));
+        }
+        Ok(statuses.clone())
+    }
+
+    fn apply_status(&mut self, signature: &str, status: &Value) {
+        let Some(mut tracked) = self.tracked.remove(signature) else { return };
+        let expired = tracked.first_seen.elapsed() >= Duration::from_secs(self.settings.drop_after_secs);
+
+        // Unknown to the RPC: still propagating, or gone with its fork
+        if status.is_null() {
+            if expired {
+                self.retract(signature, &tracked, RetractReason::Dropped);
+            } else {
+                self.tracked.insert(signature.to_string(), tracked);
+            }
+            return;
+        }
+
+        if status.get("err").is_some_and(|err| !err.is_null()) {
+            self.retract(signature, &tracked, RetractReason::Failed);
+            return;
+        }
+
+        let slot = status.get("slot").and_then(|s| s.as_u64());
+        let new_status = match status.get("confirmationStatus").and_then(|s| s.as_str()) {
+            Some("processed") => TxStatus::Processed,
+            Some("confirmed") => TxStatus::Confirmed,
+            Some("finalized") => TxStatus::Finalized,
+            _ => tracked.status,
+        };
+
+        if new_status > tracked.status {
+            tracked.status = new_status;
+            self.publish_status(signature, &tracked, slot);
+        }
+
+        if new_status >= self.target {
+            return;
+        }
+        if expired {
+            // Landed but slow to advance - stop following rather than retract a live tx
+            debug!("🧭 Giving up on {} at {}", signature, new_status.label());
+            return;
+        }
+        self.tracked.insert(signature.to_string(), tracked);
+    }
+
+    fn publish_status(&self, signature: &str, tracked: &TrackedSignature, slot: Option<u64>) {
+        let timing = lifecycle_timing(slot, tracked.status);
+        for (mint, platform) in &tracked.mints {
+            debug!("🧭 [{}] {} | CA: {} | TX: {}", platform, tracked.status.label(), mint, signature);
+            self.events.publish(MonitorEvent::Status(StatusUpdate {
+                mint: mint.clone(),
+                signature: signature.to_string(),
+                platform: *platform,
+                status: tracked.status,
+                timestamp: timing.detected_at_ms / 1000,
+                timing: timing.clone(),
+            }));
+        }
+    }
+
+    fn retract(&self, signature: &str, tracked: &TrackedSignature, reason: RetractReason) {
+        let timing = lifecycle_timing(None, tracked.status);
+        for (mint, platform) in &tracked.mints {
+            info!("↩️ [{}] Retracted ({:?}) | CA: {} | TX: {}", platform, reason, mint, signature);
+            self.events.publish(MonitorEvent::Retraction(Retraction {
+                mint: mint.clone(),
+                signature: signature.to_string(),
+                platform: *platform,
+                reason,
+                timestamp: timing.detected_at_ms / 1000,
+                timing: timing.clone(),
+            }));
+        }
+    }
+}
+
+fn lifecycle_timing(slot: Option<u64>, status: TxStatus) -> EventTiming {
+    EventTiming {
+        slot,
+        block_time_ms: None,
+        detected_at_ms: now_ms(),
+        status,
+    }
+}
@@ -0,0 +1,363 @@
+use std::collections::{HashMap, VecDeque};
+use tokio::sync::broadcast::error::RecvError;
+use tokio::time::Instant;
+use tracing::{info, warn, debug};
+
+use crate::config::{Config, PredictionConfig};
+use crate::event_store::{EventFeed, EventHub};
+use crate::pump_curve::LAMPORTS_PER_SOL;
+use crate::types::{
+    BondingCurveState, CompletionPrediction, CurveVelocity, MonitorEvent, NearCompletion,
+    NearCompletionTrigger, Platform, TradeEvent,
+};
+
+// Rates are averaged over at least this long, so a burst of trades can't extrapolate wildly
+const MIN_SPAN_MS: u64 = 60_000;
+// The overall fill rate says little about the next few minutes
+const FALLBACK_CONFIDENCE: f64 = 25.0;
+
+// ========================================================================
+// PER-CURVE WINDOW
+// ========================================================================
+
+struct WindowTrade {
+    at_ms: u64,
+    signature: String,
+    // Lamports in (buy) or out (sell)
+    sol_delta: i64,
+    token_amount: u64,
+}
+
+struct CurveWindow {
+    platform: Platform,
+    trades: VecDeque<WindowTrade>,
+    // First curve reading (time, real SOL), for the overall fill rate
+    first_reading: Option<(u64, u64)>,
+    alerted: bool,
+    last_seen: Instant,
+}
+
+impl CurveWindow {
+    fn new(platform: Platform) -> Self {
+        Self {
+            platform,
+            trades: VecDeque::new(),
+            first_reading: None,
+            alerted: false,
+            last_seen: Instant::now(),
+        }
+    }
+
+    fn push(&mut self, trade: &TradeEvent) {
+        let sol = trade.sol_amount.unwrap_or(0).min(i64::MAX as u64) as i64;
+        self.trades.push_back(WindowTrade {
+            at_ms: trade.timing.detected_at_ms,
+            signature: trade.signature.clone(),
+            sol_delta: if trade.is_buy { sol } else { -sol },
+            token_amount: trade.token_amount.unwrap_or(0),
+        });
+        self.last_seen = Instant::now();
+    }
+
+    fn prune(&mut self, now_ms: u64, window_ms: u64) {
+        while self.trades.front().is_some_and(|trade| trade.at_ms + window_ms < now_ms) {
+            self.trades.pop_front();
+        }
+    }
+
+    // Time covered by the window so far
+    fn span_ms(&self, now_ms: u64, window_ms: u64) -> u64 {
+        let oldest = self.trades.front().map_or(now_ms, |trade| trade.at_ms);
+        now_ms.saturating_sub(oldest).min(window_ms)
+    }
+
+    fn velocity(&self, mint: &str, now_ms: u64, window_ms: u64) -> CurveVelocity {
+        let minutes = self.span_ms(now_ms, window_ms).max(MIN_SPAN_MS) as f64 / 60_000.0;
+        let net_lamports: i64 = self.trades.iter().map(|trade| trade.sol_delta).sum();
+        let token_volume: u64 = self.trades.iter().map(|trade| trade.token_amount).sum();
+
+        CurveVelocity {
+            mint: mint.to_string(),
+            sol_per_minute: net_lamports as f64 / LAMPORTS_PER_SOL as f64 / minutes,
+            token_volume_per_minute: token_volume as f64 / minutes,
+            trades_per_minute: self.trades.len() as f64 / minutes,
+            calculated_over: minutes,
+        }
+    }
+
+    /// 0-100 from sample size, how much of the window is covered, and whether
+    /// the older and newer halves of the window agree.
+    fn confidence(&self, now_ms: u64, window_ms: u64, min_trades: usize) -> f64 {
+        let sample = (self.trades.len() as f64 / (4 * min_trades.max(1)) as f64).min(1.0);
+        let span_ms = self.span_ms(now_ms, window_ms);
+        let coverage = span_ms as f64 / window_ms.max(1) as f64;
+
+        let midpoint = now_ms.saturating_sub(span_ms / 2);
+        let (older, newer) = self.trades.iter().fold((0i64, 0i64), |(older, newer), trade| {
+            if trade.at_ms < midpoint {
+                (older + trade.sol_delta, newer)
+            } else {
+                (older, newer + trade.sol_delta)
+            }
+        });
+        let steadiness = if older > 0 && newer > 0 {
+            older.min(newer) as f64 / older.max(newer) as f64
+        } else {
+            0.0
+        };
+
+        100.0 * (0.4 * sample + 0.3 * coverage + 0.3 * steadiness)
+    }
+
+    fn predict(&self, curve: &BondingCurveState, settings: &PredictionConfig) -> (CurveVelocity, CompletionPrediction) {
+        let now_ms = curve.timing.detected_at_ms;
+        let window_ms = settings.window_secs * 1000;
+        let velocity = self.velocity(&curve.mint, now_ms, window_ms);
+
+        let recent = self.trades.len() >= settings.min_trades;
+        let (sol_per_minute, confidence) = if recent {
+            (velocity.sol_per_minute, self.confidence(now_ms, window_ms, settings.min_trades))
+        } else {
+            self.overall_rate(curve, window_ms)
+        };
+
+        let sol_to_complete = curve.sol_to_complete as f64 / LAMPORTS_PER_SOL as f64;
+        let prediction = CompletionPrediction {
+            mint: curve.mint.clone(),
+            estimated_minutes: (sol_per_minute > 0.0).then(|| sol_to_complete / sol_per_minute),
+            confidence,
+            based_on_recent_velocity: recent,
+        };
+        (velocity, prediction)
+    }
+
+    // SOL per minute since the first curve reading
+    fn overall_rate(&self, curve: &BondingCurveState, window_ms: u64) -> (f64, f64) {
+        let Some((first_ms, first_sol)) = self.first_reading else { return (0.0, 0.0) };
+        let elapsed_ms = curve.timing.detected_at_ms.saturating_sub(first_ms);
+        let minutes = elapsed_ms.max(MIN_SPAN_MS) as f64 / 60_000.0;
+        let gained = curve.real_sol_reserves as f64 - first_sol as f64;
+        let coverage = (elapsed_ms as f64 / window_ms.max(1) as f64).min(1.0);
+        (gained / LAMPORTS_PER_SOL as f64 / minutes, FALLBACK_CONFIDENCE * coverage)
+    }
+}
+
+// ========================================================================
+// PREDICTOR
+// ========================================================================
+
+/// Keeps a sliding trade window per curve, predicts when it completes, and
+/// publishes a `NearCompletion` alert the first time a threshold is crossed.
+/// Progress and SOL still needed come from the curve tracker's `Curve` events.
+pub struct CompletionPredictor {
+    settings: PredictionConfig,
+    events: EventHub,
+    receiver: EventFeed,
+    windows: HashMap<String, CurveWindow>,
+}
+
+impl CompletionPredictor {
+    /// Subscribes immediately so no launch is missed before `run`.
+    pub fn new(config: &Config, events: EventHub, feed: EventFeed) -> Self {
+        Self {
+            settings: config.predictions.clone(),
+            receiver: feed,
+            events,
+            windows: HashMap::new(),
+        }
+    }
+
+    pub async fn run(mut self) {
+        info!(
+            "⏳ Completion predictor started (alert at {:.0}% or ETA < {:.1} min)",
+            self.settings.near_progress_pct, self.settings.near_eta_minutes
+        );
+
+        loop {
+            match self.receiver.recv().await {
+                Ok(event) => self.handle_event(&event),
+                Err(RecvError::Lagged(skipped)) => warn!("⚠️ Completion predictor lagging, skipped {} events", skipped),
+                Err(RecvError::Closed) => break,
+            }
+        }
+    }
+
+    fn handle_event(&mut self, event: &MonitorEvent) {
+        match event {
+            MonitorEvent::Launch(launch) => self.start_tracking(&launch.contract_address, launch.platform),
+            MonitorEvent::Trade(trade) => {
+                let window_ms = self.settings.window_secs * 1000;
+                if let Some(window) = self.windows.get_mut(&trade.mint) {
+                    window.push(trade);
+                    window.prune(trade.timing.detected_at_ms, window_ms);
+                }
+            },
+            MonitorEvent::Curve(curve) => self.handle_curve(curve),
+            MonitorEvent::Retraction(retraction) => {
+                if let Some(window) = self.windows.get_mut(&retraction.mint) {
+                    window.trades.retain(|trade| trade.signature != retraction.signature);
+                }
+            },
+            MonitorEvent::Completion(_) | MonitorEvent::Migration(_) => {
+                self.windows.remove(event.mint());
+            },
+            _ => {},
+        }
+    }
+
+    fn start_tracking(&mut self, mint: &str, platform: Platform) {
+        if self.windows.contains_key(mint) {
+            return;
+        }
+        if self.windows.len() >= self.settings.max_tracked {
+            let idlest = self.windows.iter()
+                .min_by_key(|(_, window)| window.last_seen)
+                .map(|(mint, _)| mint.clone());
+            match idlest {
+                Some(idlest) => { self.windows.remove(&idlest); },
+                None => return, // max_tracked = 0
+            }
+        }
+        self.windows.insert(mint.to_string(), CurveWindow::new(platform));
+    }
+
+    fn handle_curve(&mut self, curve: &BondingCurveState) {
+        if curve.complete {
+            self.windows.remove(&curve.mint);
+            return;
+        }
+        // Launch may have been missed (lag, restart) - the curve is enough to start
+        self.start_tracking(&curve.mint, curve.platform);
+        let Some(window) = self.windows.get_mut(&curve.mint) else { return };
+
+        window.last_seen = Instant::now();
+        window.first_reading.get_or_insert((curve.timing.detected_at_ms, curve.real_sol_reserves));
+        window.prune(curve.timing.detected_at_ms, self.settings.window_secs * 1000);
+        if window.alerted {
+            return;
+        }
+
+        let (velocity, prediction) = window.predict(curve, &self.settings);
+        let eta_hit = prediction.estimated_minutes.is_some_and(|eta| eta <= self.settings.near_eta_minutes)
+            && prediction.confidence >= self.settings.min_confidence;
+        let trigger = if curve.progress_pct >= self.settings.near_progress_pct {
+            NearCompletionTrigger::Progress
+        } else if eta_hit {
+            NearCompletionTrigger::Eta
+        } else {
+            debug!("⏳ {} | {:.1}% | {:.2} SOL/min", curve.mint, curve.progress_pct, velocity.sol_per_minute);
+            return;
+        };
+        window.alerted = true;
+
+        info!(
+            "⏳ [{}] Near completion ({:?}) | CA: {} | {:.1}% | ETA: {} ({:.0}% confidence)",
+            window.platform,
+            trigger,
+            curve.mint,
+            curve.progress_pct,
+            prediction.estimated_minutes.map_or("unknown".to_string(), |eta| format!("{:.1} min", eta)),
+            prediction.confidence
+        );
+        self.events.publish(MonitorEvent::NearCompletion(NearCompletion {
+            mint: curve.mint.clone(),
+            platform: window.platform,
+            trigger,
+            progress_pct: curve.progress_pct,
+            sol_to_complete: curve.sol_to_complete,
+            velocity,
+            prediction,
+            timestamp: curve.timing.detected_at_ms / 1000,
+            timing: curve.timing.clone(),
+        }));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::EventTiming;
+
+    fn trade(at_ms: u64, is_buy: bool, sol: u64) -> TradeEvent {
+        TradeEvent {
+            mint: "mint".to_string(),
+            trader: None,
+            is_buy,
+            sol_amount: Some(sol),
+            token_amount: Some(1_000_000),
+            signature: format!("sig{}", at_ms),
+            platform: Platform::PumpFun,
+            timestamp: at_ms / 1000,
+            timing: EventTiming { detected_at_ms: at_ms, ..Default::default() },
+        }
+    }
+
+    fn curve(at_ms: u64, progress_pct: f64, sol_to_complete: u64) -> BondingCurveState {
+        BondingCurveState {
+            mint: "mint".to_string(),
+            bonding_curve: String::new(),
+            virtual_token_reserves: 0,
+            virtual_sol_reserves: 0,
+            real_token_reserves: 0,
+            real_sol_reserves: 0,
+            token_total_supply: 0,
+            complete: false,
+            progress_pct,
+            price_sol: 0.0,
+            market_cap_sol: 0.0,
+            sol_to_complete,
+            platform: Platform::PumpFun,
+            timestamp: at_ms / 1000,
+            timing: EventTiming { detected_at_ms: at_ms, ..Default::default() },
+        }
+    }
+
+    #[test]
+    fn steady_buying_predicts_eta() {
+        let settings = PredictionConfig::default();
+        let mut window = CurveWindow::new(Platform::PumpFun);
+        // 1 SOL every 15s for 5 minutes = 4 SOL/min net
+        for i in 0..20 {
+            window.push(&trade(i * 15_000, true, LAMPORTS_PER_SOL));
+        }
+
+        let (velocity, prediction) = window.predict(&curve(300_000, 50.0, 20 * LAMPORTS_PER_SOL), &settings);
+        assert!((3.9..4.4).contains(&velocity.sol_per_minute), "{}", velocity.sol_per_minute);
+        assert!((4.5..5.2).contains(&prediction.estimated_minutes.unwrap()));
+        assert!(prediction.based_on_recent_velocity);
+        assert!(prediction.confidence > 80.0, "{}", prediction.confidence);
+    }
+
+    #[test]
+    fn net_selling_has_no_eta() {
+        let settings = PredictionConfig::default();
+        let mut window = CurveWindow::new(Platform::PumpFun);
+        for i in 0..10 {
+            window.push(&trade(i * 10_000, i % 3 == 0, LAMPORTS_PER_SOL));
+        }
+
+        let (velocity, prediction) = window.predict(&curve(100_000, 40.0, LAMPORTS_PER_SOL), &settings);
+        assert!(velocity.sol_per_minute < 0.0);
+        assert!(prediction.estimated_minutes.is_none());
+    }
+
+    #[test]
+    fn alerts_once_per_mint() {
+        let config = Config::default();
+        let events = EventHub::new();
+        let mut receiver = events.subscribe();
+        let mut predictor = CompletionPredictor::new(&config, events.clone(), EventFeed::Live(events.subscribe()));
+
+        predictor.handle_event(&MonitorEvent::Curve(curve(0, 85.0, LAMPORTS_PER_SOL)));
+        predictor.handle_event(&MonitorEvent::Curve(curve(1_000, 90.0, LAMPORTS_PER_SOL)));
+
+        let mut alerts = 0;
+        while let Ok(event) = receiver.try_recv() {
+            if let MonitorEvent::NearCompletion(alert) = event {
+                assert_eq!(alert.trigger, NearCompletionTrigger::Progress);
+                alerts += 1;
+            }
+        }
+        assert_eq!(alerts, 1);
+    }
+}
@@ -0,0 +1,1198 @@
+use anyhow::{bail, Context, Result};
+use clap::{Args, Parser, Subcommand, ValueEnum};
+use notify::{RecursiveMode, Watcher};
+use serde::Deserialize;
+use std::path::{Path, PathBuf};
+use tokio::sync::{mpsc, watch};
+use tokio::time::{sleep, Duration};
+use tracing::{info, warn, error};
+
+use crate::backtest::parse_time;
+use crate::event_filter::EventFilter;
+use crate::rules::{RuleBook, RuleConfig};
+use crate::types::{Platform, TxStatus};
+
+// Config file picked up from the working directory when --config isn't given
+const DEFAULT_CONFIG_FILE: &str = "blazing.toml";
+
+// ========================================================================
+// CLI
+// ========================================================================
+
+#[derive(Debug, Clone, Parser)]
+#[command(name = "blazing-monitor", about = "Real-time pump.fun and Raydium LaunchLab token monitor")]
+pub struct Cli {
+    /// TOML config file (defaults to ./blazing.toml when present)
+    #[arg(long, env = "BLAZING_CONFIG")]
+    pub config: Option<PathBuf>,
+
+    /// Solana WebSocket endpoint
+    #[arg(long, env = "SOLANA_WS_URL")]
+    pub ws_url: Option<String>,
+
+    /// Solana HTTP RPC endpoint (derived from the WebSocket URL when unset)
+    #[arg(long, env = "SOLANA_RPC_URL")]
+    pub rpc_url: Option<String>,
+
+    /// Commitment level for log subscriptions
+    #[arg(long, value_enum)]
+    pub commitment: Option<Commitment>,
+
+    /// Keep following emitted signatures until this commitment (status updates / retractions)
+    #[arg(long, value_enum)]
+    pub track_until: Option<Commitment>,
+
+    /// Don't track emitted signatures after the first event
+    #[arg(long)]
+    pub no_lifecycle: bool,
+
+    /// Disable the pump.fun monitor
+    #[arg(long)]
+    pub no_pump: bool,
+
+    /// Disable the Raydium LaunchLab monitor
+    #[arg(long)]
+    pub no_raydium: bool,
+
+    /// Don't follow bonding curve / LaunchLab pool accounts
+    #[arg(long)]
+    pub no_curves: bool,
+
+    /// Maximum bonding curves followed at once
+    #[arg(long)]
+    pub max_curves: Option<usize>,
+
+    /// Don't compute completion ETAs / near_completion alerts
+    #[arg(long)]
+    pub no_predictions: bool,
+
+    /// Alert when a curve reaches this progress (%)
+    #[arg(long)]
+    pub near_progress: Option<f64>,
+
+    /// Alert when the predicted time to completion drops below this (minutes)
+    #[arg(long)]
+    pub near_eta_minutes: Option<f64>,
+
+    /// Don't fetch off-chain token metadata
+    #[arg(long)]
+    pub no_metadata: bool,
+
+    /// Don't read mint accounts / Metaplex metadata for new tokens
+    #[arg(long)]
+    pub no_identity: bool,
+
+    /// Don't publish creator reputation for new launches
+    #[arg(long)]
+    pub no_creators: bool,
+
+    /// Don't score launches for same-slot bundles
+    #[arg(long)]
+    pub no_bundles: bool,
+
+    /// Don't take holder distribution snapshots
+    #[arg(long)]
+    pub no_holders: bool,
+
+    /// Holder snapshot delays after launch, in seconds (e.g. 60,300,900)
+    #[arg(long, value_delimiter = ',')]
+    pub holder_delays: Vec<u64>,
+
+    /// Don't follow dev wallet trades after launch
+    #[arg(long)]
+    pub no_dev_watch: bool,
+
+    /// Rhai script hook run on every event before the sinks (repeatable, enables [scripts])
+    #[arg(long = "script")]
+    pub scripts: Vec<PathBuf>,
+
+    /// How long to follow the dev wallet after each launch
+    #[arg(long)]
+    pub dev_watch_secs: Option<u64>,
+
+    /// Dashboard API bind address
+    #[arg(long, env = "API_BIND_ADDR")]
+    pub api_bind: Option<String>,
+
+    /// Disable the dashboard API server
+    #[arg(long)]
+    pub no_api: bool,
+
+    /// gRPC bind address
+    #[arg(long, env = "GRPC_BIND_ADDR")]
+    pub grpc_bind: Option<String>,
+
+    /// Disable the gRPC server
+    #[arg(long)]
+    pub no_grpc: bool,
+
+    /// Raydium getTransaction timeout (s)
+    #[arg(long)]
+    pub request_timeout_secs: Option<u64>,
+
+    /// Sustained RPC request rate, shared by every subsystem
+    #[arg(long)]
+    pub rpc_rps: Option<f64>,
+
+    /// Maximum concurrent RPC requests
+    #[arg(long)]
+    pub rpc_max_in_flight: Option<usize>,
+
+    /// Also fetch LaunchLab sells (more getTransaction calls)
+    #[arg(long)]
+    pub raydium_sells: bool,
+
+    /// Don't print `CA: ...` lines to stdout
+    #[arg(long)]
+    pub no_console: bool,
+
+    /// Append emitted events as JSON lines to this file
+    #[arg(long)]
+    pub jsonl: Option<PathBuf>,
+
+    /// Write launches, trades, completions and migrations as Parquet under this directory (enables [parquet])
+    #[arg(long)]
+    pub parquet: Option<PathBuf>,
+
+    /// Rule set from [rules.sets] that console output must pass
+    #[arg(long)]
+    pub console_rules: Option<String>,
+
+    /// Rule set from [rules.sets] that JSON lines output must pass
+    #[arg(long)]
+    pub jsonl_rules: Option<String>,
+
+    /// Only emit events from these platforms (pump_fun, raydium)
+    #[arg(long = "platform", value_parser = parse_platform)]
+    pub platforms: Vec<Platform>,
+
+    /// Only emit these event types (launch, trade, completion, migration)
+    #[arg(long = "event-type")]
+    pub event_types: Vec<String>,
+
+    /// Drop events from this creator (repeatable)
+    #[arg(long = "deny-creator")]
+    pub creator_deny: Vec<String>,
+
+    /// Record every raw WebSocket frame (and fetched Raydium transaction) to this gzip file
+    #[arg(long, conflicts_with = "replay")]
+    pub record: Option<PathBuf>,
+
+    /// Replay a recording through the monitors instead of connecting (offline: RPC-based enrichers are off)
+    #[arg(long)]
+    pub replay: Option<PathBuf>,
+
+    /// Replay speed: 1 keeps the recorded timing, 10 is ten times faster, 0 is as fast as possible
+    #[arg(long, default_value_t = 1.0)]
+    pub replay_speed: f64,
+
+    #[command(subcommand)]
+    pub command: Option<Command>,
+}
+
+#[derive(Debug, Clone, Subcommand)]
+pub enum Command {
+    /// Run a past slot or time range through the pipeline and write the events (no console, API or gRPC output)
+    Backtest(BacktestArgs),
+    /// Convert a recording or an events JSON lines file to Parquet, offline
+    Export(ExportArgs),
+}
+
+#[derive(Debug, Clone, Args)]
+pub struct BacktestArgs {
+    /// First slot of the range
+    #[arg(long, conflicts_with_all = ["from", "to"])]
+    pub from_slot: Option<u64>,
+
+    /// Last slot of the range (defaults to the current slot)
+    #[arg(long, requires = "from_slot")]
+    pub to_slot: Option<u64>,
+
+    /// Start of the range: unix seconds or UTC `YYYY-MM-DDTHH:MM[:SS]`
+    #[arg(long, value_parser = parse_time)]
+    pub from: Option<u64>,
+
+    /// End of the range (defaults to now)
+    #[arg(long, value_parser = parse_time, requires = "from")]
+    pub to: Option<u64>,
+
+    /// Read history from an archive (a `--record` recording or `--save-archive` file) instead of the RPC
+    #[arg(long)]
+    pub archive: Option<PathBuf>,
+
+    /// Keep the history fetched from the RPC as an archive, to re-run the backtest offline
+    #[arg(long, conflicts_with = "archive")]
+    pub save_archive: Option<PathBuf>,
+
+    /// Events that pass filters and rules, as JSON lines (overwritten)
+    #[arg(long, default_value = "backtest.jsonl")]
+    pub output: PathBuf,
+
+    /// Summary stats as JSON (defaults to the output path with `.summary.json`)
+    #[arg(long)]
+    pub summary: Option<PathBuf>,
+}
+
+#[derive(Debug, Clone, Args)]
+pub struct ExportArgs {
+    /// WebSocket recording (`--record`, `--save-archive`), decoded through the monitors
+    #[arg(long, required_unless_present = "events", conflicts_with = "events")]
+    pub recording: Option<PathBuf>,
+
+    /// Events as JSON lines (`--jsonl`, backtest output)
+    #[arg(long)]
+    pub events: Option<PathBuf>,
+
+    /// Output directory (defaults to [parquet] dir)
+    #[arg(long)]
+    pub dir: Option<PathBuf>,
+}
+
+fn parse_platform(value: &str) -> Result<Platform, String> {
+    match value.to_lowercase().as_str() {
+        "pump_fun" | "pumpfun" | "pump" => Ok(Platform::PumpFun),
+        "raydium" => Ok(Platform::Raydium),
+        other => Err(format!("unknown platform: {}", other)),
+    }
+}
+
+// ========================================================================
+// CONFIG FILE
+// ========================================================================
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize, ValueEnum)]
+#[serde(rename_all = "lowercase")]
+pub enum Commitment {
+    Processed,
+    Confirmed,
+    Finalized,
+}
+
+impl Commitment {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Commitment::Processed => "processed",
+            Commitment::Confirmed => "confirmed",
+            Commitment::Finalized => "finalized",
+        }
+    }
+}
+
+impl From<Commitment> for TxStatus {
+    fn from(commitment: Commitment) -> Self {
+        match commitment {
+            Commitment::Processed => TxStatus::Processed,
+            Commitment::Confirmed => TxStatus::Confirmed,
+            Commitment::Finalized => TxStatus::Finalized,
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+#[serde(default)]
+pub struct EndpointConfig {
+    pub ws_url: String,
+    pub rpc_url: Option<String>,
+}
+
+impl Default for EndpointConfig {
+    fn default() -> Self {
+        Self {
+            ws_url: "wss://api.mainnet-beta.solana.com".to_string(),
+            rpc_url: None,
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+#[serde(default)]
+pub struct PlatformToggles {
+    pub pump_fun: bool,
+    pub raydium: bool,
+}
+
+impl Default for PlatformToggles {
+    fn default() -> Self {
+        Self { pump_fun: true, raydium: true }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+#[serde(default)]
+pub struct PumpConfig {
+    pub max_processed_signatures: usize,
+    pub keep_processed_signatures: usize,
+    pub reconnect_delay_secs: u64,
+    pub error_reconnect_delay_secs: u64,
+}
+
+impl Default for PumpConfig {
+    fn default() -> Self {
+        Self {
+            max_processed_signatures: 1000,
+            keep_processed_signatures: 500,
+            reconnect_delay_secs: 1,
+            error_reconnect_delay_secs: 5,
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+#[serde(default)]
+pub struct RaydiumConfig {
+    pub request_timeout_secs: u64,
+    // Sells share the fetch budget with the buys that detect launches
+    pub include_sells: bool,
+    pub max_processed_signatures: usize,
+    pub keep_processed_signatures: usize,
+    pub max_seen_mints: usize,
+    pub keep_seen_mints: usize,
+    pub reconnect_delay_secs: u64,
+    pub error_reconnect_delay_secs: u64,
+}
+
+impl Default for RaydiumConfig {
+    fn default() -> Self {
+        Self {
+            request_timeout_secs: 5,
+            include_sells: false,
+            max_processed_signatures: 300,
+            keep_processed_signatures: 150,
+            max_seen_mints: 2000,
+            keep_seen_mints: 1000,
+            reconnect_delay_secs: 2,
+            error_reconnect_delay_secs: 10,
+        }
+    }
+}
+
+/// Follow emitted signatures until they reach `track_until`, or retract them.
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+#[serde(default)]
+pub struct LifecycleConfig {
+    pub enabled: bool,
+    pub track_until: Commitment,
+    pub poll_interval_ms: u64,
+    // Unseen by the RPC for this long -> dropped (a blockhash expires after ~60-90s)
+    pub drop_after_secs: u64,
+    pub max_tracked_signatures: usize,
+}
+
+impl Default for LifecycleConfig {
+    fn default() -> Self {
+        Self {
+            enabled: true,
+            track_until: Commitment::Confirmed,
+            poll_interval_ms: 1000,
+            drop_after_secs: 90,
+            max_tracked_signatures: 5000,
+        }
+    }
+}
+
+/// accountSubscribe to each new pump.fun bonding curve / LaunchLab pool.
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+#[serde(default)]
+pub struct CurveConfig {
+    pub enabled: bool,
+    // Oldest-idle curve is dropped to make room for a new launch
+    pub max_tracked: usize,
+    pub idle_timeout_secs: u64,
+    pub reconnect_delay_secs: u64,
+}
+
+impl Default for CurveConfig {
+    fn default() -> Self {
+        Self {
+            enabled: true,
+            max_tracked: 200,
+            idle_timeout_secs: 600,
+            reconnect_delay_secs: 2,
+        }
+    }
+}
+
+/// Velocity / ETA per tracked curve and one-shot `near_completion` alerts.
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+#[serde(default)]
+pub struct PredictionConfig {
+    pub enabled: bool,
+    // Sliding trade window for velocity
+    pub window_secs: u64,
+    // Below this many trades in the window, fall back to the curve's overall fill rate
+    pub min_trades: usize,
+    // Either threshold fires the alert
+    pub near_progress_pct: f64,
+    pub near_eta_minutes: f64,
+    // ETA alerts need at least this confidence (0-100)
+    pub min_confidence: f64,
+    pub max_tracked: usize,
+}
+
+impl Default for PredictionConfig {
+    fn default() -> Self {
+        Self {
+            enabled: true,
+            window_secs: 300,
+            min_trades: 5,
+            near_progress_pct: 80.0,
+            near_eta_minutes: 5.0,
+            min_confidence: 30.0,
+            max_tracked: 1000,
+        }
+    }
+}
+
+/// Mint account + Metaplex metadata for every launch, batched with getMultipleAccounts.
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+#[serde(default)]
+pub struct IdentityConfig {
+    pub enabled: bool,
+    // Launches arriving within this window share one RPC call
+    pub batch_window_ms: u64,
+    // Mints whose account isn't visible yet (processed launches) are retried
+    pub max_attempts: u32,
+    pub cache_size: usize,
+}
+
+impl Default for IdentityConfig {
+    fn default() -> Self {
+        Self {
+            enabled: true,
+            batch_window_ms: 250,
+            max_attempts: 3,
+            cache_size: 10_000,
+        }
+    }
+}
+
+/// Creator history (launches, completions, dumps) summarised on every new launch.
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+#[serde(default)]
+pub struct CreatorConfig {
+    pub enabled: bool,
+    // This many launches within the window (the new one included) marks a serial launcher
+    pub serial_launches: u64,
+    pub serial_window_hours: u64,
+    // Selling at least this share of their own buys before completion counts as a dump
+    pub dump_share_pct: f64,
+}
+
+impl Default for CreatorConfig {
+    fn default() -> Self {
+        Self {
+            enabled: true,
+            serial_launches: 10,
+            serial_window_hours: 24 * 7,
+            dump_share_pct: 90.0,
+        }
+    }
+}
+
+/// Follow the creator's own trades on each new token for a while after launch.
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+#[serde(default)]
+pub struct DevWatchConfig {
+    pub enabled: bool,
+    pub window_secs: u64,
+    // Smaller trades (percent of supply) are not reported; 0 reports all
+    pub min_supply_pct: f64,
+    pub max_watched: usize,
+}
+
+impl Default for DevWatchConfig {
+    fn default() -> Self {
+        Self {
+            enabled: true,
+            window_secs: 1800,
+            min_supply_pct: 0.0,
+            max_watched: 5000,
+        }
+    }
+}
+
+/// Same-slot / create-tx buyers at launch, clustered by funding source and scored.
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+#[serde(default)]
+pub struct BundleConfig {
+    pub enabled: bool,
+    // Buys up to this many slots after the create slot count as early
+    pub early_slots: u64,
+    // Wait this long after the launch before scoring, so early trades have arrived
+    pub window_secs: u64,
+    // Look up who funded each early wallet (2 RPC calls per wallet)
+    pub resolve_funding: bool,
+    pub max_funding_lookups: usize,
+    // Signatures checked per wallet for its funding transfer
+    pub funding_lookback: usize,
+    pub score_threshold: f64,
+    pub max_pending: usize,
+}
+
+impl Default for BundleConfig {
+    fn default() -> Self {
+        Self {
+            enabled: true,
+            early_slots: 2,
+            window_secs: 5,
+            resolve_funding: true,
+            max_funding_lookups: 8,
+            funding_lookback: 20,
+            score_threshold: 60.0,
+            max_pending: 2000,
+        }
+    }
+}
+
+/// Holder distribution (top-10 share, Gini, holder count) at fixed delays after launch.
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+#[serde(default)]
+pub struct HolderConfig {
+    pub enabled: bool,
+    pub delays_secs: Vec<u64>,
+    // Every token account via getProgramAccounts; falls back to the 20 largest when refused
+    pub count_holders: bool,
+    pub max_concurrent: usize,
+    pub max_scheduled: usize,
+}
+
+impl Default for HolderConfig {
+    fn default() -> Self {
+        Self {
+            enabled: true,
+            delays_secs: vec![60, 300, 900],
+            count_holders: true,
+            max_concurrent: 4,
+            max_scheduled: 10_000,
+        }
+    }
+}
+
+/// Rhai hooks between the event hub and the output sinks.
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+#[serde(default)]
+pub struct ScriptConfig {
+    pub enabled: bool,
+    // Run in order; each gets the previous script's output
+    pub paths: Vec<PathBuf>,
+    // Per script per event, wall clock and interpreter operations
+    pub timeout_ms: u64,
+    pub max_operations: u64,
+    pub max_alerts_per_event: usize,
+    // A script that keeps failing is switched off until restart
+    pub max_consecutive_errors: u32,
+}
+
+impl Default for ScriptConfig {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            paths: Vec::new(),
+            timeout_ms: 20,
+            max_operations: 200_000,
+            max_alerts_per_event: 5,
+            max_consecutive_errors: 20,
+        }
+    }
+}
+
+/// Off-chain metadata JSON behind launch URIs (IPFS, Arweave, plain HTTPS).
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+#[serde(default)]
+pub struct MetadataConfig {
+    pub enabled: bool,
+    // Tried in order after the URI's own host; each ends with `/ipfs/` or `/`
+    pub ipfs_gateways: Vec<String>,
+    pub arweave_gateways: Vec<String>,
+    // Per gateway attempt
+    pub timeout_secs: u64,
+    pub max_bytes: usize,
+    // One JSON file per URI; empty disables the cache
+    pub cache_dir: PathBuf,
+    pub max_concurrent: usize,
+}
+
+impl Default for MetadataConfig {
+    fn default() -> Self {
+        Self {
+            enabled: true,
+            ipfs_gateways: vec![
+                "https://ipfs.io/ipfs/".to_string(),
+                "https://dweb.link/ipfs/".to_string(),
+                "https://gateway.pinata.cloud/ipfs/".to_string(),
+            ],
+            arweave_gateways: vec![
+                "https://arweave.net/".to_string(),
+                "https://ar-io.net/".to_string(),
+            ],
+            timeout_secs: 5,
+            max_bytes: 256 * 1024,
+            cache_dir: PathBuf::from(".cache/metadata"),
+            max_concurrent: 8,
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+#[serde(default)]
+pub struct ApiConfig {
+    pub enabled: bool,
+    pub bind: String,
+}
+
+impl Default for ApiConfig {
+    fn default() -> Self {
+        Self { enabled: true, bind: "127.0.0.1:8080".to_string() }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+#[serde(default)]
+pub struct GrpcConfig {
+    pub enabled: bool,
+    pub bind: String,
+}
+
+impl Default for GrpcConfig {
+    fn default() -> Self {
+        Self { enabled: true, bind: "127.0.0.1:50051".to_string() }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+#[serde(default)]
+pub struct SinkConfig {
+    pub console: bool,
+    pub jsonl_path: Option<PathBuf>,
+    // Names from [rules.sets]; unset lets everything through
+    pub console_rules: Option<String>,
+    pub jsonl_rules: Option<String>,
+}
+
+impl Default for SinkConfig {
+    fn default() -> Self {
+        Self { console: true, jsonl_path: None, console_rules: None, jsonl_rules: None }
+    }
+}
+
+/// The scheduler every HTTP RPC call goes through (see `rpc_scheduler`).
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+#[serde(default)]
+pub struct RpcConfig {
+    // Token bucket per endpoint: sustained rate and burst
+    pub requests_per_sec: f64,
+    pub burst: u32,
+    pub max_in_flight: usize,
+    // Per priority - beyond this, requests fail at once (Raydium counts them as throttled)
+    pub max_queued: usize,
+    pub request_timeout_secs: u64,
+    // On 429s, 5xx and transport errors
+    pub max_retries: u32,
+    // A 429 without Retry-After pauses the endpoint this long, doubling while they keep coming
+    pub backoff_ms: u64,
+    pub max_backoff_secs: u64,
+}
+
+impl Default for RpcConfig {
+    fn default() -> Self {
+        Self {
+            requests_per_sec: 10.0,
+            burst: 10,
+            max_in_flight: 8,
+            max_queued: 200,
+            request_timeout_secs: 10,
+            max_retries: 3,
+            backoff_ms: 500,
+            max_backoff_secs: 30,
+        }
+    }
+}
+
+/// Launches, trades, completions and migrations as Parquet, partitioned by type, date and platform.
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+#[serde(default)]
+pub struct ParquetConfig {
+    pub enabled: bool,
+    pub dir: PathBuf,
+    pub row_group_size: usize,
+    // Buffered rows go out as a new file per partition at least this often...
+    pub flush_interval_secs: u64,
+    // ...or as soon as a partition holds this many
+    pub max_file_rows: usize,
+}
+
+impl Default for ParquetConfig {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            dir: PathBuf::from("parquet"),
+            row_group_size: 10_000,
+            flush_interval_secs: 60,
+            max_file_rows: 100_000,
+        }
+    }
+}
+
+/// History fetching for the `backtest` subcommand.
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+#[serde(default)]
+pub struct BacktestConfig {
+    // getSignaturesForAddress page size (the RPC caps it at 1000)
+    pub page_size: usize,
+    pub max_concurrent_fetches: usize,
+    // Per program; stop collecting signatures past this many (pump.fun alone is thousands per minute)
+    pub max_transactions: usize,
+    pub request_timeout_secs: u64,
+    // Retries per request on 429s and transport errors, with doubling backoff
+    pub max_retries: u32,
+}
+
+impl Default for BacktestConfig {
+    fn default() -> Self {
+        Self {
+            page_size: 1000,
+            max_concurrent_fetches: 4,
+            max_transactions: 50_000,
+            request_timeout_secs: 30,
+            max_retries: 5,
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+#[serde(default)]
+pub struct Config {
+    pub endpoints: EndpointConfig,
+    pub platforms: PlatformToggles,
+    pub commitment: Commitment,
+    pub lifecycle: LifecycleConfig,
+    pub curves: CurveConfig,
+    pub predictions: PredictionConfig,
+    pub metadata: MetadataConfig,
+    pub identity: IdentityConfig,
+    pub creators: CreatorConfig,
+    pub dev_watch: DevWatchConfig,
+    pub bundles: BundleConfig,
+    pub holders: HolderConfig,
+    pub scripts: ScriptConfig,
+    pub pump: PumpConfig,
+    pub raydium: RaydiumConfig,
+    pub rpc: RpcConfig,
+    pub api: ApiConfig,
+    pub grpc: GrpcConfig,
+    pub backtest: BacktestConfig,
+    pub parquet: ParquetConfig,
+    pub sinks: SinkConfig,
+    pub filters: EventFilter,
+    pub rules: RuleConfig,
+}
+
+impl Default for Config {
+    fn default() -> Self {
+        Self {
+            endpoints: EndpointConfig::default(),
+            platforms: PlatformToggles::default(),
+            commitment: Commitment::Confirmed,
+            lifecycle: LifecycleConfig::default(),
+            curves: CurveConfig::default(),
+            predictions: PredictionConfig::default(),
+            metadata: MetadataConfig::default(),
+            identity: IdentityConfig::default(),
+            creators: CreatorConfig::default(),
+            dev_watch: DevWatchConfig::default(),
+            bundles: BundleConfig::default(),
+            holders: HolderConfig::default(),
+            scripts: ScriptConfig::default(),
+            pump: PumpConfig::default(),
+            raydium: RaydiumConfig::default(),
+            rpc: RpcConfig::default(),
+            api: ApiConfig::default(),
+            grpc: GrpcConfig::default(),
+            backtest: BacktestConfig::default(),
+            parquet: ParquetConfig::default(),
+            sinks: SinkConfig::default(),
+            filters: EventFilter::default(),
+            rules: RuleConfig::default(),
+        }
+    }
+}
+
+/// Settings that can change at runtime without touching the WebSocket connections.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct RuntimeConfig {
+    pub sinks: SinkConfig,
+    pub filters: EventFilter,
+    pub rules: RuleBook,
+}
+
+impl Config {
+    /// File (if any) -> environment -> CLI flags, later wins.
+    pub fn load(cli: &Cli) -> Result<Self> {
+        let mut config = match cli.config_path() {
+            Some(path) => Self::from_file(&path)?,
+            None => Self::default(),
+        };
+        cli.apply_overrides(&mut config);
+        Ok(config)
+    }
+
+    pub fn from_file(path: &Path) -> Result<Self> {
+        let contents = std::fs::read_to_string(path)
+            .with_context(|| format!("reading config file {}", path.display()))?;
+        toml::from_str(&contents).with_context(|| format!("parsing config file {}", path.display()))
+    }
+
+    /// WebSocket endpoint, accepting an HTTP URL by mistake.
+    pub fn ws_url(&self) -> String {
+        self.endpoints.ws_url
+            .replace("https://", "wss://")
+            .replace("http://", "ws://")
+    }
+
+    /// HTTP RPC endpoint - explicit, or derived from the WebSocket URL.
+    pub fn rpc_url(&self) -> String {
+        self.endpoints.rpc_url.clone().unwrap_or_else(|| {
+            self.endpoints.ws_url
+                .replace("wss://", "https://")
+                .replace("ws://", "http://")
+        })
+    }
+
+    /// Sinks, filters and compiled rules. Fails on bad rules, unreadable lists
+    /// or a sink naming a rule set that doesn't exist.
+    pub fn runtime(&self) -> Result<RuntimeConfig> {
+        let rules = RuleBook::load(&self.rules).context("loading [rules]")?;
+        for set in [&self.sinks.console_rules, &self.sinks.jsonl_rules].into_iter().flatten() {
+            if !rules.contains(set) {
+                bail!("sink refers to unknown rule set `{}`", set);
+            }
+        }
+        Ok(RuntimeConfig {
+            sinks: self.sinks.clone(),
+            filters: self.filters.clone(),
+            rules,
+        })
+    }
+
+    /// Everything except sinks/filters/rules needs a restart to take effect.
+    fn structural_eq(&self, other: &Config) -> bool {
+        self.endpoints == other.endpoints
+            && self.platforms == other.platforms
+            && self.commitment == other.commitment
+            && self.lifecycle == other.lifecycle
+            && self.curves == other.curves
+            && self.predictions == other.predictions
+            && self.metadata == other.metadata
+            && self.identity == other.identity
+            && self.creators == other.creators
+            && self.dev_watch == other.dev_watch
+            && self.bundles == other.bundles
+            && self.holders == other.holders
+            && self.scripts == other.scripts
+            && self.pump == other.pump
+            && self.raydium == other.raydium
+            && self.rpc == other.rpc
+            && self.api == other.api
+            && self.grpc == other.grpc
+            && self.backtest == other.backtest
+            && self.parquet == other.parquet
+    }
+}
+
+impl Cli {
+    fn config_path(&self) -> Option<PathBuf> {
+        self.config.clone().or_else(|| {
+            let default = PathBuf::from(DEFAULT_CONFIG_FILE);
+            default.exists().then_some(default)
+        })
+    }
+
+    fn apply_overrides(&self, config: &mut Config) {
+        if let Some(ws_url) = &self.ws_url { config.endpoints.ws_url = ws_url.clone(); }
+        if let Some(rpc_url) = &self.rpc_url { config.endpoints.rpc_url = Some(rpc_url.clone()); }
+        if let Some(commitment) = self.commitment { config.commitment = commitment; }
+        if let Some(track_until) = self.track_until { config.lifecycle.track_until = track_until; }
+        if self.no_lifecycle { config.lifecycle.enabled = false; }
+        if self.no_pump { config.platforms.pump_fun = false; }
+        if self.no_raydium { config.platforms.raydium = false; }
+        if self.no_curves { config.curves.enabled = false; }
+        if let Some(value) = self.max_curves { config.curves.max_tracked = value; }
+        if self.no_predictions { config.predictions.enabled = false; }
+        if let Some(value) = self.near_progress { config.predictions.near_progress_pct = value; }
+        if let Some(value) = self.near_eta_minutes { config.predictions.near_eta_minutes = value; }
+        if self.no_metadata { config.metadata.enabled = false; }
+        if self.no_identity { config.identity.enabled = false; }
+        if self.no_creators { config.creators.enabled = false; }
+        if self.no_dev_watch { config.dev_watch.enabled = false; }
+        if self.no_bundles { config.bundles.enabled = false; }
+        if self.no_holders { config.holders.enabled = false; }
+        if !self.holder_delays.is_empty() { config.holders.delays_secs = self.holder_delays.clone(); }
+        if let Some(value) = self.dev_watch_secs { config.dev_watch.window_secs = value; }
+        if !self.scripts.is_empty() {
+            config.scripts.enabled = true;
+            config.scripts.paths = self.scripts.clone();
+        }
+
+        if let Some(bind) = &self.api_bind { config.api.bind = bind.clone(); }
+        if self.no_api { config.api.enabled = false; }
+        if let Some(bind) = &self.grpc_bind { config.grpc.bind = bind.clone(); }
+        if self.no_grpc { config.grpc.enabled = false; }
+
+        if let Some(value) = self.request_timeout_secs { config.raydium.request_timeout_secs = value; }
+        if let Some(value) = self.rpc_rps { config.rpc.requests_per_sec = value; }
+        if let Some(value) = self.rpc_max_in_flight { config.rpc.max_in_flight = value; }
+        if self.raydium_sells { config.raydium.include_sells = true; }
+
+        if self.no_console { config.sinks.console = false; }
+        if let Some(path) = &self.jsonl { config.sinks.jsonl_path = Some(path.clone()); }
+        if let Some(dir) = &self.parquet {
+            config.parquet.enabled = true;
+            config.parquet.dir = dir.clone();
+        }
+        if let Some(set) = &self.console_rules { config.sinks.console_rules = Some(set.clone()); }
+        if let Some(set) = &self.jsonl_rules { config.sinks.jsonl_rules = Some(set.clone()); }
+
+        // Replays run offline - everything that would call the RPC is off
+        if self.replay.is_some() {
+            config.lifecycle.enabled = false;
+            config.curves.enabled = false;
+            config.identity.enabled = false;
+            config.metadata.enabled = false;
+            config.holders.enabled = false;
+            config.bundles.resolve_funding = false;
+        }
+
+        // Backtests write their output file only, and can't follow state that has moved on since
+        if let Some(Command::Backtest(args)) = &self.command {
+            config.api.enabled = false;
+            config.grpc.enabled = false;
+            config.sinks.console = false;
+            config.sinks.jsonl_path = Some(args.output.clone());
+            config.lifecycle.enabled = false;
+            config.curves.enabled = false;
+            config.holders.enabled = false;
+            config.bundles.resolve_funding = false;
+        }
+
+        // `export` always writes Parquet, wherever [parquet] says
+        if let Some(Command::Export(args)) = &self.command {
+            config.parquet.enabled = true;
+            if let Some(dir) = &args.dir { config.parquet.dir = dir.clone(); }
+        }
+
+        if !self.platforms.is_empty() { config.filters.platforms = self.platforms.iter().copied().collect(); }
+        if !self.event_types.is_empty() { config.filters.event_types = self.event_types.iter().cloned().collect(); }
+        config.filters.creator_deny.extend(self.creator_deny.iter().cloned());
+    }
+}
+
+// ========================================================================
+// HOT RELOAD
+// ========================================================================
+
+/// Re-read the config on file change or SIGHUP and publish new sinks/filters/rules.
+/// Rule list files are re-read too, so SIGHUP picks up edited lists.
+pub async fn watch_for_changes(cli: Cli, mut current: Config, runtime: watch::Sender<RuntimeConfig>) {
+    let Some(path) = cli.config_path() else {
+        info!("🔧 No config file - hot reload disabled");
+        return;
+    };
+
+    let (tx, mut rx) = mpsc::channel::<()>(1);
+
+    // Watch the directory: editors often replace the file instead of writing it
+    let file_name = path.file_name().map(|n| n.to_os_string());
+    let watch_dir = path.parent().filter(|p| !p.as_os_str().is_empty()).unwrap_or(Path::new(".")).to_path_buf();
+    let file_tx = tx.clone();
+    let watcher = notify::recommended_watcher(move |res: notify::Result<notify::Event>| {
+        if let Ok(event) = res {
+            if event.paths.iter().any(|p| p.file_name().map(|n| n.to_os_string()) == file_name) {
+                let _ = file_tx.try_send(());
+            }
+        }
+    });
+    let _watcher = match watcher {
+        Ok(mut watcher) => match watcher.watch(&watch_dir, RecursiveMode::NonRecursive) {
+            Ok(()) => Some(watcher),
+            Err(e) => {
+                warn!("⚠️ Can't watch {}: {} (SIGHUP reload still works)", watch_dir.display(), e);
+                None
+            },
+        },
+        Err(e) => {
+            warn!("⚠️ File watcher unavailable: {} (SIGHUP reload still works)", e);
+            None
+        },
+    };
+
+    #[cfg(unix)]
+    {
+        use tokio::signal::unix::{signal, SignalKind};
+        let sighup_tx = tx.clone();
+        match signal(SignalKind::hangup()) {
+            Ok(mut hangup) => {
+                tokio::spawn(async move {
+                    while hangup.recv().await.is_some() {
+                        info!("🔧 SIGHUP received - reloading config");
+                        let _ = sighup_tx.send(()).await;
+                    }
+                });
+            },
+            Err(e) => warn!("⚠️ Can't install SIGHUP handler: {}", e),
+        }
+    }
+
+    info!("🔧 Watching {} for changes", path.display());
+
+    while rx.recv().await.is_some() {
+        // Let editors finish writing before reading
+        sleep(Duration::from_millis(250)).await;
+        while rx.try_recv().is_ok() {}
+
+        let mut reloaded = match Config::from_file(&path) {
+            Ok(config) => config,
+            Err(e) => {
+                error!("❌ Config reload failed, keeping previous settings: {:#}", e);
+                continue;
+            }
+        };
+        cli.apply_overrides(&mut reloaded);
+
+        if !reloaded.structural_eq(&current) {
+            warn!("⚠️ Endpoints, platforms, throttles and servers only change on restart - applying sinks/filters/rules only");
+        }
+
+        let new_runtime = match reloaded.runtime() {
+            Ok(new_runtime) => new_runtime,
+            Err(e) => {
+                error!("❌ Rules reload failed, keeping previous settings: {:#}", e);
+                continue;
+            }
+        };
+        if new_runtime != *runtime.borrow() {
+            info!("🔧 Applied new sinks/filters/rules from {}", path.display());
+            let _ = runtime.send(new_runtime);
+        }
+        current = reloaded;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::{Mutex, MutexGuard};
+
+    fn config_file(name: &str, contents: &str) -> PathBuf {
+        let path = std::env::temp_dir().join(format!("blazing-config-{}-{}.toml", std::process::id(), name));
+        std::fs::write(&path, contents).unwrap();
+        path
+    }
+
+    // Parsing reads the environment, which is shared by the test threads - whoever parses
+    // or sets variables holds this, so a test changing them can't race the others
+    static ENVIRONMENT: Mutex<()> = Mutex::new(());
+
+    fn environment() -> MutexGuard<'static, ()> {
+        ENVIRONMENT.lock().unwrap_or_else(|poisoned| poisoned.into_inner())
+    }
+
+    fn parse(path: &Path, args: &[&str]) -> Config {
+        let mut argv = vec!["blazing-monitor", "--config", path.to_str().unwrap()];
+        argv.extend_from_slice(args);
+        Config::load(&Cli::try_parse_from(argv).unwrap()).unwrap()
+    }
+
+    fn load(path: &Path, args: &[&str]) -> Config {
+        let _environment = environment();
+        parse(path, args)
+    }
+
+    #[test]
+    fn file_then_environment_then_flags() {
+        let path = config_file("precedence", r#"
+            [endpoints]
+            rpc_url = "http://file"
+            [grpc]
+            bind = "file:1"
+            [curves]
+            max_tracked = 50
+            idle_timeout_secs = 30
+        "#);
+        let config = {
+            let _environment = environment();
+            std::env::set_var("SOLANA_RPC_URL", "http://env");
+            std::env::set_var("GRPC_BIND_ADDR", "env:1");
+            let config = parse(&path, &["--grpc-bind", "flag:1", "--max-curves", "10"]);
+            std::env::remove_var("SOLANA_RPC_URL");
+            std::env::remove_var("GRPC_BIND_ADDR");
+            config
+        };
+
+        assert_eq!(config.endpoints.rpc_url.as_deref(), Some("http://env"));
+        assert_eq!(config.grpc.bind, "flag:1");
+        assert_eq!(config.curves.max_tracked, 10);
+        // Untouched by env or flags - the file's value, and defaults for the rest
+        assert_eq!(config.curves.idle_timeout_secs, 30);
+        assert_eq!(config.curves.reconnect_delay_secs, CurveConfig::default().reconnect_delay_secs);
+        std::fs::remove_file(path).ok();
+    }
+
+    #[test]
+    fn replay_switches_off_rpc_enrichers_whatever_the_file_says() {
+        let path = config_file("replay", r#"
+            [curves]
+            enabled = true
+            [holders]
+            enabled = true
+        "#);
+        let config = load(&path, &["--replay", "session.jsonl.gz"]);
+        assert!(!config.lifecycle.enabled && !config.curves.enabled && !config.holders.enabled);
+        assert!(!config.identity.enabled && !config.metadata.enabled && !config.bundles.resolve_funding);
+        // Offline-capable stages and outputs stay as configured
+        assert!(config.dev_watch.enabled && config.bundles.enabled && config.sinks.console && config.api.enabled);
+        std::fs::remove_file(path).ok();
+    }
+
+    #[test]
+    fn backtest_writes_only_its_output_file() {
+        let path = config_file("backtest", r#"
+            [sinks]
+            console = true
+            jsonl_path = "live.jsonl"
+        "#);
+        let config = load(&path, &["--jsonl", "flag.jsonl", "backtest", "--from-slot", "1", "--output", "range.jsonl"]);
+        assert_eq!(config.sinks.jsonl_path, Some(PathBuf::from("range.jsonl")));
+        assert!(!config.sinks.console && !config.api.enabled && !config.grpc.enabled);
+        assert!(!config.lifecycle.enabled && !config.curves.enabled && !config.holders.enabled && !config.bundles.resolve_funding);
+        // Identity and metadata read current accounts, which backtests still use
+        assert!(config.identity.enabled && config.metadata.enabled);
+
+        let config = load(&path, &["export", "--events", "range.jsonl", "--dir", "out"]);
+        assert!(config.parquet.enabled);
+        assert_eq!(config.parquet.dir, PathBuf::from("out"));
+        std::fs::remove_file(path).ok();
+    }
+
+    #[test]
+    fn only_sinks_filters_and_rules_change_without_restart() {
+        let current = Config::default();
+
+        let mut reloaded = current.clone();
+        reloaded.sinks.console = false;
+        reloaded.sinks.jsonl_path = Some(PathBuf::from("events.jsonl"));
+        reloaded.filters.creator_deny.extend(["creator".to_string()]);
+        assert!(reloaded.structural_eq(&current));
+
+        let mut reloaded = current.clone();
+        reloaded.endpoints.ws_url = "wss://elsewhere".to_string();
+        assert!(!reloaded.structural_eq(&current));
+
+        let mut reloaded = current.clone();
+        reloaded.platforms.raydium = false;
+        assert!(!reloaded.structural_eq(&current));
+
+        let mut reloaded = current.clone();
+        reloaded.parquet.enabled = true;
+        assert!(!reloaded.structural_eq(&current));
+    }
+}
@@ -0,0 +1,371 @@
+use serde::Serialize;
+use std::collections::{HashMap, HashSet, VecDeque};
+use tokio::sync::broadcast::error::RecvError;
+use tracing::{info, warn};
+
+use crate::config::{Config, CreatorConfig};
+use crate::event_store::{EventFeed, EventHub};
+use crate::types::{CreatorReputation, EventTiming, MonitorEvent, Platform, TradeEvent};
+
+// Memory limits - the book outlives the event store's token records
+const MAX_CREATORS: usize = 20_000;
+const MAX_TRACKED_MINTS: usize = 50_000;
+// Per-creator history; lifetime counters keep going past it
+const MAX_TOKENS_PER_CREATOR: usize = 100;
+const MAX_REPORTED: usize = 10_000;
+
+// ========================================================================
+// CREATOR BOOK - aggregated by the event store
+// ========================================================================
+
+#[derive(Debug, Clone, Serialize)]
+pub struct CreatorToken {
+    pub mint: String,
+    pub platform: Platform,
+    pub launched_at: u64,
+    pub completed_at: Option<u64>,
+    // Raw token amounts the creator traded on the curve (dev buy included)
+    pub bought: u64,
+    pub sold: u64,
+}
+
+impl CreatorToken {
+    /// Share of what the creator bought that they sold again, 0-100.
+    pub fn share_sold_pct(&self) -> Option<f64> {
+        (self.bought > 0).then(|| (self.sold as f64 / self.bought as f64 * 100.0).min(100.0))
+    }
+
+    fn completion_secs(&self) -> Option<u64> {
+        self.completed_at.map(|at| at.saturating_sub(self.launched_at))
+    }
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct CreatorProfile {
+    pub creator: String,
+    pub launches: u64,
+    pub completions: u64,
+    pub total_completion_secs: u64,
+    pub first_launch: u64,
+    pub last_launch: u64,
+    // Most recent launches, newest last
+    pub tokens: VecDeque<CreatorToken>,
+}
+
+impl CreatorProfile {
+    fn new(creator: &str, launched_at: u64) -> Self {
+        Self {
+            creator: creator.to_string(),
+            launches: 0,
+            completions: 0,
+            total_completion_secs: 0,
+            first_launch: launched_at,
+            last_launch: launched_at,
+            tokens: VecDeque::new(),
+        }
+    }
+
+    pub fn completion_rate_pct(&self) -> Option<f64> {
+        (self.launches > 0).then(|| self.completions as f64 / self.launches as f64 * 100.0)
+    }
+
+    pub fn avg_time_to_completion_secs(&self) -> Option<u64> {
+        (self.completions > 0).then(|| self.total_completion_secs / self.completions)
+    }
+
+    /// History before `mint` - the launch being judged doesn't count towards its own reputation.
+    pub fn reputation(&self, mint: &str, now: u64, settings: &CreatorConfig) -> CreatorReputation {
+        let current = self.tokens.iter().find(|token| token.mint == mint);
+        let prior_launches = self.launches - u64::from(current.is_some());
+        let current_secs = current.and_then(CreatorToken::completion_secs);
+        let completions = self.completions - u64::from(current_secs.is_some());
+        let completion_secs = self.total_completion_secs - current_secs.unwrap_or(0);
+
+        let prior: Vec<&CreatorToken> = self.tokens.iter().filter(|token| token.mint != mint).collect();
+        let window_start = now.saturating_sub(settings.serial_window_hours * 3600);
+        let launches_in_window = prior.iter().filter(|token| token.launched_at >= window_start).count() as u64;
+
+        let shares: Vec<f64> = prior.iter().filter_map(|token| token.share_sold_pct()).collect();
+        let avg_share_sold_pct = (!shares.is_empty()).then(|| shares.iter().sum::<f64>() / shares.len() as f64);
+        let dumped_tokens = prior.iter()
+            .filter(|token| token.completed_at.is_none())
+            .filter(|token| token.share_sold_pct().is_some_and(|share| share >= settings.dump_share_pct))
+            .count() as u64;
+
+        CreatorReputation {
+            mint: mint.to_string(),
+            creator: self.creator.clone(),
+            prior_launches,
+            launches_in_window,
+            completion_rate_pct: (prior_launches > 0).then(|| completions as f64 / prior_launches as f64 * 100.0),
+            avg_time_to_completion_secs: (completions > 0).then(|| completion_secs / completions),
+            avg_share_sold_pct,
+            dumped_tokens,
+            // The new launch counts towards the serial threshold
+            serial_launcher: launches_in_window + 1 >= settings.serial_launches,
+            platform: current.map_or(Platform::PumpFun, |token| token.platform),
+            timestamp: now,
+            timing: EventTiming::default(),
+        }
+    }
+}
+
+#[derive(Default)]
+pub struct CreatorBook {
+    profiles: HashMap<String, CreatorProfile>,
+    creator_order: VecDeque<String>,
+    // mint -> creator, so trades and completions find their profile
+    mint_creators: HashMap<String, String>,
+    mint_order: VecDeque<String>,
+}
+
+impl CreatorBook {
+    pub fn profile(&self, creator: &str) -> Option<&CreatorProfile> {
+        self.profiles.get(creator)
+    }
+
+    pub fn record_launch(&mut self, creator: &str, mint: &str, platform: Platform, launched_at: u64) {
+        if self.mint_creators.contains_key(mint) {
+            return;
+        }
+        self.mint_creators.insert(mint.to_string(), creator.to_string());
+        self.mint_order.push_back(mint.to_string());
+        while self.mint_order.len() > MAX_TRACKED_MINTS {
+            if let Some(oldest) = self.mint_order.pop_front() {
+                self.mint_creators.remove(&oldest);
+            }
+        }
+
+        if !self.profiles.contains_key(creator) {
+            self.creator_order.push_back(creator.to_string());
+            while self.creator_order.len() > MAX_CREATORS {
+                if let Some(oldest) = self.creator_order.pop_front() {
+                    self.profiles.remove(&oldest);
+                }
+            }
+        }
+        let profile = self.profiles.entry(creator.to_string()).or_insert_with(|| CreatorProfile::new(creator, launched_at));
+        profile.launches += 1;
+        profile.last_launch = profile.last_launch.max(launched_at);
+        profile.tokens.push_back(CreatorToken {
+            mint: mint.to_string(),
+            platform,
+            launched_at,
+            completed_at: None,
+            bought: 0,
+            sold: 0,
+        });
+        if profile.tokens.len() > MAX_TOKENS_PER_CREATOR {
+            profile.tokens.pop_front();
+        }
+    }
+
+    /// Only the creator's own trades count.
+    pub fn record_trade(&mut self, trade: &TradeEvent) {
+        let Some(trader) = trade.trader.as_deref() else { return };
+        let Some(token) = self.token_mut(&trade.mint).filter(|(creator, _)| *creator == trader).map(|(_, token)| token) else {
+            return;
+        };
+        let amount = trade.token_amount.unwrap_or(0);
+        if trade.is_buy {
+            token.bought = token.bought.saturating_add(amount);
+        } else {
+            token.sold = token.sold.saturating_add(amount);
+        }
+    }
+
+    /// Completion and migration both land here; only the first one counts.
+    pub fn record_completion(&mut self, mint: &str, completed_at: u64) {
+        let Some(creator) = self.mint_creators.get(mint) else { return };
+        let Some(profile) = self.profiles.get_mut(creator) else { return };
+        let Some(token) = profile.tokens.iter_mut().find(|token| token.mint == mint) else { return };
+        if token.completed_at.is_some() {
+            return;
+        }
+        token.completed_at = Some(completed_at);
+        profile.completions += 1;
+        profile.total_completion_secs += token.completion_secs().unwrap_or(0);
+    }
+
+    /// A launch whose transaction never landed.
+    pub fn retract_launch(&mut self, mint: &str) {
+        let Some(creator) = self.mint_creators.remove(mint) else { return };
+        self.mint_order.retain(|tracked| tracked != mint);
+        if let Some(profile) = self.profiles.get_mut(&creator) {
+            let before = profile.tokens.len();
+            profile.tokens.retain(|token| token.mint != mint);
+            if profile.tokens.len() < before {
+                profile.launches = profile.launches.saturating_sub(1);
+            }
+        }
+    }
+
+    fn token_mut(&mut self, mint: &str) -> Option<(&str, &mut CreatorToken)> {
+        let creator = self.mint_creators.get(mint)?;
+        let profile = self.profiles.get_mut(creator)?;
+        let token = profile.tokens.iter_mut().find(|token| token.mint == mint)?;
+        Some((creator.as_str(), token))
+    }
+}
+
+// ========================================================================
+// PROFILER - publishes a reputation summary per launch
+// ========================================================================
+
+pub struct CreatorProfiler {
+    settings: CreatorConfig,
+    events: EventHub,
+    receiver: EventFeed,
+    reported: HashSet<String>,
+    reported_order: VecDeque<String>,
+}
+
+impl CreatorProfiler {
+    pub fn new(config: &Config, events: EventHub, feed: EventFeed) -> Self {
+        Self {
+            settings: config.creators.clone(),
+            receiver: feed,
+            events,
+            reported: HashSet::new(),
+            reported_order: VecDeque::new(),
+        }
+    }
+
+    pub async fn run(mut self) {
+        info!(
+            "👤 Creator profiler started (serial launcher at {} launches / {}h)",
+            self.settings.serial_launches, self.settings.serial_window_hours
+        );
+
+        loop {
+            match self.receiver.recv().await {
+                Ok(event) => self.handle_event(&event),
+                Err(RecvError::Lagged(skipped)) => warn!("⚠️ Creator profiler lagging, skipped {} events", skipped),
+                Err(RecvError::Closed) => break,
+            }
+        }
+    }
+
+    fn handle_event(&mut self, event: &MonitorEvent) {
+        // LaunchLab creators are only known once the identity enricher reads the pool
+        let (mint, creator, platform, timing) = match event {
+            MonitorEvent::Launch(launch) => (&launch.contract_address, launch.creator.as_deref(), launch.platform, &launch.timing),
+            MonitorEvent::Identity(identity) => (&identity.mint, identity.creator.as_deref(), identity.platform, &identity.timing),
+            _ => return,
+        };
+        let Some(creator) = creator else { return };
+        if !self.mark_reported(mint) {
+            return;
+        }
+
+        let now = timing.detected_at_ms / 1000;
+        let Some(mut reputation) = self.events.read(|store| {
+            store.creator(creator).map(|profile| profile.reputation(mint, now, &self.settings))
+        }) else {
+            return;
+        };
+        reputation.platform = platform;
+        reputation.timing = timing.clone();
+
+        log_reputation(&reputation);
+        self.events.publish(MonitorEvent::Creator(reputation));
+    }
+
+    fn mark_reported(&mut self, mint: &str) -> bool {
+        if !self.reported.insert(mint.to_string()) {
+            return false;
+        }
+        self.reported_order.push_back(mint.to_string());
+        while self.reported_order.len() > MAX_REPORTED {
+            if let Some(oldest) = self.reported_order.pop_front() {
+                self.reported.remove(&oldest);
+            }
+        }
+        true
+    }
+}
+
+fn log_reputation(reputation: &CreatorReputation) {
+    let pct = |value: Option<f64>| value.map_or("n/a".to_string(), |v| format!("{:.0}%", v));
+    info!(
+        "👤 [{}] Creator {} | CA: {} | {} prior launches ({} recent) | Completed: {} | Avg sold: {} | Dumped: {}",
+        reputation.platform,
+        reputation.creator,
+        reputation.mint,
+        reputation.prior_launches,
+        reputation.launches_in_window,
+        pct(reputation.completion_rate_pct),
+        pct(reputation.avg_share_sold_pct),
+        reputation.dumped_tokens
+    );
+    if reputation.serial_launcher {
+        warn!("🚨 Serial launcher {} - {} launches in the window", reputation.creator, reputation.launches_in_window + 1);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn trade(mint: &str, trader: &str, is_buy: bool, amount: u64) -> TradeEvent {
+        TradeEvent {
+            mint: mint.to_string(),
+            trader: Some(trader.to_string()),
+            is_buy,
+            sol_amount: None,
+            token_amount: Some(amount),
+            signature: String::new(),
+            platform: Platform::PumpFun,
+            timestamp: 0,
+            timing: EventTiming::default(),
+        }
+    }
+
+    #[test]
+    fn reputation_excludes_current_launch() {
+        let settings = CreatorConfig::default();
+        let mut book = CreatorBook::default();
+
+        // Graduated in 10 minutes, creator kept their bag
+        book.record_launch("dev", "a", Platform::PumpFun, 1_000);
+        book.record_trade(&trade("a", "dev", true, 100));
+        book.record_completion("a", 1_600);
+        // Dumped everything before graduation
+        book.record_launch("dev", "b", Platform::PumpFun, 2_000);
+        book.record_trade(&trade("b", "dev", true, 100));
+        book.record_trade(&trade("b", "dev", false, 100));
+        // Someone else's sell doesn't count
+        book.record_trade(&trade("b", "other", false, 50));
+        book.record_launch("dev", "c", Platform::PumpFun, 3_000);
+
+        let reputation = book.profile("dev").unwrap().reputation("c", 3_000, &settings);
+        assert_eq!(reputation.prior_launches, 2);
+        assert_eq!(reputation.completion_rate_pct, Some(50.0));
+        assert_eq!(reputation.avg_time_to_completion_secs, Some(600));
+        assert_eq!(reputation.avg_share_sold_pct, Some(50.0));
+        assert_eq!(reputation.dumped_tokens, 1);
+        assert!(!reputation.serial_launcher);
+    }
+
+    #[test]
+    fn flags_serial_launchers_within_window() {
+        let settings = CreatorConfig { serial_launches: 3, serial_window_hours: 1, ..Default::default() };
+        let mut book = CreatorBook::default();
+        // One launch long before the window, two inside it
+        for (mint, at) in [("old", 0), ("a", 10_000), ("b", 11_000), ("c", 12_000)] {
+            book.record_launch("dev", mint, Platform::PumpFun, at);
+        }
+        book.record_completion("a", 10_500);
+        book.record_completion("a", 11_500);
+
+        let profile = book.profile("dev").unwrap();
+        assert_eq!(profile.completions, 1);
+        let reputation = profile.reputation("c", 12_000, &settings);
+        assert_eq!(reputation.prior_launches, 3);
+        assert_eq!(reputation.launches_in_window, 2);
+        assert!(reputation.serial_launcher);
+
+        book.retract_launch("c");
+        assert_eq!(book.profile("dev").unwrap().launches, 3);
+    }
+}
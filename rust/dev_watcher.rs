@@ -0,0 +1,309 @@
+use std::collections::{HashMap, VecDeque};
+use tokio::sync::broadcast::error::RecvError;
+use tokio::time::{interval, Duration, MissedTickBehavior};
+use tracing::{info, warn, debug};
+
+use crate::block_clock::EventClock;
+use crate::config::{Config, DevWatchConfig};
+use crate::event_store::{EventFeed, EventHub};
+use crate::pump_curve::GlobalAccount;
+use crate::types::{DevActivity, MonitorEvent, Platform, TokenLaunch, TradeEvent};
+
+// LaunchLab launches are detected from a buy before the creator is known (identity reads the pool)
+const MAX_PENDING_TRADES: usize = 32;
+const PRUNE_INTERVAL_SECS: u64 = 30;
+
+// ========================================================================
+// PER-LAUNCH WATCH
+// ========================================================================
+
+struct Watch {
+    platform: Platform,
+    creator: Option<String>,
+    launched_at_ms: u64,
+    // Raw token supply, for percentages
+    supply: Option<u64>,
+    // Creator's position from the trades seen since launch
+    bought: u64,
+    sold: u64,
+    pending: Vec<TradeEvent>,
+}
+
+impl Watch {
+    fn new(launch: &TokenLaunch) -> Self {
+        // pump.fun supply is fixed; LaunchLab's arrives with the identity or the pool
+        let supply = match launch.platform {
+            Platform::PumpFun => Some(GlobalAccount::default().token_total_supply),
+            Platform::Raydium => None,
+        };
+        Self {
+            platform: launch.platform,
+            creator: launch.creator.clone(),
+            launched_at_ms: launch.timing.detected_at_ms,
+            supply,
+            bought: 0,
+            sold: 0,
+            pending: Vec::new(),
+        }
+    }
+
+    fn expired(&self, now_ms: u64, window_ms: u64) -> bool {
+        now_ms.saturating_sub(self.launched_at_ms) > window_ms
+    }
+
+    fn supply_pct(&self, amount: u64) -> Option<f64> {
+        self.supply.filter(|supply| *supply > 0).map(|supply| amount as f64 / supply as f64 * 100.0)
+    }
+
+    /// The creator's trade as a dev event; None for anyone else's.
+    fn apply(&mut self, trade: &TradeEvent) -> Option<DevActivity> {
+        let creator = self.creator.as_deref()?;
+        if trade.trader.as_deref() != Some(creator) {
+            return None;
+        }
+
+        let amount = trade.token_amount.unwrap_or(0);
+        if trade.is_buy {
+            self.bought = self.bought.saturating_add(amount);
+        } else {
+            self.sold = self.sold.saturating_add(amount);
+        }
+        let net = self.bought as i128 - self.sold as i128;
+
+        Some(DevActivity {
+            mint: trade.mint.clone(),
+            creator: creator.to_string(),
+            token_amount: amount,
+            sol_amount: trade.sol_amount,
+            supply_pct: self.supply_pct(amount),
+            net_position_pct: self.supply.filter(|supply| *supply > 0).map(|supply| net as f64 / supply as f64 * 100.0),
+            secs_since_launch: trade.timing.detected_at_ms.saturating_sub(self.launched_at_ms) / 1000,
+            signature: trade.signature.clone(),
+            platform: self.platform,
+            timestamp: trade.timestamp,
+            timing: trade.timing.clone(),
+        })
+    }
+}
+
+// ========================================================================
+// WATCHER
+// ========================================================================
+
+pub struct DevWatcher {
+    settings: DevWatchConfig,
+    events: EventHub,
+    receiver: EventFeed,
+    watches: HashMap<String, Watch>,
+    order: VecDeque<String>,
+    // Watch windows run on event time, so backtests prune like live runs
+    clock: EventClock,
+}
+
+impl DevWatcher {
+    pub fn new(config: &Config, events: EventHub, feed: EventFeed) -> Self {
+        Self {
+            settings: config.dev_watch.clone(),
+            receiver: feed,
+            events,
+            watches: HashMap::new(),
+            order: VecDeque::new(),
+            clock: EventClock::default(),
+        }
+    }
+
+    pub async fn run(mut self) {
+        info!("👀 Dev wallet watcher started ({}s after each launch)", self.settings.window_secs);
+
+        let mut ticker = interval(Duration::from_secs(PRUNE_INTERVAL_SECS));
+        ticker.set_missed_tick_behavior(MissedTickBehavior::Delay);
+
+        loop {
+            tokio::select! {
+                event = self.receiver.recv() => match event {
+                    Ok(event) => self.handle_event(&event),
+                    Err(RecvError::Lagged(skipped)) => warn!("⚠️ Dev watcher lagging, skipped {} events", skipped),
+                    Err(RecvError::Closed) => break,
+                },
+                _ = ticker.tick() => self.prune(self.clock.now_ms()),
+            }
+        }
+    }
+
+    fn handle_event(&mut self, event: &MonitorEvent) {
+        if matches!(event, MonitorEvent::Launch(_) | MonitorEvent::Trade(_)) {
+            self.clock.observe(event.timing());
+        }
+        match event {
+            MonitorEvent::Launch(launch) => self.watch(launch),
+            MonitorEvent::Trade(trade) => self.handle_trade(trade),
+            MonitorEvent::Identity(identity) => {
+                if let Some(watch) = self.watches.get_mut(&identity.mint) {
+                    if identity.supply > 0 {
+                        watch.supply = Some(identity.supply);
+                    }
+                    if watch.creator.is_none() {
+                        watch.creator = identity.creator.clone();
+                    }
+                }
+                self.replay_pending(&identity.mint);
+            },
+            MonitorEvent::Curve(curve) if curve.token_total_supply > 0 => {
+                if let Some(watch) = self.watches.get_mut(&curve.mint) {
+                    watch.supply = Some(curve.token_total_supply);
+                }
+            },
+            _ => {},
+        }
+    }
+
+    fn watch(&mut self, launch: &TokenLaunch) {
+        if self.watches.contains_key(&launch.contract_address) {
+            return;
+        }
+        self.watches.insert(launch.contract_address.clone(), Watch::new(launch));
+        self.order.push_back(launch.contract_address.clone());
+        while self.order.len() > self.settings.max_watched {
+            if let Some(oldest) = self.order.pop_front() {
+                self.watches.remove(&oldest);
+            }
+        }
+    }
+
+    fn handle_trade(&mut self, trade: &TradeEvent) {
+        let window_ms = self.settings.window_secs * 1000;
+        let Some(watch) = self.watches.get_mut(&trade.mint) else { return };
+        if watch.expired(trade.timing.detected_at_ms, window_ms) {
+            return;
+        }
+        if watch.creator.is_none() {
+            if watch.pending.len() < MAX_PENDING_TRADES {
+                watch.pending.push(trade.clone());
+            }
+            return;
+        }
+        if let Some(activity) = watch.apply(trade) {
+            self.emit(activity, trade.is_buy);
+        }
+    }
+
+    // Trades that arrived before the creator was known
+    fn replay_pending(&mut self, mint: &str) {
+        let Some(watch) = self.watches.get_mut(mint) else { return };
+        if watch.creator.is_none() {
+            return;
+        }
+        let pending = std::mem::take(&mut watch.pending);
+        let activities: Vec<(DevActivity, bool)> = pending.iter()
+            .filter_map(|trade| watch.apply(trade).map(|activity| (activity, trade.is_buy)))
+            .collect();
+        for (activity, is_buy) in activities {
+            self.emit(activity, is_buy);
+        }
+    }
+
+    fn emit(&self, activity: DevActivity, is_buy: bool) {
+        if activity.supply_pct.is_some_and(|pct| pct < self.settings.min_supply_pct) {
+            debug!("👀 Dev trade below threshold | CA: {} | {:.3}%", activity.mint, activity.supply_pct.unwrap_or(0.0));
+            return;
+        }
+
+        let pct = |value: Option<f64>| value.map_or("n/a".to_string(), |v| format!("{:.2}%", v));
+        let message = format!(
+            "[{}] Dev {} {} | CA: {} | Supply: {} | Dev holds: {} | {}s after launch",
+            activity.platform,
+            if is_buy { "bought" } else { "sold" },
+            activity.token_amount,
+            activity.mint,
+            pct(activity.supply_pct),
+            pct(activity.net_position_pct),
+            activity.secs_since_launch
+        );
+        if is_buy {
+            info!("🟢 {}", message);
+            self.events.publish(MonitorEvent::DevBought(activity));
+        } else {
+            warn!("🔴 {}", message);
+            self.events.publish(MonitorEvent::DevSold(activity));
+        }
+    }
+
+    fn prune(&mut self, now_ms: u64) {
+        let window_ms = self.settings.window_secs * 1000;
+        self.watches.retain(|_, watch| !watch.expired(now_ms, window_ms));
+        let watches = &self.watches;
+        self.order.retain(|mint| watches.contains_key(mint));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::EventTiming;
+
+    fn launch(creator: Option<&str>, platform: Platform) -> TokenLaunch {
+        TokenLaunch {
+            contract_address: "mint".to_string(),
+            name: None,
+            symbol: None,
+            creator: creator.map(str::to_string),
+            uri: None,
+            signature: "launch".to_string(),
+            platform,
+            timestamp: 0,
+            timing: EventTiming { detected_at_ms: 1_000, ..Default::default() },
+        }
+    }
+
+    fn trade(trader: &str, is_buy: bool, amount: u64, at_ms: u64) -> TradeEvent {
+        TradeEvent {
+            mint: "mint".to_string(),
+            trader: Some(trader.to_string()),
+            is_buy,
+            sol_amount: Some(1),
+            token_amount: Some(amount),
+            signature: format!("sig{}", at_ms),
+            platform: Platform::PumpFun,
+            timestamp: at_ms / 1000,
+            timing: EventTiming { detected_at_ms: at_ms, ..Default::default() },
+        }
+    }
+
+    #[test]
+    fn tracks_creator_position_as_share_of_supply() {
+        let mut watch = Watch::new(&launch(Some("dev"), Platform::PumpFun));
+        assert!(watch.apply(&trade("someone", true, 1_000, 2_000)).is_none());
+
+        // 5% then half of it back out
+        let bought = watch.apply(&trade("dev", true, 50_000_000_000_000, 2_000)).unwrap();
+        assert_eq!(bought.supply_pct, Some(5.0));
+        let sold = watch.apply(&trade("dev", false, 25_000_000_000_000, 61_000)).unwrap();
+        assert_eq!(sold.supply_pct, Some(2.5));
+        assert_eq!(sold.net_position_pct, Some(2.5));
+        assert_eq!(sold.secs_since_launch, 60);
+    }
+
+    #[test]
+    fn launchlab_trades_wait_for_creator() {
+        let events = EventHub::new();
+        let mut receiver = events.subscribe();
+        let mut watcher = DevWatcher::new(&Config::default(), events.clone(), EventFeed::Live(events.subscribe()));
+
+        watcher.handle_event(&MonitorEvent::Launch(launch(None, Platform::Raydium)));
+        watcher.handle_trade(&trade("dev", true, 100, 2_000));
+        assert!(receiver.try_recv().is_err());
+
+        let watch = watcher.watches.get_mut("mint").unwrap();
+        watch.creator = Some("dev".to_string());
+        watch.supply = Some(1_000);
+        watcher.replay_pending("mint");
+        match receiver.try_recv() {
+            Ok(MonitorEvent::DevBought(activity)) => assert_eq!(activity.supply_pct, Some(10.0)),
+            other => panic!("expected DevBought, got {:?}", other),
+        }
+
+        // Past the window nothing is reported
+        watcher.handle_trade(&trade("dev", false, 100, 10_000_000));
+        assert!(receiver.try_recv().is_err());
+    }
+}
@@ -0,0 +1,43 @@
+use serde::Deserialize;
+use std::collections::HashSet;
+
+use crate::types::{MonitorEvent, Platform};
+
+/// Simple allow/deny filter shared by config-driven sinks and gRPC subscribers.
+/// Empty sets match everything.
+#[derive(Debug, Clone, Default, PartialEq, Deserialize)]
+#[serde(default)]
+pub struct EventFilter {
+    pub platforms: HashSet<Platform>,
+    pub event_types: HashSet<String>,
+    pub creator_allow: HashSet<String>,
+    pub creator_deny: HashSet<String>,
+    pub mint_deny: HashSet<String>,
+}
+
+impl EventFilter {
+    /// `creator` is the launch creator of the event's mint, when known.
+    pub fn matches(&self, event: &MonitorEvent, creator: Option<&str>) -> bool {
+        if !self.platforms.is_empty() && !self.platforms.contains(&event.platform()) {
+            return false;
+        }
+        if !self.event_types.is_empty() && !self.event_types.contains(event.kind()) {
+            return false;
+        }
+        if self.mint_deny.contains(event.mint()) {
+            return false;
+        }
+        if !self.creator_allow.is_empty() && !creator.is_some_and(|c| self.creator_allow.contains(c)) {
+            return false;
+        }
+        if creator.is_some_and(|c| self.creator_deny.contains(c)) {
+            return false;
+        }
+        true
+    }
+
+    /// Creator checks need a store lookup for non-launch events - skip it when unused.
+    pub fn needs_creator(&self) -> bool {
+        !self.creator_allow.is_empty() || !self.creator_deny.is_empty()
+    }
+}
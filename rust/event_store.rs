@@ -0,0 +1,555 @@
+use serde::Serialize;
+use std::collections::hash_map::Entry;
+use std::collections::{HashMap, VecDeque};
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::{Arc, Mutex, RwLock};
+use tokio::sync::broadcast::{self, error::RecvError, error::TryRecvError};
+use tokio::sync::mpsc;
+
+use crate::creator_profiler::{CreatorBook, CreatorProfile};
+use crate::metrics::metrics;
+use crate::types::{
+    BondingCurveState, BundleReport, CreatorReputation, HolderSnapshot, MonitorEvent, Platform, TokenIdentity, TokenLaunch, TokenMetadata,
+};
+
+// Memory limits for the in-process store
+const MAX_RECENT_EVENTS: usize = 1000;
+const MAX_TRACKED_TOKENS: usize = 5000;
+const EVENT_CHANNEL_CAPACITY: usize = 1024;
+
+// ========================================================================
+// TOKEN RECORDS & STATS
+// ========================================================================
+
+#[derive(Debug, Clone, Serialize)]
+pub struct TokenRecord {
+    pub launch: TokenLaunch,
+    pub trade_count: u64,
+    pub buy_count: u64,
+    pub first_seen: u64,
+    pub last_activity: u64,
+    pub completed: bool,
+    pub migrated: bool,
+    // Latest bonding curve account, while the curve tracker follows it
+    pub curve: Option<BondingCurveState>,
+    // Off-chain metadata, once the launch URI resolves
+    pub metadata: Option<TokenMetadata>,
+    // Mint account + Metaplex metadata
+    pub identity: Option<TokenIdentity>,
+    // Creator's history as of this launch
+    pub creator_reputation: Option<CreatorReputation>,
+    // Early-slot buyers and bundle score
+    pub bundle: Option<BundleReport>,
+    // One per configured delay, oldest first
+    pub holders: Vec<HolderSnapshot>,
+}
+
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct PlatformStats {
+    pub launches: u64,
+    pub trades: u64,
+    pub completions: u64,
+    pub migrations: u64,
+    pub retractions: u64,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct MonitorStats {
+    pub started_at: u64,
+    pub uptime_secs: u64,
+    pub total_events: u64,
+    pub tracked_tokens: usize,
+    pub pump_fun: PlatformStats,
+    pub raydium: PlatformStats,
+    pub last_event_time: Option<u64>,
+}
+
+// ========================================================================
+// EVENT STORE
+// ========================================================================
+
+pub struct EventStore {
+    recent_events: VecDeque<MonitorEvent>,
+    tokens: HashMap<String, TokenRecord>,
+    token_order: VecDeque<String>,
+    creators: CreatorBook,
+    pump_fun: PlatformStats,
+    raydium: PlatformStats,
+    total_events: u64,
+    started_at: u64,
+    last_event_time: Option<u64>,
+}
+
+impl EventStore {
+    fn new() -> Self {
+        Self {
+            recent_events: VecDeque::with_capacity(MAX_RECENT_EVENTS),
+            tokens: HashMap::new(),
+            token_order: VecDeque::new(),
+            creators: CreatorBook::default(),
+            pump_fun: PlatformStats::default(),
+            raydium: PlatformStats::default(),
+            total_events: 0,
+            started_at: now_secs(),
+            last_event_time: None,
+        }
+    }
+
+    fn record(&mut self, event: &MonitorEvent) {
+        let now = now_secs();
+        self.total_events += 1;
+        self.last_event_time = Some(now);
+
+        match event {
+            MonitorEvent::Launch(launch) => {
+                // A mint announced again keeps its record (curve, metadata, ...) and counts once
+                if let Entry::Vacant(entry) = self.tokens.entry(launch.contract_address.clone()) {
+                    entry.insert(TokenRecord {
+                        launch: launch.clone(),
+                        trade_count: 0,
+                        buy_count: 0,
+                        first_seen: now,
+                        last_activity: now,
+                        completed: false,
+                        migrated: false,
+                        curve: None,
+                        metadata: None,
+                        identity: None,
+                        creator_reputation: None,
+                        bundle: None,
+                        holders: Vec::new(),
+                    });
+                    self.token_order.push_back(launch.contract_address.clone());
+                    self.platform_stats_mut(launch.platform).launches += 1;
+                    if let Some(creator) = &launch.creator {
+                        self.creators.record_launch(creator, &launch.contract_address, launch.platform, launch.timestamp);
+                    }
+                }
+            },
+            MonitorEvent::Trade(trade) => {
+                self.platform_stats_mut(trade.platform).trades += 1;
+                self.creators.record_trade(trade);
+                if let Some(record) = self.tokens.get_mut(&trade.mint) {
+                    record.trade_count += 1;
+                    if trade.is_buy {
+                        record.buy_count += 1;
+                    }
+                    record.last_activity = now;
+                }
+            },
+            MonitorEvent::Completion(completion) => {
+                self.platform_stats_mut(completion.platform).completions += 1;
+                self.creators.record_completion(&completion.mint, completion.timestamp);
+                if let Some(record) = self.tokens.get_mut(&completion.mint) {
+                    record.completed = true;
+                    record.last_activity = now;
+                }
+            },
+            MonitorEvent::Migration(migration) => {
+                self.platform_stats_mut(migration.platform).migrations += 1;
+                self.creators.record_completion(&migration.mint, migration.timestamp);
+                if let Some(record) = self.tokens.get_mut(&migration.mint) {
+                    record.completed = true;
+                    record.migrated = true;
+                    record.last_activity = now;
+                }
+            },
+            MonitorEvent::Status(update) => {
+                // Keep stored copies in step so queries show the latest commitment
+                for stored in self.recent_events.iter_mut().filter(|e| e.signature() == Some(update.signature.as_str())) {
+                    stored.set_status(update.status);
+                }
+                if let Some(record) = self.tokens.get_mut(&update.mint) {
+                    if record.launch.signature == update.signature {
+                        record.launch.timing.status = update.status;
+                    }
+                }
+            },
+            MonitorEvent::Retraction(retraction) => {
+                self.platform_stats_mut(retraction.platform).retractions += 1;
+                self.recent_events.retain(|e| e.signature() != Some(retraction.signature.as_str()));
+                let launch_retracted = self.tokens.get(&retraction.mint)
+                    .is_some_and(|record| record.launch.signature == retraction.signature);
+                if launch_retracted {
+                    self.creators.retract_launch(&retraction.mint);
+                    self.tokens.remove(&retraction.mint);
+                    self.token_order.retain(|mint| *mint != retraction.mint);
+                }
+            },
+            MonitorEvent::Curve(curve) => {
+                if let Some(record) = self.tokens.get_mut(&curve.mint) {
+                    record.completed |= curve.complete;
+                    record.curve = Some(curve.clone());
+                }
+                // Account updates are high-volume snapshots - kept on the record, not in the event log
+                return;
+            },
+            MonitorEvent::NearCompletion(_) => {},
+            MonitorEvent::Metadata(metadata) => {
+                if let Some(record) = self.tokens.get_mut(&metadata.mint) {
+                    record.metadata = Some(metadata.clone());
+                }
+            },
+            MonitorEvent::Identity(identity) => {
+                // Fill what the launch couldn't carry (LaunchLab buys have no name/symbol/creator)
+                for stored in self.recent_events.iter_mut() {
+                    if let MonitorEvent::Launch(launch) = stored {
+                        if launch.contract_address == identity.mint {
+                            fill_launch(launch, identity);
+                        }
+                    }
+                }
+                if let Some(record) = self.tokens.get_mut(&identity.mint) {
+                    if let (None, Some(creator)) = (&record.launch.creator, &identity.creator) {
+                        let launch = &record.launch;
+                        self.creators.record_launch(creator, &launch.contract_address, launch.platform, launch.timestamp);
+                    }
+                    fill_launch(&mut record.launch, identity);
+                    record.identity = Some(identity.clone());
+                }
+            },
+            MonitorEvent::Creator(reputation) => {
+                if let Some(record) = self.tokens.get_mut(&reputation.mint) {
+                    record.creator_reputation = Some(reputation.clone());
+                }
+            },
+            MonitorEvent::DevBought(_) | MonitorEvent::DevSold(_) => {},
+            MonitorEvent::Bundle(report) => {
+                if let Some(record) = self.tokens.get_mut(&report.mint) {
+                    record.bundle = Some(report.clone());
+                }
+            },
+            MonitorEvent::Holders(snapshot) => {
+                if let Some(record) = self.tokens.get_mut(&snapshot.mint) {
+                    record.holders.push(snapshot.clone());
+                    record.holders.sort_by_key(|s| s.delay_secs);
+                }
+            },
+            MonitorEvent::Alert(_) => {},
+        }
+
+        // Keep memory usage manageable - drop oldest tokens first
+        while self.token_order.len() > MAX_TRACKED_TOKENS {
+            if let Some(oldest) = self.token_order.pop_front() {
+                self.tokens.remove(&oldest);
+            }
+        }
+
+        if self.recent_events.len() >= MAX_RECENT_EVENTS {
+            self.recent_events.pop_front();
+        }
+        self.recent_events.push_back(event.clone());
+    }
+
+    fn platform_stats_mut(&mut self, platform: Platform) -> &mut PlatformStats {
+        match platform {
+            Platform::PumpFun => &mut self.pump_fun,
+            Platform::Raydium => &mut self.raydium,
+        }
+    }
+
+    /// Most recent launches first, optionally restricted to one platform.
+    /// Read from the tracked tokens - trades would push launches out of the event log within seconds.
+    pub fn recent_launches(&self, platform: Option<Platform>, limit: usize) -> Vec<TokenLaunch> {
+        self.token_order
+            .iter()
+            .rev()
+            .filter_map(|mint| self.tokens.get(mint))
+            .map(|record| &record.launch)
+            .filter(|launch| platform.is_none_or(|p| launch.platform == p))
+            .take(limit)
+            .cloned()
+            .collect()
+    }
+
+    pub fn recent_events(&self, limit: usize) -> Vec<MonitorEvent> {
+        self.recent_events.iter().rev().take(limit).cloned().collect()
+    }
+
+    pub fn token(&self, mint: &str) -> Option<TokenRecord> {
+        self.tokens.get(mint).cloned()
+    }
+
+    /// Launch creator of a tracked mint, without cloning its record.
+    pub fn launch_creator(&self, mint: &str) -> Option<&str> {
+        self.tokens.get(mint)?.launch.creator.as_deref()
+    }
+
+    pub fn creator(&self, address: &str) -> Option<&CreatorProfile> {
+        self.creators.profile(address)
+    }
+
+    pub fn stats(&self) -> MonitorStats {
+        MonitorStats {
+            started_at: self.started_at,
+            uptime_secs: now_secs().saturating_sub(self.started_at),
+            total_events: self.total_events,
+            tracked_tokens: self.tokens.len(),
+            pump_fun: self.pump_fun.clone(),
+            raydium: self.raydium.clone(),
+            last_event_time: self.last_event_time,
+        }
+    }
+}
+
+// ========================================================================
+// EVENT HUB - shared between monitors and consumers
+// ========================================================================
+
+/// Cheap to clone: monitors publish into it, the API server reads from it.
+#[derive(Clone)]
+pub struct EventHub {
+    sender: broadcast::Sender<MonitorEvent>,
+    // Unbounded per-subscriber queues for offline runs, see `subscribe_lossless`
+    lossless: Arc<Mutex<Vec<mpsc::UnboundedSender<MonitorEvent>>>>,
+    // Events sitting in those queues, unread
+    backlog: Arc<AtomicUsize>,
+    store: Arc<RwLock<EventStore>>,
+}
+
+/// A consumer's view of the hub: the bounded broadcast, or a lossless queue.
+pub enum EventFeed {
+    Live(broadcast::Receiver<MonitorEvent>),
+    Lossless(mpsc::UnboundedReceiver<MonitorEvent>, Arc<AtomicUsize>),
+}
+
+impl EventFeed {
+    /// Next event. Only live feeds report `Lagged`; lossless ones end with `Closed`
+    /// once the hub closes them and everything queued has been read.
+    pub async fn recv(&mut self) -> Result<MonitorEvent, RecvError> {
+        match self {
+            EventFeed::Live(receiver) => receiver.recv().await,
+            EventFeed::Lossless(receiver, backlog) => {
+                let event = receiver.recv().await.ok_or(RecvError::Closed)?;
+                backlog.fetch_sub(1, Ordering::Relaxed);
+                Ok(event)
+            },
+        }
+    }
+
+    /// An already-published event, if any is waiting. Lag on a live feed is skipped over.
+    pub fn try_recv(&mut self) -> Option<MonitorEvent> {
+        match self {
+            EventFeed::Live(receiver) => loop {
+                match receiver.try_recv() {
+                    Ok(event) => return Some(event),
+                    Err(TryRecvError::Lagged(_)) => continue,
+                    Err(_) => return None,
+                }
+            },
+            EventFeed::Lossless(receiver, backlog) => {
+                let event = receiver.try_recv().ok()?;
+                backlog.fetch_sub(1, Ordering::Relaxed);
+                Some(event)
+            },
+        }
+    }
+
+    /// Events waiting to be read on a lossless feed (live ones report 0).
+    pub fn queued(&self) -> usize {
+        match self {
+            EventFeed::Live(_) => 0,
+            EventFeed::Lossless(receiver, _) => receiver.len(),
+        }
+    }
+}
+
+impl Drop for EventFeed {
+    // A consumer that stops early no longer holds up `EventHub::backlog`
+    fn drop(&mut self) {
+        if let EventFeed::Lossless(receiver, backlog) = self {
+            backlog.fetch_sub(receiver.len(), Ordering::Relaxed);
+        }
+    }
+}
+
+impl EventHub {
+    pub fn new() -> Self {
+        let (sender, _) = broadcast::channel(EVENT_CHANNEL_CAPACITY);
+        Self {
+            sender,
+            lossless: Arc::new(Mutex::new(Vec::new())),
+            backlog: Arc::new(AtomicUsize::new(0)),
+            store: Arc::new(RwLock::new(EventStore::new())),
+        }
+    }
+
+    pub fn publish(&self, event: MonitorEvent) {
+        metrics().event_decoded(&event);
+        if let Ok(mut store) = self.store.write() {
+            store.record(&event);
+        }
+        if let Ok(mut lossless) = self.lossless.lock() {
+            lossless.retain(|feed| {
+                // Counted before sending, so a reader can't take it below zero
+                self.backlog.fetch_add(1, Ordering::Relaxed);
+                let sent = feed.send(event.clone()).is_ok();
+                if !sent {
+                    self.backlog.fetch_sub(1, Ordering::Relaxed);
+                }
+                sent
+            });
+        }
+        // No subscribers is fine - the store still keeps the event
+        let _ = self.sender.send(event);
+    }
+
+    pub fn subscribe(&self) -> broadcast::Receiver<MonitorEvent> {
+        self.sender.subscribe()
+    }
+
+    /// Every event from now on, however far behind the consumer falls. For the sinks
+    /// (the console's CA lines) and for replays, backtests and exports, which publish
+    /// faster than consumers keep up - other live consumers should stay on `subscribe`,
+    /// where a stuck reader can't grow memory.
+    pub fn subscribe_lossless(&self) -> EventFeed {
+        let (sender, receiver) = mpsc::unbounded_channel();
+        self.lossless.lock().unwrap_or_else(|poisoned| poisoned.into_inner()).push(sender);
+        EventFeed::Lossless(receiver, self.backlog.clone())
+    }
+
+    /// Events published to lossless feeds that their consumers haven't read yet.
+    pub fn backlog(&self) -> usize {
+        self.backlog.load(Ordering::Relaxed)
+    }
+
+    /// End every lossless feed once its consumer has read what's queued.
+    pub fn close_lossless(&self) {
+        self.lossless.lock().unwrap_or_else(|poisoned| poisoned.into_inner()).clear();
+    }
+
+    /// Launch creator for the event's mint - carried on launches, looked up otherwise.
+    pub fn creator_of(&self, event: &MonitorEvent) -> Option<String> {
+        match event {
+            MonitorEvent::Launch(launch) => launch.creator.clone(),
+            other => self.read(|store| store.launch_creator(other.mint()).map(str::to_string)),
+        }
+    }
+
+    /// Run a read-only query against the store.
+    pub fn read<T>(&self, f: impl FnOnce(&EventStore) -> T) -> T {
+        let store = self.store.read().unwrap_or_else(|poisoned| poisoned.into_inner());
+        f(&store)
+    }
+}
+
+impl Default for EventHub {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+fn fill_launch(launch: &mut TokenLaunch, identity: &TokenIdentity) {
+    if launch.name.is_none() { launch.name = identity.name.clone(); }
+    if launch.symbol.is_none() { launch.symbol = identity.symbol.clone(); }
+    if launch.creator.is_none() { launch.creator = identity.creator.clone(); }
+    if launch.uri.is_none() { launch.uri = identity.uri.clone(); }
+}
+
+fn now_secs() -> u64 {
+    std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .map(|d| d.as_secs())
+        .unwrap_or(0)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::{EventTiming, TradeEvent};
+
+    fn launch(mint: &str, platform: Platform) -> MonitorEvent {
+        MonitorEvent::Launch(TokenLaunch {
+            contract_address: mint.to_string(),
+            name: None,
+            symbol: None,
+            creator: None,
+            uri: None,
+            signature: format!("launch-{}", mint),
+            platform,
+            timestamp: 0,
+            timing: EventTiming::default(),
+        })
+    }
+
+    fn trade(mint: &str, seq: usize) -> MonitorEvent {
+        MonitorEvent::Trade(TradeEvent {
+            mint: mint.to_string(),
+            trader: None,
+            is_buy: true,
+            sol_amount: None,
+            token_amount: None,
+            signature: format!("trade-{}", seq),
+            platform: Platform::PumpFun,
+            timestamp: 0,
+            timing: EventTiming::default(),
+        })
+    }
+
+    #[test]
+    fn recent_launches_survive_trade_volume() {
+        let mut store = EventStore::new();
+        store.record(&launch("first", Platform::PumpFun));
+        store.record(&launch("second", Platform::Raydium));
+        for seq in 0..MAX_RECENT_EVENTS * 2 {
+            store.record(&trade("first", seq));
+        }
+
+        let mints: Vec<_> = store.recent_launches(None, 10).into_iter().map(|l| l.contract_address).collect();
+        assert_eq!(mints, ["second", "first"]);
+        let pump: Vec<_> = store.recent_launches(Some(Platform::PumpFun), 10).into_iter().map(|l| l.contract_address).collect();
+        assert_eq!(pump, ["first"]);
+        assert_eq!(store.recent_launches(None, 1).len(), 1);
+    }
+
+    #[test]
+    fn repeated_launch_keeps_the_record_and_counts_once() {
+        let mut store = EventStore::new();
+        store.record(&launch("mint", Platform::Raydium));
+        store.record(&trade("mint", 0));
+        store.tokens.get_mut("mint").unwrap().completed = true;
+        store.record(&launch("mint", Platform::Raydium));
+
+        let record = store.token("mint").unwrap();
+        assert_eq!(record.trade_count, 1);
+        assert!(record.completed);
+        assert_eq!(store.stats().raydium.launches, 1);
+        assert_eq!(store.token_order.len(), 1);
+    }
+
+    #[tokio::test]
+    async fn lossless_feed_keeps_everything_until_closed() {
+        let hub = EventHub::new();
+        let mut live = EventFeed::Live(hub.subscribe());
+        let mut lossless = hub.subscribe_lossless();
+        let published = EVENT_CHANNEL_CAPACITY * 2;
+        for seq in 0..published {
+            hub.publish(trade("mint", seq));
+        }
+        hub.close_lossless();
+        assert_eq!(hub.backlog(), published);
+
+        assert!(matches!(live.recv().await, Err(RecvError::Lagged(_))));
+        let mut received = 0;
+        while let Ok(event) = lossless.recv().await {
+            assert_eq!(event.signature(), Some(format!("trade-{}", received).as_str()));
+            received += 1;
+        }
+        assert_eq!(received, published);
+        assert_eq!(hub.backlog(), 0);
+    }
+
+    #[test]
+    fn dropped_lossless_feed_leaves_no_backlog() {
+        let hub = EventHub::new();
+        let mut kept = hub.subscribe_lossless();
+        let dropped = hub.subscribe_lossless();
+        hub.publish(trade("mint", 0));
+        hub.publish(trade("mint", 1));
+        assert_eq!(hub.backlog(), 4);
+
+        drop(dropped);
+        assert!(kept.try_recv().is_some());
+        assert_eq!(hub.backlog(), 1);
+    }
+}
@@ -0,0 +1,23 @@
+//! Account data decoders: pump.fun and LaunchLab curve accounts, mints,
+//! Token-2022 extensions and Metaplex metadata.
+#![no_main]
+
+use libfuzzer_sys::fuzz_target;
+
+use blazing_monitor::launchlab_curve::{GlobalConfig, PlatformConfig, PoolState};
+use blazing_monitor::pump_curve::{BondingCurveAccount, GlobalAccount};
+use blazing_monitor::token_extensions::parse_extensions;
+use blazing_monitor::token_identity::{MetaplexMetadata, MintAccount};
+use blazing_monitor::types::{TOKEN_2022_PROGRAM_ID, TOKEN_PROGRAM_ID};
+
+fuzz_target!(|data: &[u8]| {
+    let _ = BondingCurveAccount::decode(data);
+    let _ = GlobalAccount::decode(data);
+    let _ = PoolState::decode(data);
+    let _ = GlobalConfig::decode(data);
+    let _ = PlatformConfig::decode(data);
+    let _ = MintAccount::decode(data, TOKEN_PROGRAM_ID);
+    let _ = MintAccount::decode(data, TOKEN_2022_PROGRAM_ID);
+    let _ = parse_extensions(data);
+    let _ = MetaplexMetadata::decode(data);
+});
@@ -0,0 +1,13 @@
+//! LaunchLab swap decoding from `getTransaction` (jsonParsed) results.
+#![no_main]
+
+use libfuzzer_sys::fuzz_target;
+use serde_json::Value;
+
+use blazing_monitor::raydium_launchpad_monitor::decode_swap;
+
+fuzz_target!(|input: &[u8]| {
+    if let Ok(transaction) = serde_json::from_slice::<Value>(input) {
+        let _ = decode_swap(&transaction);
+    }
+});
@@ -0,0 +1,36 @@
+//! pump.fun event decoders. The first byte picks the discriminator, so the
+//! fuzzer spends its time inside the decoders rather than guessing prefixes.
+#![no_main]
+
+use base64::{engine::general_purpose, Engine as _};
+use libfuzzer_sys::fuzz_target;
+
+use blazing_monitor::pump_monitor::{
+    decode_complete_event, decode_create_event, decode_migration_event, decode_trade_event, parse_event_from_log,
+};
+use blazing_monitor::types::{
+    EventTiming, PUMP_COMPLETE_EVENT_DISCRIMINATOR, PUMP_CREATE_EVENT_DISCRIMINATOR, PUMP_MIGRATION_EVENT_DISCRIMINATOR,
+    PUMP_TRADE_EVENT_DISCRIMINATOR,
+};
+
+fuzz_target!(|input: &[u8]| {
+    let Some((&selector, body)) = input.split_first() else { return };
+    let discriminator = [
+        PUMP_CREATE_EVENT_DISCRIMINATOR,
+        PUMP_TRADE_EVENT_DISCRIMINATOR,
+        PUMP_COMPLETE_EVENT_DISCRIMINATOR,
+        PUMP_MIGRATION_EVENT_DISCRIMINATOR,
+    ][selector as usize % 4];
+    let data = [&discriminator[..], body].concat();
+    let timing = EventTiming::default();
+
+    let _ = decode_create_event(&data, String::new(), timing.clone());
+    let _ = decode_trade_event(&data, String::new(), timing.clone());
+    let _ = decode_complete_event(&data, String::new(), timing.clone());
+    let _ = decode_migration_event(&data, String::new(), timing.clone());
+
+    let line = format!("Program data: {}", general_purpose::STANDARD.encode(&data));
+    let _ = parse_event_from_log(&line, String::new(), &timing);
+    // And the raw bytes as a log line, for the text handling around the base64
+    let _ = parse_event_from_log(&String::from_utf8_lossy(input), String::new(), &timing);
+});
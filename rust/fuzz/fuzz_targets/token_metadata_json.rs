@@ -0,0 +1,26 @@
+//! Off-chain token metadata documents (the JSON behind a launch's URI).
+#![no_main]
+
+use std::path::PathBuf;
+use std::sync::OnceLock;
+
+use libfuzzer_sys::fuzz_target;
+use serde_json::Value;
+
+use blazing_monitor::config::MetadataConfig;
+use blazing_monitor::metadata_fetcher::{parse_metadata, MetadataResolver};
+
+fn resolver() -> &'static MetadataResolver {
+    static RESOLVER: OnceLock<MetadataResolver> = OnceLock::new();
+    RESOLVER.get_or_init(|| {
+        // No cache directory - nothing is written to disk
+        let settings = MetadataConfig { cache_dir: PathBuf::new(), ..Default::default() };
+        MetadataResolver::new(&settings).expect("resolver")
+    })
+}
+
+fuzz_target!(|input: &[u8]| {
+    if let Ok(document) = serde_json::from_slice::<Value>(input) {
+        let _ = parse_metadata(&document, resolver());
+    }
+});
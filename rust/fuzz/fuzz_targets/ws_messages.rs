@@ -0,0 +1,57 @@
+//! Raw WebSocket text frames through both monitors' message handling, the
+//! same path `--replay` uses. Nothing is fetched: Raydium swaps resolve
+//! against an empty recorded-transaction map.
+#![no_main]
+
+use std::collections::HashMap;
+use std::sync::{Mutex, OnceLock};
+
+use libfuzzer_sys::fuzz_target;
+use tokio::runtime::Runtime;
+use tokio::sync::mpsc;
+
+use blazing_monitor::block_clock::BlockClock;
+use blazing_monitor::config::Config;
+use blazing_monitor::event_store::EventHub;
+use blazing_monitor::pump_monitor::PumpFunMonitor;
+use blazing_monitor::raydium_launchpad_monitor::RaydiumBuyMonitor;
+
+struct Monitors {
+    runtime: Runtime,
+    pump: PumpFunMonitor,
+    raydium: RaydiumBuyMonitor,
+}
+
+// Building the monitors (HTTP clients) dominates an iteration, so keep one pair
+fn monitors() -> &'static Mutex<Monitors> {
+    static MONITORS: OnceLock<Mutex<Monitors>> = OnceLock::new();
+    MONITORS.get_or_init(|| {
+        let runtime = tokio::runtime::Builder::new_current_thread().enable_all().build().expect("runtime");
+        let config = Config::default();
+        let events = EventHub::new();
+        let (pump, raydium) = runtime.block_on(async {
+            let pump = PumpFunMonitor::new(&config, events.clone(), BlockClock::new(&config)?).await?;
+            let raydium = RaydiumBuyMonitor::new(&config, events.clone(), BlockClock::new(&config)?).await?;
+            anyhow::Ok((pump, raydium))
+        }).expect("monitors");
+        Mutex::new(Monitors { runtime, pump, raydium })
+    })
+}
+
+fuzz_target!(|input: &[u8]| {
+    let Ok(frame) = std::str::from_utf8(input) else { return };
+    let mut guard = monitors().lock().unwrap();
+    let Monitors { runtime, pump, raydium } = &mut *guard;
+
+    runtime.block_on(async {
+        let (sender, frames) = mpsc::channel(1);
+        sender.send(frame.to_string()).await.unwrap();
+        drop(sender);
+        pump.replay(frames).await;
+
+        let (sender, frames) = mpsc::channel(1);
+        sender.send(frame.to_string()).await.unwrap();
+        drop(sender);
+        raydium.replay(frames, HashMap::new()).await;
+    });
+});
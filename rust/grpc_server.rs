@@ -0,0 +1,644 @@
+use anyhow::Result;
+use futures_util::{Stream, StreamExt};
+use std::pin::Pin;
+use tokio_stream::wrappers::{BroadcastStream, errors::BroadcastStreamRecvError};
+use tonic::{Request, Response, Status};
+use tracing::{info, warn};
+
+use crate::event_filter::EventFilter;
+use crate::event_store::{EventHub, TokenRecord};
+use crate::types::{
+    BondingCurveState, BundleReport, CreatorReputation, DevActivity, EventTiming, HolderSnapshot, HolderSource, MonitorEvent, NearCompletion, NearCompletionTrigger, Platform, RetractReason,
+    RiskFlag, RiskSeverity, TokenIdentity, TokenLaunch, TokenMetadata, TokenProgram, TxStatus,
+};
+
+pub mod proto {
+    tonic::include_proto!("blazing.monitor.v1");
+}
+
+use proto::monitor_service_server::{MonitorService, MonitorServiceServer};
+
+const DEFAULT_LIST_LIMIT: usize = 50;
+const MAX_LIST_LIMIT: usize = 500;
+
+/// Typed event feed for backend services (tonic).
+pub struct GrpcServer {
+    bind_addr: String,
+    events: EventHub,
+}
+
+impl GrpcServer {
+    pub fn new(bind_addr: &str, events: EventHub) -> Self {
+        Self {
+            bind_addr: bind_addr.to_string(),
+            events,
+        }
+    }
+
+    pub async fn run(self) -> Result<()> {
+        let addr = self.bind_addr.parse()?;
+        info!("📡 gRPC server listening on {}", addr);
+
+        tonic::transport::Server::builder()
+            .add_service(MonitorServiceServer::new(MonitorGrpcService { events: self.events }))
+            .serve(addr)
+            .await?;
+        Ok(())
+    }
+}
+
+struct MonitorGrpcService {
+    events: EventHub,
+}
+
+#[tonic::async_trait]
+impl MonitorService for MonitorGrpcService {
+    type SubscribeStream = Pin<Box<dyn Stream<Item = Result<proto::Event, Status>> + Send + 'static>>;
+
+    async fn subscribe(&self, request: Request<proto::Filter>) -> Result<Response<Self::SubscribeStream>, Status> {
+        let filter = filter_from_proto(request.into_inner());
+        let events = self.events.clone();
+
+        let stream = BroadcastStream::new(self.events.subscribe()).filter_map(move |event| {
+            let result = match event {
+                Ok(event) => {
+                    let creator = if filter.needs_creator() { events.creator_of(&event) } else { None };
+                    filter.matches(&event, creator.as_deref()).then(|| Ok(event_to_proto(event)))
+                },
+                Err(BroadcastStreamRecvError::Lagged(skipped)) => {
+                    warn!("⚠️ gRPC subscriber lagging, skipped {} events", skipped);
+                    None
+                },
+            };
+            async move { result }
+        });
+
+        Ok(Response::new(Box::pin(stream)))
+    }
+
+    async fn get_token(&self, request: Request<proto::GetTokenRequest>) -> Result<Response<proto::TokenRecord>, Status> {
+        let mint = request.into_inner().mint;
+        match self.events.read(|store| store.token(&mint)) {
+            Some(record) => Ok(Response::new(token_record_to_proto(record))),
+            None => Err(Status::not_found(format!("token {} not found", mint))),
+        }
+    }
+
+    async fn list_launches(&self, request: Request<proto::ListLaunchesRequest>) -> Result<Response<proto::ListLaunchesResponse>, Status> {
+        let request = request.into_inner();
+        let platform = platform_from_proto(request.platform());
+        let limit = match request.limit as usize {
+            0 => DEFAULT_LIST_LIMIT,
+            limit => limit.min(MAX_LIST_LIMIT),
+        };
+
+        let launches = self.events.read(|store| store.recent_launches(platform, limit));
+        Ok(Response::new(proto::ListLaunchesResponse {
+            launches: launches.into_iter().map(launch_to_proto).collect(),
+        }))
+    }
+}
+
+// ========================================================================
+// SUBSCRIPTION FILTER
+// ========================================================================
+
+fn filter_from_proto(filter: proto::Filter) -> EventFilter {
+    EventFilter {
+        platforms: filter.platforms().filter_map(platform_from_proto).collect(),
+        event_types: filter.event_types().filter_map(event_kind_from_proto).map(str::to_string).collect(),
+        creator_allow: filter.creator_allow.into_iter().collect(),
+        creator_deny: filter.creator_deny.into_iter().collect(),
+        mint_deny: Default::default(),
+    }
+}
+
+// ========================================================================
+// CONVERSIONS
+// ========================================================================
+
+fn event_kind_from_proto(event_type: proto::EventType) -> Option<&'static str> {
+    match event_type {
+        proto::EventType::Launch => Some("launch"),
+        proto::EventType::Trade => Some("trade"),
+        proto::EventType::Completion => Some("completion"),
+        proto::EventType::Migration => Some("migration"),
+        proto::EventType::Status => Some("status"),
+        proto::EventType::Retraction => Some("retraction"),
+        proto::EventType::Curve => Some("curve"),
+        proto::EventType::NearCompletion => Some("near_completion"),
+        proto::EventType::Metadata => Some("metadata"),
+        proto::EventType::Identity => Some("identity"),
+        proto::EventType::Creator => Some("creator"),
+        proto::EventType::DevBought => Some("dev_bought"),
+        proto::EventType::DevSold => Some("dev_sold"),
+        proto::EventType::Bundle => Some("bundle"),
+        proto::EventType::Holders => Some("holders"),
+        proto::EventType::Alert => Some("alert"),
+        proto::EventType::Unspecified => None,
+    }
+}
+
+fn platform_to_proto(platform: Platform) -> i32 {
+    match platform {
+        Platform::PumpFun => proto::Platform::PumpFun as i32,
+        Platform::Raydium => proto::Platform::Raydium as i32,
+    }
+}
+
+fn platform_from_proto(platform: proto::Platform) -> Option<Platform> {
+    match platform {
+        proto::Platform::PumpFun => Some(Platform::PumpFun),
+        proto::Platform::Raydium => Some(Platform::Raydium),
+        proto::Platform::Unspecified => None,
+    }
+}
+
+fn status_to_proto(status: TxStatus) -> i32 {
+    match status {
+        TxStatus::Processed => proto::TxStatus::Processed as i32,
+        TxStatus::Confirmed => proto::TxStatus::Confirmed as i32,
+        TxStatus::Finalized => proto::TxStatus::Finalized as i32,
+    }
+}
+
+fn retract_reason_to_proto(reason: RetractReason) -> i32 {
+    match reason {
+        RetractReason::Failed => proto::RetractReason::Failed as i32,
+        RetractReason::Dropped => proto::RetractReason::Dropped as i32,
+    }
+}
+
+fn timing_to_proto(timing: EventTiming) -> proto::EventTiming {
+    proto::EventTiming {
+        slot: timing.slot,
+        block_time_ms: timing.block_time_ms,
+        detected_at_ms: timing.detected_at_ms,
+        status: status_to_proto(timing.status),
+    }
+}
+
+fn launch_to_proto(launch: TokenLaunch) -> proto::TokenLaunch {
+    proto::TokenLaunch {
+        contract_address: launch.contract_address,
+        name: launch.name,
+        symbol: launch.symbol,
+        creator: launch.creator,
+        uri: launch.uri,
+        signature: launch.signature,
+        platform: platform_to_proto(launch.platform),
+        timestamp: launch.timestamp,
+        timing: Some(timing_to_proto(launch.timing)),
+    }
+}
+
+fn curve_to_proto(curve: BondingCurveState) -> proto::BondingCurve {
+    proto::BondingCurve {
+        mint: curve.mint,
+        bonding_curve: curve.bonding_curve,
+        virtual_token_reserves: curve.virtual_token_reserves,
+        virtual_sol_reserves: curve.virtual_sol_reserves,
+        real_token_reserves: curve.real_token_reserves,
+        real_sol_reserves: curve.real_sol_reserves,
+        token_total_supply: curve.token_total_supply,
+        complete: curve.complete,
+        progress_pct: curve.progress_pct,
+        price_sol: curve.price_sol,
+        market_cap_sol: curve.market_cap_sol,
+        sol_to_complete: curve.sol_to_complete,
+        platform: platform_to_proto(curve.platform),
+        timestamp: curve.timestamp,
+        timing: Some(timing_to_proto(curve.timing)),
+    }
+}
+
+fn metadata_to_proto(metadata: TokenMetadata) -> proto::TokenMetadata {
+    proto::TokenMetadata {
+        mint: metadata.mint,
+        uri: metadata.uri,
+        name: metadata.name,
+        symbol: metadata.symbol,
+        description: metadata.description,
+        image: metadata.image,
+        twitter: metadata.twitter,
+        telegram: metadata.telegram,
+        website: metadata.website,
+        platform: platform_to_proto(metadata.platform),
+        timestamp: metadata.timestamp,
+        timing: Some(timing_to_proto(metadata.timing)),
+    }
+}
+
+fn risk_severity_to_proto(severity: Option<RiskSeverity>) -> i32 {
+    match severity {
+        Some(RiskSeverity::Low) => proto::RiskSeverity::Low as i32,
+        Some(RiskSeverity::Medium) => proto::RiskSeverity::Medium as i32,
+        Some(RiskSeverity::High) => proto::RiskSeverity::High as i32,
+        None => proto::RiskSeverity::Unspecified as i32,
+    }
+}
+
+fn risk_flag_to_proto(flag: RiskFlag) -> proto::RiskFlag {
+    use proto::RiskFlagKind as Kind;
+
+    let mut out = proto::RiskFlag {
+        severity: risk_severity_to_proto(Some(flag.severity())),
+        ..Default::default()
+    };
+    let kind = match flag {
+        RiskFlag::MintAuthority { authority } => {
+            out.account = Some(authority);
+            Kind::MintAuthority
+        },
+        RiskFlag::FreezeAuthority { authority } => {
+            out.account = Some(authority);
+            Kind::FreezeAuthority
+        },
+        RiskFlag::TransferFee { basis_points, maximum_fee, authority } => {
+            out.basis_points = Some(basis_points as u32);
+            out.maximum_fee = Some(maximum_fee);
+            out.account = authority;
+            Kind::TransferFee
+        },
+        RiskFlag::PermanentDelegate { delegate } => {
+            out.account = Some(delegate);
+            Kind::PermanentDelegate
+        },
+        RiskFlag::TransferHook { program, authority } => {
+            out.account = program.or(authority);
+            Kind::TransferHook
+        },
+        RiskFlag::NonTransferable => Kind::NonTransferable,
+        RiskFlag::DefaultFrozen => Kind::DefaultFrozen,
+        RiskFlag::MintCloseAuthority { authority } => {
+            out.account = Some(authority);
+            Kind::MintCloseAuthority
+        },
+        RiskFlag::Pausable { paused } => {
+            out.paused = Some(paused);
+            Kind::Pausable
+        },
+        RiskFlag::ConfidentialTransfers => Kind::ConfidentialTransfers,
+        RiskFlag::ScaledUiAmount => Kind::ScaledUiAmount,
+        RiskFlag::InterestBearing => Kind::InterestBearing,
+    };
+    out.kind = kind as i32;
+    out
+}
+
+fn identity_to_proto(identity: TokenIdentity) -> proto::TokenIdentity {
+    let token_program = match identity.token_program {
+        TokenProgram::Token => proto::TokenProgram::Token,
+        TokenProgram::Token2022 => proto::TokenProgram::Token2022,
+    };
+    proto::TokenIdentity {
+        mint: identity.mint,
+        token_program: token_program as i32,
+        decimals: identity.decimals as u32,
+        supply: identity.supply,
+        mint_authority: identity.mint_authority,
+        freeze_authority: identity.freeze_authority,
+        name: identity.name,
+        symbol: identity.symbol,
+        uri: identity.uri,
+        update_authority: identity.update_authority,
+        is_mutable: identity.is_mutable,
+        creator: identity.creator,
+        platform: platform_to_proto(identity.platform),
+        timestamp: identity.timestamp,
+        timing: Some(timing_to_proto(identity.timing)),
+        extensions: identity.extensions,
+        risk_flags: identity.risk_flags.into_iter().map(risk_flag_to_proto).collect(),
+        risk_level: risk_severity_to_proto(identity.risk_level),
+    }
+}
+
+fn creator_to_proto(reputation: CreatorReputation) -> proto::CreatorReputation {
+    proto::CreatorReputation {
+        mint: reputation.mint,
+        creator: reputation.creator,
+        prior_launches: reputation.prior_launches,
+        launches_in_window: reputation.launches_in_window,
+        completion_rate_pct: reputation.completion_rate_pct,
+        avg_time_to_completion_secs: reputation.avg_time_to_completion_secs,
+        avg_share_sold_pct: reputation.avg_share_sold_pct,
+        dumped_tokens: reputation.dumped_tokens,
+        serial_launcher: reputation.serial_launcher,
+        platform: platform_to_proto(reputation.platform),
+        timestamp: reputation.timestamp,
+        timing: Some(timing_to_proto(reputation.timing)),
+    }
+}
+
+fn dev_activity_to_proto(activity: DevActivity) -> proto::DevActivity {
+    proto::DevActivity {
+        mint: activity.mint,
+        creator: activity.creator,
+        token_amount: activity.token_amount,
+        sol_amount: activity.sol_amount,
+        supply_pct: activity.supply_pct,
+        net_position_pct: activity.net_position_pct,
+        secs_since_launch: activity.secs_since_launch,
+        signature: activity.signature,
+        platform: platform_to_proto(activity.platform),
+        timestamp: activity.timestamp,
+        timing: Some(timing_to_proto(activity.timing)),
+    }
+}
+
+fn bundle_to_proto(report: BundleReport) -> proto::BundleReport {
+    proto::BundleReport {
+        mint: report.mint,
+        creator: report.creator,
+        launch_slot: report.launch_slot,
+        same_slot_buyers: report.same_slot_buyers,
+        same_tx_buyers: report.same_tx_buyers,
+        wallets: report.wallets.into_iter().map(|wallet| proto::BundleWallet {
+            wallet: wallet.wallet,
+            slot: wallet.slot,
+            same_tx: wallet.same_tx,
+            same_slot: wallet.same_slot,
+            is_creator: wallet.is_creator,
+            token_amount: wallet.token_amount,
+            supply_pct: wallet.supply_pct,
+            funder: wallet.funder,
+        }).collect(),
+        clusters: report.clusters.into_iter().map(|cluster| proto::FundingCluster {
+            funder: cluster.funder,
+            creator_funded: cluster.creator_funded,
+            wallets: cluster.wallets,
+            supply_pct: cluster.supply_pct,
+        }).collect(),
+        bundled_supply_pct: report.bundled_supply_pct,
+        bundle_score: report.bundle_score,
+        bundled: report.bundled,
+        platform: platform_to_proto(report.platform),
+        timestamp: report.timestamp,
+        timing: Some(timing_to_proto(report.timing)),
+    }
+}
+
+fn holders_to_proto(snapshot: HolderSnapshot) -> proto::HolderSnapshot {
+    let source = match snapshot.source {
+        HolderSource::AllAccounts => proto::HolderSource::AllAccounts,
+        HolderSource::LargestAccounts => proto::HolderSource::LargestAccounts,
+    };
+    proto::HolderSnapshot {
+        mint: snapshot.mint,
+        delay_secs: snapshot.delay_secs,
+        source: source as i32,
+        holder_count: snapshot.holder_count,
+        top10_share_pct: snapshot.top10_share_pct,
+        gini: snapshot.gini,
+        circulating_pct: snapshot.circulating_pct,
+        top_holders: snapshot.top_holders.into_iter().map(|holder| proto::Holder {
+            owner: holder.owner,
+            amount: holder.amount,
+            pct: holder.pct,
+        }).collect(),
+        platform: platform_to_proto(snapshot.platform),
+        timestamp: snapshot.timestamp,
+        timing: Some(timing_to_proto(snapshot.timing)),
+    }
+}
+
+fn near_completion_to_proto(alert: NearCompletion) -> proto::NearCompletion {
+    let trigger = match alert.trigger {
+        NearCompletionTrigger::Progress => proto::NearCompletionTrigger::Progress,
+        NearCompletionTrigger::Eta => proto::NearCompletionTrigger::Eta,
+    };
+    proto::NearCompletion {
+        mint: alert.mint,
+        platform: platform_to_proto(alert.platform),
+        trigger: trigger as i32,
+        progress_pct: alert.progress_pct,
+        sol_to_complete: alert.sol_to_complete,
+        velocity: Some(proto::CurveVelocity {
+            sol_per_minute: alert.velocity.sol_per_minute,
+            token_volume_per_minute: alert.velocity.token_volume_per_minute,
+            trades_per_minute: alert.velocity.trades_per_minute,
+            calculated_over: alert.velocity.calculated_over,
+        }),
+        prediction: Some(proto::CompletionPrediction {
+            estimated_minutes: alert.prediction.estimated_minutes,
+            confidence: alert.prediction.confidence,
+            based_on_recent_velocity: alert.prediction.based_on_recent_velocity,
+        }),
+        timestamp: alert.timestamp,
+        timing: Some(timing_to_proto(alert.timing)),
+    }
+}
+
+fn event_to_proto(event: MonitorEvent) -> proto::Event {
+    use proto::event::Payload;
+
+    let payload = match event {
+        MonitorEvent::Launch(launch) => Payload::Launch(launch_to_proto(launch)),
+        MonitorEvent::Trade(trade) => Payload::Trade(proto::Trade {
+            mint: trade.mint,
+            trader: trade.trader,
+            is_buy: trade.is_buy,
+            sol_amount: trade.sol_amount,
+            token_amount: trade.token_amount,
+            signature: trade.signature,
+            platform: platform_to_proto(trade.platform),
+            timestamp: trade.timestamp,
+            timing: Some(timing_to_proto(trade.timing)),
+        }),
+        MonitorEvent::Completion(completion) => Payload::Completion(proto::Completion {
+            mint: completion.mint,
+            bonding_curve: completion.bonding_curve,
+            user: completion.user,
+            signature: completion.signature,
+            platform: platform_to_proto(completion.platform),
+            timestamp: completion.timestamp,
+            timing: Some(timing_to_proto(completion.timing)),
+        }),
+        MonitorEvent::Migration(migration) => Payload::Migration(proto::Migration {
+            mint: migration.mint,
+            pool: migration.pool,
+            sol_amount: migration.sol_amount,
+            token_amount: migration.token_amount,
+            signature: migration.signature,
+            platform: platform_to_proto(migration.platform),
+            timestamp: migration.timestamp,
+            timing: Some(timing_to_proto(migration.timing)),
+        }),
+        MonitorEvent::Status(update) => Payload::Status(proto::StatusUpdate {
+            mint: update.mint,
+            signature: update.signature,
+            platform: platform_to_proto(update.platform),
+            status: status_to_proto(update.status),
+            timestamp: update.timestamp,
+            timing: Some(timing_to_proto(update.timing)),
+        }),
+        MonitorEvent::Retraction(retraction) => Payload::Retraction(proto::Retraction {
+            mint: retraction.mint,
+            signature: retraction.signature,
+            platform: platform_to_proto(retraction.platform),
+            reason: retract_reason_to_proto(retraction.reason),
+            timestamp: retraction.timestamp,
+            timing: Some(timing_to_proto(retraction.timing)),
+        }),
+        MonitorEvent::Curve(curve) => Payload::Curve(curve_to_proto(curve)),
+        MonitorEvent::NearCompletion(alert) => Payload::NearCompletion(near_completion_to_proto(alert)),
+        MonitorEvent::Metadata(metadata) => Payload::Metadata(metadata_to_proto(metadata)),
+        MonitorEvent::Identity(identity) => Payload::Identity(identity_to_proto(identity)),
+        MonitorEvent::Creator(reputation) => Payload::Creator(creator_to_proto(reputation)),
+        MonitorEvent::DevBought(activity) => Payload::DevBought(dev_activity_to_proto(activity)),
+        MonitorEvent::DevSold(activity) => Payload::DevSold(dev_activity_to_proto(activity)),
+        MonitorEvent::Bundle(report) => Payload::Bundle(bundle_to_proto(report)),
+        MonitorEvent::Holders(snapshot) => Payload::Holders(holders_to_proto(snapshot)),
+        MonitorEvent::Alert(alert) => Payload::Alert(proto::ScriptAlert {
+            mint: alert.mint,
+            script: alert.script,
+            message: alert.message,
+            source_kind: alert.source_kind,
+            platform: platform_to_proto(alert.platform),
+            timestamp: alert.timestamp,
+            timing: Some(timing_to_proto(alert.timing)),
+        }),
+    };
+
+    proto::Event { payload: Some(payload) }
+}
+
+fn token_record_to_proto(record: TokenRecord) -> proto::TokenRecord {
+    proto::TokenRecord {
+        launch: Some(launch_to_proto(record.launch)),
+        trade_count: record.trade_count,
+        buy_count: record.buy_count,
+        first_seen: record.first_seen,
+        last_activity: record.last_activity,
+        completed: record.completed,
+        migrated: record.migrated,
+        curve: record.curve.map(curve_to_proto),
+        metadata: record.metadata.map(metadata_to_proto),
+        identity: record.identity.map(identity_to_proto),
+        creator_reputation: record.creator_reputation.map(creator_to_proto),
+        bundle: record.bundle.map(bundle_to_proto),
+        holders: record.holders.into_iter().map(holders_to_proto).collect(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::TradeEvent;
+
+    fn launch(mint: &str, creator: &str) -> TokenLaunch {
+        TokenLaunch {
+            contract_address: mint.to_string(),
+            name: Some("Test".to_string()),
+            symbol: Some("TST".to_string()),
+            creator: Some(creator.to_string()),
+            uri: None,
+            signature: format!("launch-{}", mint),
+            platform: Platform::Raydium,
+            timestamp: 1_700_000_000,
+            timing: EventTiming { slot: Some(42), block_time_ms: Some(1_700_000_000_000), detected_at_ms: 1_700_000_000_250, status: TxStatus::Processed },
+        }
+    }
+
+    fn trade(mint: &str) -> MonitorEvent {
+        MonitorEvent::Trade(TradeEvent {
+            mint: mint.to_string(),
+            trader: Some("trader".to_string()),
+            is_buy: false,
+            sol_amount: Some(5),
+            token_amount: Some(7),
+            signature: format!("trade-{}", mint),
+            platform: Platform::PumpFun,
+            timestamp: 1_700_000_001,
+            timing: EventTiming::default(),
+        })
+    }
+
+    #[test]
+    fn filter_drops_unspecified_and_unknown_enum_values() {
+        let filter = filter_from_proto(proto::Filter {
+            platforms: vec![proto::Platform::Raydium as i32, proto::Platform::Unspecified as i32, 99],
+            event_types: vec![proto::EventType::Launch as i32, proto::EventType::DevSold as i32, proto::EventType::Unspecified as i32, 99],
+            creator_allow: vec!["alice".to_string()],
+            creator_deny: vec!["mallory".to_string()],
+        });
+
+        assert_eq!(filter.platforms, [Platform::Raydium].into_iter().collect());
+        assert_eq!(filter.event_types, ["launch", "dev_sold"].into_iter().map(str::to_string).collect());
+        assert!(filter.creator_allow.contains("alice"));
+        assert!(filter.creator_deny.contains("mallory"));
+        assert!(filter.mint_deny.is_empty());
+        assert!(filter.needs_creator());
+    }
+
+    #[test]
+    fn filter_matches_like_the_proto_describes() {
+        let filter = filter_from_proto(proto::Filter {
+            platforms: vec![proto::Platform::Raydium as i32],
+            event_types: vec![proto::EventType::Launch as i32],
+            creator_allow: vec!["alice".to_string()],
+            creator_deny: vec![],
+        });
+
+        assert!(filter.matches(&MonitorEvent::Launch(launch("mint", "alice")), Some("alice")));
+        assert!(!filter.matches(&MonitorEvent::Launch(launch("mint", "bob")), Some("bob")));
+        assert!(!filter.matches(&trade("mint"), Some("alice")));
+
+        let everything = filter_from_proto(proto::Filter::default());
+        assert!(everything.matches(&trade("mint"), None));
+        assert!(!everything.needs_creator());
+    }
+
+    #[test]
+    fn every_event_type_maps_to_a_distinct_kind() {
+        let kinds: Vec<&str> = (1..=16).filter_map(|value| proto::EventType::try_from(value).ok()).filter_map(event_kind_from_proto).collect();
+        let unique: std::collections::HashSet<&str> = kinds.iter().copied().collect();
+
+        assert_eq!(kinds.len(), 16);
+        assert_eq!(unique.len(), 16);
+        assert_eq!(event_kind_from_proto(proto::EventType::Unspecified), None);
+        assert_eq!(event_kind_from_proto(proto::EventType::Trade), Some(trade("mint").kind()));
+        assert_eq!(event_kind_from_proto(proto::EventType::Launch), Some(MonitorEvent::Launch(launch("mint", "alice")).kind()));
+    }
+
+    #[test]
+    fn platform_round_trips() {
+        for platform in [Platform::PumpFun, Platform::Raydium] {
+            let wire = proto::Platform::try_from(platform_to_proto(platform)).unwrap();
+            assert_eq!(platform_from_proto(wire), Some(platform));
+        }
+        assert_eq!(platform_from_proto(proto::Platform::Unspecified), None);
+    }
+
+    #[test]
+    fn launch_conversion_keeps_fields_and_timing() {
+        let converted = launch_to_proto(launch("mint", "alice"));
+
+        assert_eq!(converted.contract_address, "mint");
+        assert_eq!(converted.symbol.as_deref(), Some("TST"));
+        assert_eq!(converted.creator.as_deref(), Some("alice"));
+        assert_eq!(converted.platform, proto::Platform::Raydium as i32);
+        assert_eq!(converted.timestamp, 1_700_000_000);
+
+        let timing = converted.timing.unwrap();
+        assert_eq!(timing.slot, Some(42));
+        assert_eq!(timing.block_time_ms, Some(1_700_000_000_000));
+        assert_eq!(timing.detected_at_ms, 1_700_000_000_250);
+        assert_eq!(timing.status, proto::TxStatus::Processed as i32);
+    }
+
+    #[test]
+    fn event_conversion_picks_matching_payload() {
+        let Some(proto::event::Payload::Trade(converted)) = event_to_proto(trade("mint")).payload else {
+            panic!("trade should convert to a trade payload");
+        };
+
+        assert_eq!(converted.mint, "mint");
+        assert_eq!(converted.trader.as_deref(), Some("trader"));
+        assert!(!converted.is_buy);
+        assert_eq!(converted.sol_amount, Some(5));
+        assert_eq!(converted.token_amount, Some(7));
+        assert_eq!(converted.platform, proto::Platform::PumpFun as i32);
+        assert_eq!(converted.timing.unwrap().status, proto::TxStatus::Confirmed as i32);
+    }
+}
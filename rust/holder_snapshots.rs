@@ -0,0 +1,359 @@
+use anyhow::{anyhow, Result};
+use base64::{Engine as _, engine::general_purpose};
+use serde_json::{json, Value};
+use solana_sdk::pubkey::Pubkey;
+use std::collections::{HashMap, HashSet};
+use std::str::FromStr;
+use std::sync::Arc;
+use tokio::sync::broadcast::error::RecvError;
+use tokio::sync::Semaphore;
+use tokio::time::{interval, Duration, MissedTickBehavior};
+use tracing::{info, warn, debug};
+
+use crate::block_clock::now_ms;
+use crate::config::{Commitment, Config, HolderConfig};
+use crate::event_store::{EventFeed, EventHub};
+use crate::launchlab_curve::sol_pool_address;
+use crate::pump_curve::{bonding_curve_address, GlobalAccount};
+use crate::rpc_scheduler::{Priority, RpcScheduler};
+use crate::types::{
+    EventTiming, Holder, HolderSnapshot, HolderSource, MonitorEvent, Platform, TokenProgram, TxStatus,
+    RAYDIUM_LAUNCHPAD_AUTHORITY, TOKEN_2022_PROGRAM_ID, TOKEN_PROGRAM_ID,
+};
+
+const TOP_HOLDERS: usize = 10;
+// SPL token account: mint | owner | amount ...
+const TOKEN_ACCOUNT_LEN: u64 = 165;
+const CHECK_INTERVAL_SECS: u64 = 1;
+
+// ========================================================================
+// DISTRIBUTION MATH
+// ========================================================================
+
+/// Gini coefficient of the balances: 0 = everyone holds the same, → 1 = one wallet holds everything.
+pub fn gini(balances: &[u64]) -> f64 {
+    let mut sorted: Vec<u64> = balances.iter().copied().filter(|b| *b > 0).collect();
+    let n = sorted.len();
+    let total: f64 = sorted.iter().map(|b| *b as f64).sum();
+    if n < 2 || total == 0.0 {
+        return 0.0;
+    }
+    sorted.sort_unstable();
+    let weighted: f64 = sorted.iter().enumerate().map(|(i, b)| (i as f64 + 1.0) * *b as f64).sum();
+    let n = n as f64;
+    (2.0 * weighted) / (n * total) - (n + 1.0) / n
+}
+
+pub struct Distribution {
+    pub top_holders: Vec<Holder>,
+    pub top10_share_pct: f64,
+    pub gini: f64,
+    pub circulating_pct: f64,
+}
+
+/// Snapshot metrics from per-owner balances, curve/vault owners already removed.
+pub fn summarize(balances: HashMap<String, u64>, supply: u64) -> Distribution {
+    let pct = |amount: u64| if supply > 0 { amount as f64 / supply as f64 * 100.0 } else { 0.0 };
+    let mut holders: Vec<(String, u64)> = balances.into_iter().filter(|(_, amount)| *amount > 0).collect();
+    holders.sort_by(|a, b| b.1.cmp(&a.1).then_with(|| a.0.cmp(&b.0)));
+
+    let amounts: Vec<u64> = holders.iter().map(|(_, amount)| *amount).collect();
+    let circulating: u64 = amounts.iter().sum();
+    let top10: u64 = amounts.iter().take(TOP_HOLDERS).sum();
+    let top_holders = holders.into_iter().take(TOP_HOLDERS)
+        .map(|(owner, amount)| Holder { pct: pct(amount), owner, amount })
+        .collect();
+    Distribution {
+        top_holders,
+        top10_share_pct: pct(top10),
+        gini: gini(&amounts),
+        circulating_pct: pct(circulating),
+    }
+}
+
+// ========================================================================
+// RPC
+// ========================================================================
+
+#[derive(Clone)]
+struct HolderReader {
+    rpc: RpcScheduler,
+    commitment: Commitment,
+    count_holders: bool,
+}
+
+impl HolderReader {
+    /// Every token account when the RPC allows it, the 20 largest otherwise.
+    async fn balances(&self, mint: &str, token_program: TokenProgram) -> Result<(HashMap<String, u64>, HolderSource)> {
+        if self.count_holders {
+            match self.all_accounts(mint, token_program).await {
+                Ok(balances) => return Ok((balances, HolderSource::AllAccounts)),
+                Err(e) => debug!("⚠️ getProgramAccounts failed for {}, using largest accounts: {}", mint, e),
+            }
+        }
+        Ok((self.largest_accounts(mint).await?, HolderSource::LargestAccounts))
+    }
+
+    async fn all_accounts(&self, mint: &str, token_program: TokenProgram) -> Result<HashMap<String, u64>> {
+        // Token-2022 accounts carry extensions, so their size varies
+        let (program, mut filters) = match token_program {
+            TokenProgram::Token => (TOKEN_PROGRAM_ID, vec![json!({ "dataSize": TOKEN_ACCOUNT_LEN })]),
+            TokenProgram::Token2022 => (TOKEN_2022_PROGRAM_ID, Vec::new()),
+        };
+        filters.push(json!({ "memcmp": { "offset": 0, "bytes": mint } }));
+
+        let accounts = self.call("getProgramAccounts", json!([program, {
+            "encoding": "base64",
+            "commitment": self.commitment.as_str(),
+            "dataSlice": { "offset": 32, "length": 40 },
+            "filters": filters,
+        }])).await?;
+
+        let mut balances = HashMap::new();
+        for account in accounts.as_array().ok_or_else(|| anyhow!("expected account list"))? {
+            let Some(data) = account.get("account").and_then(|a| a.get("data")).and_then(|d| d.get(0)).and_then(|d| d.as_str()) else {
+                continue;
+            };
+            let bytes = general_purpose::STANDARD.decode(data)?;
+            if bytes.len() < 40 {
+                continue;
+            }
+            let owner = Pubkey::new_from_array(bytes[..32].try_into()?).to_string();
+            let amount = u64::from_le_bytes(bytes[32..40].try_into()?);
+            *balances.entry(owner).or_default() += amount;
+        }
+        Ok(balances)
+    }
+
+    async fn largest_accounts(&self, mint: &str) -> Result<HashMap<String, u64>> {
+        let largest = self.call("getTokenLargestAccounts", json!([mint, { "commitment": self.commitment.as_str() }])).await?;
+        let accounts: Vec<(String, u64)> = largest.get("value").and_then(|v| v.as_array()).into_iter().flatten()
+            .filter_map(|account| {
+                let address = account.get("address")?.as_str()?.to_string();
+                let amount = account.get("amount")?.as_str()?.parse().ok()?;
+                Some((address, amount))
+            })
+            .collect();
+        if accounts.is_empty() {
+            return Ok(HashMap::new());
+        }
+
+        // Token account -> owner
+        let addresses: Vec<&str> = accounts.iter().map(|(address, _)| address.as_str()).collect();
+        let infos = self.call("getMultipleAccounts", json!([addresses, {
+            "encoding": "jsonParsed",
+            "commitment": self.commitment.as_str(),
+        }])).await?;
+        let infos = infos.get("value").and_then(|v| v.as_array()).ok_or_else(|| anyhow!("missing result.value"))?;
+
+        let mut balances = HashMap::new();
+        for ((address, amount), info) in accounts.into_iter().zip(infos) {
+            let owner = info.get("data")
+                .and_then(|d| d.get("parsed"))
+                .and_then(|p| p.get("info"))
+                .and_then(|i| i.get("owner"))
+                .and_then(|o| o.as_str())
+                .map(str::to_string)
+                .unwrap_or(address);
+            *balances.entry(owner).or_default() += amount;
+        }
+        Ok(balances)
+    }
+
+    async fn call(&self, method: &str, params: Value) -> Result<Value> {
+        self.rpc.result(Priority::Background, method, params).await
+    }
+}
+
+/// Curve and pool vault owners - their balance is unsold supply, not a holder.
+fn excluded_owners(mint: &str, platform: Platform) -> HashSet<String> {
+    let mut owners = HashSet::from([RAYDIUM_LAUNCHPAD_AUTHORITY.to_string()]);
+    if let Ok(mint) = Pubkey::from_str(mint) {
+        match platform {
+            Platform::PumpFun => { owners.insert(bonding_curve_address(&mint).to_string()); },
+            Platform::Raydium => { owners.insert(sol_pool_address(&mint).to_string()); },
+        }
+    }
+    owners
+}
+
+// ========================================================================
+// SCHEDULER
+// ========================================================================
+
+struct ScheduledSnapshot {
+    due_ms: u64,
+    mint: String,
+    platform: Platform,
+    delay_secs: u64,
+}
+
+/// Takes holder snapshots at fixed delays after each launch and publishes them as `Holders` events.
+pub struct HolderSnapshotter {
+    settings: HolderConfig,
+    reader: HolderReader,
+    events: EventHub,
+    receiver: EventFeed,
+    limiter: Arc<Semaphore>,
+    scheduled: Vec<ScheduledSnapshot>,
+}
+
+impl HolderSnapshotter {
+    pub fn new(config: &Config, events: EventHub, feed: EventFeed) -> Result<Self> {
+        Ok(Self {
+            reader: HolderReader {
+                rpc: RpcScheduler::shared(config)?.with_timeout(Duration::from_secs(10)),
+                commitment: config.commitment,
+                count_holders: config.holders.count_holders,
+            },
+            limiter: Arc::new(Semaphore::new(config.holders.max_concurrent.max(1))),
+            settings: config.holders.clone(),
+            receiver: feed,
+            events,
+            scheduled: Vec::new(),
+        })
+    }
+
+    pub async fn run(mut self) {
+        info!("👥 Holder snapshots started (at {:?}s after launch)", self.settings.delays_secs);
+
+        let mut ticker = interval(Duration::from_secs(CHECK_INTERVAL_SECS));
+        ticker.set_missed_tick_behavior(MissedTickBehavior::Delay);
+
+        loop {
+            tokio::select! {
+                event = self.receiver.recv() => match event {
+                    Ok(MonitorEvent::Launch(launch)) => self.schedule(&launch.contract_address, launch.platform, launch.timing.detected_at_ms),
+                    Ok(_) => {},
+                    Err(RecvError::Lagged(skipped)) => warn!("⚠️ Holder snapshots lagging, skipped {} events", skipped),
+                    Err(RecvError::Closed) => break,
+                },
+                _ = ticker.tick() => self.take_due(now_ms()),
+            }
+        }
+    }
+
+    fn schedule(&mut self, mint: &str, platform: Platform, launched_at_ms: u64) {
+        if self.scheduled.iter().any(|s| s.mint == mint) {
+            return;
+        }
+        if self.scheduled.len() + self.settings.delays_secs.len() > self.settings.max_scheduled {
+            debug!("⏸️ Holder snapshot queue full, skipping {}", mint);
+            return;
+        }
+        for &delay_secs in &self.settings.delays_secs {
+            self.scheduled.push(ScheduledSnapshot {
+                due_ms: launched_at_ms + delay_secs * 1000,
+                mint: mint.to_string(),
+                platform,
+                delay_secs,
+            });
+        }
+    }
+
+    fn take_due(&mut self, now_ms: u64) {
+        let (due, waiting): (Vec<_>, Vec<_>) = std::mem::take(&mut self.scheduled).into_iter().partition(|s| s.due_ms <= now_ms);
+        self.scheduled = waiting;
+
+        for snapshot in due {
+            // Supply and token program come from the identity when it has been read
+            let identity = self.events.read(|store| store.token(&snapshot.mint).and_then(|record| record.identity));
+            let supply = identity.as_ref().map(|i| i.supply).filter(|s| *s > 0).unwrap_or(GlobalAccount::default().token_total_supply);
+            let token_program = identity.map_or(TokenProgram::Token, |i| i.token_program);
+
+            let reader = self.reader.clone();
+            let events = self.events.clone();
+            let limiter = self.limiter.clone();
+            tokio::spawn(async move {
+                let Ok(_permit) = limiter.acquire_owned().await else { return };
+                match take_snapshot(&reader, &snapshot, supply, token_program).await {
+                    Ok(snapshot) => {
+                        info!(
+                            "👥 [{}] CA: {} | +{}s | Holders: {} | Top 10: {:.1}% | Gini: {:.2}",
+                            snapshot.platform,
+                            snapshot.mint,
+                            snapshot.delay_secs,
+                            snapshot.holder_count.map_or("20+".to_string(), |count| count.to_string()),
+                            snapshot.top10_share_pct,
+                            snapshot.gini
+                        );
+                        events.publish(MonitorEvent::Holders(snapshot));
+                    },
+                    Err(e) => warn!("⚠️ Holder snapshot failed for {}: {}", snapshot.mint, e),
+                }
+            });
+        }
+    }
+}
+
+async fn take_snapshot(reader: &HolderReader, scheduled: &ScheduledSnapshot, supply: u64, token_program: TokenProgram) -> Result<HolderSnapshot> {
+    let (mut balances, source) = reader.balances(&scheduled.mint, token_program).await?;
+    let excluded = excluded_owners(&scheduled.mint, scheduled.platform);
+    balances.retain(|owner, amount| *amount > 0 && !excluded.contains(owner));
+
+    let holder_count = (source == HolderSource::AllAccounts).then_some(balances.len() as u64);
+    let distribution = summarize(balances, supply);
+    let timing = EventTiming {
+        slot: None,
+        block_time_ms: None,
+        detected_at_ms: now_ms(),
+        status: TxStatus::default(),
+    };
+
+    Ok(HolderSnapshot {
+        mint: scheduled.mint.clone(),
+        delay_secs: scheduled.delay_secs,
+        source,
+        holder_count,
+        top10_share_pct: distribution.top10_share_pct,
+        gini: distribution.gini,
+        circulating_pct: distribution.circulating_pct,
+        top_holders: distribution.top_holders,
+        platform: scheduled.platform,
+        timestamp: timing.detected_at_ms / 1000,
+        timing,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn gini_bounds() {
+        assert_eq!(gini(&[]), 0.0);
+        assert!(gini(&[100, 100, 100, 100]).abs() < 1e-12);
+        // One whale among many dust wallets approaches 1
+        let mut balances = vec![1u64; 99];
+        balances.push(1_000_000);
+        assert!(gini(&balances) > 0.98);
+        // Known value: [1, 2, 3, 4] -> 0.25
+        assert!((gini(&[4, 1, 3, 2]) - 0.25).abs() < 1e-12);
+    }
+
+    #[test]
+    fn summary_ranks_and_shares() {
+        let balances: HashMap<String, u64> = (0..15u64)
+            .map(|i| (format!("holder{:02}", i), (i + 1) * 1_000))
+            .chain([("empty".to_string(), 0)])
+            .collect();
+        let distribution = summarize(balances, 1_000_000);
+
+        assert_eq!(distribution.top_holders.len(), 10);
+        assert_eq!(distribution.top_holders[0].owner, "holder14");
+        assert_eq!(distribution.top_holders[0].amount, 15_000);
+        // 6k..15k = 105k of 1M; everyone = 120k
+        assert!((distribution.top10_share_pct - 10.5).abs() < 1e-9);
+        assert!((distribution.circulating_pct - 12.0).abs() < 1e-9);
+    }
+
+    #[test]
+    fn excludes_curve_and_vault_owners() {
+        let mint = Pubkey::new_unique();
+        let pump = excluded_owners(&mint.to_string(), Platform::PumpFun);
+        assert!(pump.contains(&bonding_curve_address(&mint).to_string()));
+        assert!(pump.contains(RAYDIUM_LAUNCHPAD_AUTHORITY));
+        let launchlab = excluded_owners(&mint.to_string(), Platform::Raydium);
+        assert!(launchlab.contains(&sol_pool_address(&mint).to_string()));
+    }
+}
@@ -0,0 +1,636 @@
+//! Raydium LaunchLab pool math. Pools trade base token A against quote token B
+//! (usually WSOL) on one of three curves until `total_fund_raising_b` is raised,
+//! then migrate. Pure functions, no I/O.
+
+use anyhow::{anyhow, Result};
+use solana_sdk::pubkey::Pubkey;
+use std::str::FromStr;
+
+use crate::types::{
+    LAUNCHLAB_GLOBAL_CONFIG_DISCRIMINATOR, LAUNCHLAB_PLATFORM_CONFIG_DISCRIMINATOR,
+    LAUNCHLAB_POOL_STATE_DISCRIMINATOR, RAYDIUM_PROGRAM_ID,
+};
+
+// Fee rates are parts per million
+pub const FEE_RATE_DENOMINATOR: u64 = 1_000_000;
+pub const WSOL_MINT: &str = "So11111111111111111111111111111111111111112";
+
+// ========================================================================
+// ACCOUNTS
+// ========================================================================
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CurveType {
+    ConstantProduct,
+    FixedPrice,
+    Linear,
+}
+
+impl CurveType {
+    pub fn from_u8(value: u8) -> Result<Self> {
+        match value {
+            0 => Ok(CurveType::ConstantProduct),
+            1 => Ok(CurveType::FixedPrice),
+            2 => Ok(CurveType::Linear),
+            other => Err(anyhow!("Unknown LaunchLab curve type: {}", other)),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PoolStatus {
+    Trading,
+    // Fundraising target hit, waiting for the migrate instruction
+    Migrating,
+    Migrated,
+}
+
+/// Decoded LaunchLab `PoolState` account.
+#[derive(Debug, Clone, PartialEq)]
+pub struct PoolState {
+    pub status: PoolStatus,
+    pub mint_decimals_a: u8,
+    pub mint_decimals_b: u8,
+    pub supply: u64,
+    // Base tokens the curve sells before migrating
+    pub total_sell_a: u64,
+    pub virtual_a: u64,
+    pub virtual_b: u64,
+    // Base sold / quote raised so far
+    pub real_a: u64,
+    pub real_b: u64,
+    // Migration threshold, in quote units
+    pub total_fund_raising_b: u64,
+    pub global_config: Pubkey,
+    pub platform_config: Pubkey,
+    pub mint_a: Pubkey,
+    pub mint_b: Pubkey,
+    pub creator: Pubkey,
+}
+
+impl PoolState {
+    pub fn decode(data: &[u8]) -> Result<Self> {
+        // discriminator | epoch | bump, status, decimals a/b, migrate type | 10 x u64
+        // | vesting schedule (5 x u64) | 7 x pubkey
+        const PUBKEYS: usize = 8 + 8 + 5 + 10 * 8 + 5 * 8;
+        if data.len() < PUBKEYS + 7 * 32 {
+            return Err(anyhow!("Invalid data length for pool state: {}", data.len()));
+        }
+        if data[..8] != LAUNCHLAB_POOL_STATE_DISCRIMINATOR {
+            return Err(anyhow!("Unknown discriminator for pool state"));
+        }
+
+        let status = match data[17] {
+            0 => PoolStatus::Trading,
+            1 => PoolStatus::Migrating,
+            _ => PoolStatus::Migrated,
+        };
+        let pubkey_at = |index: usize| pubkey_at(data, PUBKEYS + index * 32);
+
+        Ok(Self {
+            status,
+            mint_decimals_a: data[18],
+            mint_decimals_b: data[19],
+            supply: u64_at(data, 21),
+            total_sell_a: u64_at(data, 29),
+            virtual_a: u64_at(data, 37),
+            virtual_b: u64_at(data, 45),
+            real_a: u64_at(data, 53),
+            real_b: u64_at(data, 61),
+            total_fund_raising_b: u64_at(data, 69),
+            global_config: pubkey_at(0),
+            platform_config: pubkey_at(1),
+            mint_a: pubkey_at(2),
+            mint_b: pubkey_at(3),
+            creator: pubkey_at(6),
+        })
+    }
+}
+
+/// Decoded LaunchLab `GlobalConfig` account - curve type and protocol fee.
+#[derive(Debug, Clone, PartialEq)]
+pub struct GlobalConfig {
+    pub curve_type: CurveType,
+    pub migrate_fee: u64,
+    pub trade_fee_rate: u64,
+    pub mint_b: Pubkey,
+}
+
+impl GlobalConfig {
+    pub fn decode(data: &[u8]) -> Result<Self> {
+        // discriminator | epoch | curve_type (u8) | index (u16) | migrate_fee | trade_fee_rate
+        // | 6 x u64 limits | mint_b
+        if data.len() < 83 + 32 {
+            return Err(anyhow!("Invalid data length for global config: {}", data.len()));
+        }
+        if data[..8] != LAUNCHLAB_GLOBAL_CONFIG_DISCRIMINATOR {
+            return Err(anyhow!("Unknown discriminator for global config"));
+        }
+
+        Ok(Self {
+            curve_type: CurveType::from_u8(data[16])?,
+            migrate_fee: u64_at(data, 19),
+            trade_fee_rate: u64_at(data, 27),
+            mint_b: pubkey_at(data, 83),
+        })
+    }
+}
+
+/// The part of a LaunchLab `PlatformConfig` that affects quotes.
+#[derive(Debug, Clone, PartialEq)]
+pub struct PlatformConfig {
+    pub fee_rate: u64,
+    pub name: String,
+}
+
+impl PlatformConfig {
+    pub fn decode(data: &[u8]) -> Result<Self> {
+        // discriminator | epoch | fee wallet | nft wallet | 3 x scale (u64) | fee_rate | name [u8; 64]
+        if data.len() < 112 + 64 {
+            return Err(anyhow!("Invalid data length for platform config: {}", data.len()));
+        }
+        if data[..8] != LAUNCHLAB_PLATFORM_CONFIG_DISCRIMINATOR {
+            return Err(anyhow!("Unknown discriminator for platform config"));
+        }
+
+        let name = &data[112..176];
+        let end = name.iter().position(|b| *b == 0).unwrap_or(name.len());
+        Ok(Self {
+            fee_rate: u64_at(data, 104),
+            name: String::from_utf8_lossy(&name[..end]).into_owned(),
+        })
+    }
+}
+
+/// Pool PDA for a base/quote mint pair.
+pub fn pool_address(mint_a: &Pubkey, mint_b: &Pubkey) -> Pubkey {
+    let program_id = Pubkey::from_str(RAYDIUM_PROGRAM_ID).expect("valid program id");
+    Pubkey::find_program_address(&[b"pool", mint_a.as_ref(), mint_b.as_ref()], &program_id).0
+}
+
+/// Pool PDA for a token quoted in SOL - the common case.
+pub fn sol_pool_address(mint_a: &Pubkey) -> Pubkey {
+    pool_address(mint_a, &Pubkey::from_str(WSOL_MINT).expect("valid mint"))
+}
+
+fn u64_at(data: &[u8], offset: usize) -> u64 {
+    u64::from_le_bytes(data[offset..offset + 8].try_into().expect("length checked"))
+}
+
+fn pubkey_at(data: &[u8], offset: usize) -> Pubkey {
+    Pubkey::new_from_array(data[offset..offset + 32].try_into().expect("length checked"))
+}
+
+// ========================================================================
+// CURVES
+// ========================================================================
+
+/// Raw curve functions over (virtual, real) amounts, fees excluded.
+/// Buys take quote B and return base A; sells the reverse.
+impl CurveType {
+    fn buy_exact_in(&self, pool: &PoolState, amount_b: u64) -> Option<u64> {
+        match self {
+            CurveType::ConstantProduct => {
+                let input_reserve = pool.virtual_b.checked_add(pool.real_b)?;
+                let output_reserve = pool.virtual_a.checked_sub(pool.real_a)?;
+                mul_div_floor(amount_b, output_reserve, input_reserve.checked_add(amount_b)?)
+            },
+            CurveType::FixedPrice => mul_div_floor(amount_b, pool.virtual_a, pool.virtual_b),
+            CurveType::Linear => {
+                let a = linear_slope(pool)?;
+                let x0 = pool.real_a as f64;
+                let x1 = (x0 * x0 + 2.0 * amount_b as f64 / a).sqrt();
+                Some((x1 - x0).floor() as u64)
+            },
+        }
+    }
+
+    fn buy_exact_out(&self, pool: &PoolState, amount_a: u64) -> Option<u64> {
+        match self {
+            CurveType::ConstantProduct => {
+                let input_reserve = pool.virtual_b.checked_add(pool.real_b)?;
+                let output_reserve = pool.virtual_a.checked_sub(pool.real_a)?;
+                let remaining = output_reserve.checked_sub(amount_a).filter(|r| *r > 0)?;
+                mul_div_ceil(amount_a, input_reserve, remaining)
+            },
+            CurveType::FixedPrice => mul_div_ceil(amount_a, pool.virtual_b, pool.virtual_a),
+            CurveType::Linear => {
+                let a = linear_slope(pool)?;
+                let x0 = pool.real_a as f64;
+                let x1 = x0 + amount_a as f64;
+                Some((a / 2.0 * (x1 * x1 - x0 * x0)).ceil() as u64)
+            },
+        }
+    }
+
+    fn sell_exact_in(&self, pool: &PoolState, amount_a: u64) -> Option<u64> {
+        match self {
+            CurveType::ConstantProduct => {
+                let input_reserve = pool.virtual_a.checked_sub(pool.real_a)?;
+                let output_reserve = pool.virtual_b.checked_add(pool.real_b)?;
+                mul_div_floor(amount_a, output_reserve, input_reserve.checked_add(amount_a)?)
+            },
+            CurveType::FixedPrice => mul_div_floor(amount_a, pool.virtual_b, pool.virtual_a),
+            CurveType::Linear => {
+                let a = linear_slope(pool)?;
+                let x0 = pool.real_a as f64;
+                let x1 = x0 - amount_a as f64;
+                if x1 < 0.0 {
+                    return None;
+                }
+                Some((a / 2.0 * (x0 * x0 - x1 * x1)).floor() as u64)
+            },
+        }
+    }
+
+    fn sell_exact_out(&self, pool: &PoolState, amount_b: u64) -> Option<u64> {
+        match self {
+            CurveType::ConstantProduct => {
+                let input_reserve = pool.virtual_a.checked_sub(pool.real_a)?;
+                let output_reserve = pool.virtual_b.checked_add(pool.real_b)?;
+                let remaining = output_reserve.checked_sub(amount_b).filter(|r| *r > 0)?;
+                mul_div_ceil(amount_b, input_reserve, remaining)
+            },
+            CurveType::FixedPrice => mul_div_ceil(amount_b, pool.virtual_a, pool.virtual_b),
+            CurveType::Linear => {
+                let a = linear_slope(pool)?;
+                let x0 = pool.real_a as f64;
+                let x1_squared = x0 * x0 - 2.0 * amount_b as f64 / a;
+                if x1_squared < 0.0 {
+                    return None;
+                }
+                Some((x0 - x1_squared.sqrt()).ceil() as u64)
+            },
+        }
+    }
+
+    /// Quote units per base unit at the current point on the curve.
+    fn spot_price(&self, pool: &PoolState) -> f64 {
+        match self {
+            CurveType::ConstantProduct => {
+                let base = pool.virtual_a.saturating_sub(pool.real_a);
+                if base == 0 {
+                    return 0.0;
+                }
+                (pool.virtual_b as f64 + pool.real_b as f64) / base as f64
+            },
+            CurveType::FixedPrice => {
+                if pool.virtual_a == 0 {
+                    return 0.0;
+                }
+                pool.virtual_b as f64 / pool.virtual_a as f64
+            },
+            CurveType::Linear => linear_slope(pool).map_or(0.0, |a| a * pool.real_a as f64),
+        }
+    }
+}
+
+// Linear curves store the slope in Q64.64 as `virtual_a`
+fn linear_slope(pool: &PoolState) -> Option<f64> {
+    (pool.virtual_a > 0).then(|| pool.virtual_a as f64 / 2f64.powi(64))
+}
+
+// ========================================================================
+// QUOTE ENGINE
+// ========================================================================
+
+/// Fee rates charged on the quote side, in parts per million.
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct FeeRates {
+    pub protocol: u64,
+    pub platform: u64,
+    pub share: u64,
+}
+
+impl FeeRates {
+    pub fn new(config: &GlobalConfig, platform: Option<&PlatformConfig>) -> Self {
+        Self {
+            protocol: config.trade_fee_rate,
+            platform: platform.map_or(0, |p| p.fee_rate),
+            share: 0,
+        }
+    }
+
+    // Rates come from on-chain config - `None` if they add up past 100%
+    fn total(&self) -> Option<u64> {
+        self.protocol.checked_add(self.platform)?.checked_add(self.share)
+            .filter(|rate| *rate <= FEE_RATE_DENOMINATOR)
+    }
+}
+
+/// Result of a simulated trade. Amounts are base / quote token base units.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Quote {
+    pub amount_in: u64,
+    pub amount_out: u64,
+    // Always in quote units
+    pub fee: u64,
+    // Execution price vs. spot price before the trade, in percent
+    pub price_impact_pct: f64,
+    // Pool after the trade
+    pub after: PoolState,
+}
+
+/// Everything needed to price a pool: its state plus its config's curve and fees.
+#[derive(Debug, Clone, PartialEq)]
+pub struct LaunchLabCurve {
+    pub pool: PoolState,
+    pub curve_type: CurveType,
+    pub fees: FeeRates,
+}
+
+impl LaunchLabCurve {
+    pub fn new(pool: PoolState, config: &GlobalConfig, platform: Option<&PlatformConfig>) -> Self {
+        Self {
+            pool,
+            curve_type: config.curve_type,
+            fees: FeeRates::new(config, platform),
+        }
+    }
+
+    fn is_trading(&self) -> bool {
+        self.pool.status == PoolStatus::Trading
+    }
+
+    /// Base tokens still for sale before migration.
+    pub fn remaining_a(&self) -> u64 {
+        self.pool.total_sell_a.saturating_sub(self.pool.real_a)
+    }
+
+    /// Spot price of one whole base token in whole quote tokens.
+    pub fn price(&self) -> f64 {
+        let scale = 10f64.powi(self.pool.mint_decimals_a as i32 - self.pool.mint_decimals_b as i32);
+        self.curve_type.spot_price(&self.pool) * scale
+    }
+
+    /// Fully diluted market cap in whole quote tokens.
+    pub fn market_cap(&self) -> f64 {
+        self.price() * self.pool.supply as f64 / 10f64.powi(self.pool.mint_decimals_a as i32)
+    }
+
+    /// 0-100, quote raised against the migration threshold.
+    pub fn progress_pct(&self) -> f64 {
+        if !self.is_trading() || self.pool.total_fund_raising_b == 0 {
+            return 100.0;
+        }
+        (self.pool.real_b as f64 / self.pool.total_fund_raising_b as f64 * 100.0).min(100.0)
+    }
+
+    /// Quote units still needed to reach the migration threshold (fees excluded).
+    pub fn quote_to_migrate(&self) -> u64 {
+        if !self.is_trading() {
+            return 0;
+        }
+        self.pool.total_fund_raising_b.saturating_sub(self.pool.real_b)
+    }
+
+    /// Spend exactly `amount_b` quote units (fees included). Capped at the
+    /// remaining base tokens, in which case less is spent.
+    pub fn quote_buy_exact_in(&self, amount_b: u64) -> Option<Quote> {
+        if !self.is_trading() || amount_b == 0 {
+            return None;
+        }
+        let fee = fee_on(amount_b, self.fees.total()?);
+        let net_b = amount_b.checked_sub(fee)?;
+        let amount_a = self.curve_type.buy_exact_in(&self.pool, net_b)?;
+        if amount_a == 0 {
+            return None;
+        }
+        if amount_a >= self.remaining_a() {
+            return self.quote_buy_exact_out(self.remaining_a());
+        }
+        Some(self.buy_quote(amount_a, net_b, fee))
+    }
+
+    /// Buy exactly `amount_a` base units. `None` past the migration threshold.
+    pub fn quote_buy_exact_out(&self, amount_a: u64) -> Option<Quote> {
+        if !self.is_trading() || amount_a == 0 || amount_a > self.remaining_a() {
+            return None;
+        }
+        let cost = self.curve_type.buy_exact_out(&self.pool, amount_a)?;
+        let gross = gross_up(cost, self.fees.total()?)?;
+        Some(self.buy_quote(amount_a, cost, gross - cost))
+    }
+
+    /// Sell exactly `amount_a` base units; `amount_out` is quote after fees.
+    pub fn quote_sell_exact_in(&self, amount_a: u64) -> Option<Quote> {
+        if !self.is_trading() || amount_a == 0 || amount_a > self.pool.real_a {
+            return None;
+        }
+        let gross = self.curve_type.sell_exact_in(&self.pool, amount_a)?;
+        if gross == 0 || gross > self.pool.real_b {
+            return None;
+        }
+        let fee = fee_on(gross, self.fees.total()?);
+        Some(self.sell_quote(amount_a, gross, fee))
+    }
+
+    /// Base units to sell so exactly `amount_b` quote units arrive after fees.
+    pub fn quote_sell_exact_out(&self, amount_b: u64) -> Option<Quote> {
+        if !self.is_trading() || amount_b == 0 {
+            return None;
+        }
+        let gross = gross_up(amount_b, self.fees.total()?)?;
+        if gross > self.pool.real_b {
+            return None;
+        }
+        let amount_a = self.curve_type.sell_exact_out(&self.pool, gross)?;
+        if amount_a > self.pool.real_a {
+            return None;
+        }
+        Some(self.sell_quote(amount_a, gross, gross - amount_b))
+    }
+
+    fn buy_quote(&self, amount_a: u64, net_b: u64, fee: u64) -> Quote {
+        let after = PoolState {
+            real_a: self.pool.real_a + amount_a,
+            real_b: self.pool.real_b + net_b,
+            ..self.pool.clone()
+        };
+        Quote {
+            amount_in: net_b + fee,
+            amount_out: amount_a,
+            fee,
+            price_impact_pct: price_impact(self.curve_type.spot_price(&self.pool), net_b, amount_a),
+            after,
+        }
+    }
+
+    fn sell_quote(&self, amount_a: u64, gross_b: u64, fee: u64) -> Quote {
+        let after = PoolState {
+            real_a: self.pool.real_a - amount_a,
+            real_b: self.pool.real_b - gross_b,
+            ..self.pool.clone()
+        };
+        Quote {
+            amount_in: amount_a,
+            amount_out: gross_b - fee,
+            fee,
+            price_impact_pct: price_impact(self.curve_type.spot_price(&self.pool), gross_b, amount_a),
+            after,
+        }
+    }
+}
+
+fn fee_on(amount: u64, rate: u64) -> u64 {
+    (amount as u128 * rate as u128).div_ceil(FEE_RATE_DENOMINATOR as u128) as u64
+}
+
+// Smallest gross amount that leaves `net` after the fee
+fn gross_up(net: u64, rate: u64) -> Option<u64> {
+    if rate >= FEE_RATE_DENOMINATOR {
+        return None;
+    }
+    let mut gross = u64::try_from(
+        (net as u128 * FEE_RATE_DENOMINATOR as u128).div_ceil((FEE_RATE_DENOMINATOR - rate) as u128),
+    ).ok()?;
+    while gross - fee_on(gross, rate) < net {
+        gross = gross.checked_add(1)?;
+    }
+    Some(gross)
+}
+
+fn price_impact(spot_price: f64, amount_b: u64, amount_a: u64) -> f64 {
+    if spot_price == 0.0 || amount_a == 0 {
+        return 0.0;
+    }
+    (amount_b as f64 / amount_a as f64 / spot_price - 1.0).abs() * 100.0
+}
+
+fn mul_div_floor(a: u64, b: u64, denominator: u64) -> Option<u64> {
+    if denominator == 0 {
+        return None;
+    }
+    u64::try_from(a as u128 * b as u128 / denominator as u128).ok()
+}
+
+fn mul_div_ceil(a: u64, b: u64, denominator: u64) -> Option<u64> {
+    if denominator == 0 {
+        return None;
+    }
+    u64::try_from((a as u128 * b as u128).div_ceil(denominator as u128)).ok()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // Default LaunchLab (bonk.fun style) constant-product pool: 1B supply, 793.1M for sale, 85 SOL target
+    fn pool(curve_type: CurveType) -> LaunchLabCurve {
+        let pool = PoolState {
+            status: PoolStatus::Trading,
+            mint_decimals_a: 6,
+            mint_decimals_b: 9,
+            supply: 1_000_000_000_000_000,
+            total_sell_a: 793_100_000_000_000,
+            virtual_a: 1_073_025_605_596_382,
+            virtual_b: 30_000_852_951,
+            real_a: 0,
+            real_b: 0,
+            total_fund_raising_b: 85_000_000_000,
+            global_config: Pubkey::default(),
+            platform_config: Pubkey::default(),
+            mint_a: Pubkey::default(),
+            mint_b: Pubkey::default(),
+            creator: Pubkey::default(),
+        };
+        LaunchLabCurve {
+            pool,
+            curve_type,
+            fees: FeeRates { protocol: 2_500, platform: 10_000, share: 0 },
+        }
+    }
+
+    #[test]
+    fn constant_product_fills_the_target_when_sold_out() {
+        let curve = pool(CurveType::ConstantProduct);
+        let quote = curve.quote_buy_exact_in(200_000_000_000).unwrap();
+        assert_eq!(quote.amount_out, curve.remaining_a());
+
+        let raised = quote.after.real_b as f64 / 1e9;
+        assert!((raised - 85.0).abs() < 0.1, "{}", raised);
+        assert!(quote.after.real_a == quote.after.total_sell_a);
+    }
+
+    #[test]
+    fn round_trips_never_profit() {
+        for curve_type in [CurveType::ConstantProduct, CurveType::FixedPrice] {
+            let curve = pool(curve_type);
+            for amount in [1_000u64, 10_000_000, 1_000_000_000, 20_000_000_000] {
+                let Some(buy) = curve.quote_buy_exact_in(amount) else { continue };
+                let after = LaunchLabCurve { pool: buy.after.clone(), ..curve.clone() };
+                if let Some(sell) = after.quote_sell_exact_in(buy.amount_out) {
+                    assert!(sell.amount_out <= buy.amount_in, "{:?} {}", curve_type, amount);
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn exact_out_quotes_cover_the_request() {
+        let curve = pool(CurveType::ConstantProduct);
+        let buy = curve.quote_buy_exact_out(1_000_000_000_000).unwrap();
+        assert_eq!(buy.amount_out, 1_000_000_000_000);
+
+        let after = LaunchLabCurve { pool: buy.after.clone(), ..curve.clone() };
+        let sell = after.quote_sell_exact_out(10_000_000).unwrap();
+        assert_eq!(sell.amount_out, 10_000_000);
+        assert!(sell.amount_in <= buy.amount_out);
+    }
+
+    #[test]
+    fn linear_curve_price_rises_with_sales() {
+        let mut curve = pool(CurveType::Linear);
+        // Slope so that selling 793.1M tokens raises ~85 SOL: a = 2 * B / A^2, in Q64.64
+        let a = 2.0 * 85e9 / (793.1e12f64 * 793.1e12);
+        curve.pool.virtual_a = (a * 2f64.powi(64)) as u64;
+
+        let first = curve.quote_buy_exact_in(1_000_000_000).unwrap();
+        let next = LaunchLabCurve { pool: first.after.clone(), ..curve.clone() };
+        let second = next.quote_buy_exact_in(1_000_000_000).unwrap();
+        assert!(second.amount_out < first.amount_out);
+        assert!(next.price() > curve.price());
+    }
+
+    #[test]
+    fn migrated_pools_do_not_quote() {
+        let mut curve = pool(CurveType::ConstantProduct);
+        curve.pool.status = PoolStatus::Migrated;
+        assert!(curve.quote_buy_exact_in(1_000_000_000).is_none());
+        assert_eq!(curve.progress_pct(), 100.0);
+    }
+
+    #[test]
+    fn fees_past_the_whole_amount_do_not_quote() {
+        let mut curve = pool(CurveType::ConstantProduct);
+        curve.fees = FeeRates { protocol: 600_000, platform: 500_000, share: 0 };
+        assert!(curve.quote_buy_exact_in(1_000_000_000).is_none());
+        assert!(curve.quote_sell_exact_in(1_000).is_none());
+
+        curve.fees = FeeRates { protocol: u64::MAX, platform: 1, share: 0 };
+        assert!(curve.quote_buy_exact_out(1_000).is_none());
+    }
+
+    #[test]
+    fn decodes_global_config() {
+        let mut data = LAUNCHLAB_GLOBAL_CONFIG_DISCRIMINATOR.to_vec();
+        data.extend_from_slice(&7u64.to_le_bytes()); // epoch
+        data.push(2); // curve_type
+        data.extend_from_slice(&3u16.to_le_bytes()); // index
+        data.extend_from_slice(&15u64.to_le_bytes()); // migrate_fee
+        data.extend_from_slice(&2_500u64.to_le_bytes()); // trade_fee_rate
+        for limit in 1u64..=6 {
+            data.extend_from_slice(&limit.to_le_bytes());
+        }
+        data.extend_from_slice(&[9u8; 32]); // mint_b
+        data.extend_from_slice(&[0u8; 32 * 4]); // trailing wallets
+
+        let config = GlobalConfig::decode(&data).unwrap();
+        assert_eq!(config.curve_type, CurveType::Linear);
+        assert_eq!(config.migrate_fee, 15);
+        assert_eq!(config.trade_fee_rate, 2_500);
+        assert_eq!(config.mint_b, Pubkey::new_from_array([9u8; 32]));
+        assert!(GlobalConfig::decode(&data[..83 + 31]).is_err());
+        assert!(GlobalConfig::decode(&data[..83 + 32]).is_ok());
+    }
+}
@@ -0,0 +1,34 @@
+//! Blazing Monitor - real-time pump.fun and Raydium LaunchLab token detection.
+//!
+//! The binary (`main.rs`) wires these together; the decoders and curve math
+//! are usable on their own.
+
+pub mod types;
+pub mod config;
+pub mod event_filter;
+pub mod metrics;
+pub mod backtest;
+pub mod block_clock;
+pub mod bonding_curve_tracker;
+pub mod bundle_detector;
+pub mod commitment_tracker;
+pub mod completion_predictor;
+pub mod creator_profiler;
+pub mod dev_watcher;
+pub mod event_store;
+pub mod api_server;
+pub mod grpc_server;
+pub mod holder_snapshots;
+pub mod launchlab_curve;
+pub mod metadata_fetcher;
+pub mod parquet_export;
+pub mod pump_curve;
+pub mod pump_monitor;
+pub mod raydium_launchpad_monitor;
+pub mod rpc_scheduler;
+pub mod rules;
+pub mod script_hooks;
+pub mod sinks;
+pub mod token_extensions;
+pub mod token_identity;
+pub mod ws_recording;
@@ -1,15 +1,43 @@
-use anyhow::Result;
-use std::env;
-use tokio;
+use anyhow::{Context, Result};
+use clap::Parser;
+use std::io::{BufRead, BufReader};
+use std::path::PathBuf;
+use std::time::Instant;
+use tokio::sync::{mpsc, watch};
+use tokio::task::JoinSet;
+use tokio::time::Duration;
 use tracing::info;
-use tracing_subscriber;
 
-mod types;
-mod pump_monitor;
-mod raydium_launchpad_monitor;
+use blazing_monitor::api_server::ApiServer;
+use blazing_monitor::backtest::{self, BacktestRange, BacktestSummary, HistoryFetcher};
+use blazing_monitor::block_clock::BlockClock;
+use blazing_monitor::bonding_curve_tracker::BondingCurveTracker;
+use blazing_monitor::bundle_detector::BundleDetector;
+use blazing_monitor::completion_predictor::CompletionPredictor;
+use blazing_monitor::commitment_tracker::CommitmentTracker;
+use blazing_monitor::config::{self, BacktestArgs, Cli, Command, Config, ExportArgs};
+use blazing_monitor::creator_profiler::CreatorProfiler;
+use blazing_monitor::dev_watcher::DevWatcher;
+use blazing_monitor::grpc_server::GrpcServer;
+use blazing_monitor::holder_snapshots::HolderSnapshotter;
+use blazing_monitor::metadata_fetcher::MetadataFetcher;
+use blazing_monitor::parquet_export::{ParquetExporter, PartitionedWriter};
+use blazing_monitor::event_store::{EventFeed, EventHub};
+use blazing_monitor::pump_monitor::PumpFunMonitor;
+use blazing_monitor::raydium_launchpad_monitor::RaydiumBuyMonitor;
+use blazing_monitor::script_hooks::ScriptHost;
+use blazing_monitor::sinks;
+use blazing_monitor::token_identity::TokenIdentityEnricher;
+use blazing_monitor::types::MonitorEvent;
+use blazing_monitor::ws_recording::{self, Frame, FrameRecorder, ReplayStats, ReplayTargets};
 
-use pump_monitor::PumpFunMonitor;
-use raydium_launchpad_monitor::RaydiumBuyMonitor;
+// Replayed frames queued per monitor
+const REPLAY_CHANNEL_CAPACITY: usize = 256;
+// After the last frame, let enrichers and sinks finish what's queued
+const REPLAY_DRAIN: Duration = Duration::from_secs(1);
+// A backtest ends once no event has been published for this long
+const BACKTEST_QUIET: Duration = Duration::from_secs(2);
+const SINKS_TASK: &str = "🖨️ Output sinks";
 
 #[tokio::main]
 async fn main() -> Result<()> {
@@ -19,12 +47,21 @@ async fn main() -> Result<()> {
     // Initialize logging
     tracing_subscriber::fmt::init();
     
-    // Get WebSocket URL from environment variable
-    let ws_url = env::var("SOLANA_WS_URL")
-        .unwrap_or_else(|_| "wss://api.mainnet-beta.solana.com".to_string());
+    // Config file -> environment -> CLI flags
+    let cli = Cli::parse();
+    let config = Config::load(&cli)?;
+    match cli.command.clone() {
+        Some(Command::Backtest(args)) => return run_backtest(config, args).await,
+        Some(Command::Export(args)) => return run_export(config, args).await,
+        None => {},
+    }
+    let ws_url = config.endpoints.ws_url.clone();
+    let replay = cli.replay.clone().map(|path| (path, cli.replay_speed));
+    let recorder = cli.record.as_deref().map(FrameRecorder::start).transpose()?;
     
     info!("🚀 Starting Blazing Monitor - Enhanced Edition (Optimized)");
     info!("🔗 WebSocket URL: {}", mask_url(&ws_url));
+    info!("📜 Commitment: {}", config.commitment.as_str());
     
     // Show which API provider we're using
     if ws_url.contains("helius-rpc.com") {
@@ -35,37 +72,369 @@ async fn main() -> Result<()> {
         info!("🔗 Using custom WebSocket endpoint");
     }
     
-    info!("🔥 Starting dual monitoring (OPTIMIZED VERSION):");
-    info!("🎯 Pump.fun: Listening for CreateEvent");
-    info!("🛒 Raydium LaunchPad: Listening for BUY transactions ONLY (Lower resource usage)");
+    info!("🔥 Starting monitoring (OPTIMIZED VERSION):");
+    if config.platforms.pump_fun {
+        info!("🎯 Pump.fun: Listening for Create/Trade/Complete/Migration events");
+    }
+    if config.platforms.raydium {
+        info!("🛒 Raydium LaunchPad: Listening for BUY transactions ONLY (Lower resource usage)");
+    }
     
-    // Create both monitors
-    let mut pump_monitor = PumpFunMonitor::new(&ws_url).await?;
-    let mut raydium_monitor = RaydiumBuyMonitor::new(&ws_url).await?;
+    // Shared event hub - monitors publish, API server consumes
+    let events = EventHub::new();
     
-    // Run both monitors concurrently
-    let pump_handle = tokio::spawn(async move {
-        if let Err(e) = pump_monitor.start_monitoring().await {
-            tracing::error!("❌ Pump.fun monitor error: {}", e);
-        }
-    });
+    // Slot -> on-chain time, shared by both monitors for latency measurement
+    let block_clock = BlockClock::new(&config)?;
+    let replaying = replay.is_some();
+    if !replaying {
+        tokio::spawn(block_clock.clone().run());
+    }
     
-    let raydium_handle = tokio::spawn(async move {
-        if let Err(e) = raydium_monitor.start_monitoring().await {
-            tracing::error!("❌ Raydium LaunchPad monitor error: {}", e);
-        }
+    // Lifecycle, curves, predictions and the enrichers all feed on the hub
+    spawn_enrichers(&config, &events, &block_clock, replaying)?;
+    
+    // Sinks, filters and rules can be reloaded without restarting
+    let (runtime_tx, runtime_rx) = watch::channel(config.runtime()?);
+    tokio::spawn(config::watch_for_changes(cli, config.clone(), runtime_tx));
+    
+    // Every task returns its name so we can report which one stopped
+    let mut tasks: JoinSet<&'static str> = JoinSet::new();
+    
+    // Script hooks run in the sinks task, never on the WebSocket path
+    let scripts = load_scripts(&config, &events)?;
+
+    // The console's CA lines come from here, so the sinks never skip an event - drained before exit
+    let sink_events = events.clone();
+    let sink_feed = events.subscribe_lossless();
+    let sinks = tasks.spawn(async move {
+        sinks::run_sinks(sink_events, sink_feed, runtime_rx, scripts).await;
+        SINKS_TASK
     });
     
-    // Wait for both tasks (they run indefinitely unless there's an error)
+    // Analytics output; buffered rows are written out on the way down. Lossless when a replay can outrun it
+    let parquet_feed = if replaying { events.subscribe_lossless() } else { EventFeed::Live(events.subscribe()) };
+    let parquet = config.parquet.enabled.then(|| ParquetExporter::new(&config, parquet_feed).spawn());
+    
+    if config.api.enabled {
+        let api_server = ApiServer::new(&config.api.bind, events.clone());
+        tasks.spawn(async move {
+            if let Err(e) = api_server.run().await {
+                tracing::error!("❌ API server error: {}", e);
+            }
+            "🌐 API server"
+        });
+    }
+    
+    if config.grpc.enabled {
+        let grpc_server = GrpcServer::new(&config.grpc.bind, events.clone());
+        tasks.spawn(async move {
+            if let Err(e) = grpc_server.run().await {
+                tracing::error!("❌ gRPC server error: {}", e);
+            }
+            "📡 gRPC server"
+        });
+    }
+    
+    if let Some((path, speed)) = replay {
+        // Same decoding path as live, fed from the recording
+        info!("⏪ Replaying {} at {}x", path.display(), speed);
+        let replay_config = config.clone();
+        let replay_events = events.clone();
+        let replay_clock = block_clock.clone();
+        tasks.spawn(async move {
+            match replay_recording(&replay_config, &replay_events, &replay_clock, path, speed, |_| true).await {
+                Ok(stats) => info!(
+                    "⏪ Replayed {} Pump.fun / {} Raydium frames ({} skipped) covering {:.1}s",
+                    stats.pump_fun_frames,
+                    stats.raydium_frames,
+                    stats.skipped_frames,
+                    stats.recorded_span_ms as f64 / 1000.0
+                ),
+                Err(e) => tracing::error!("❌ Replay failed: {:#}", e),
+            }
+            // Enrichers work through their queues before the feeds close
+            backtest::wait_until_quiet(&replay_events, REPLAY_DRAIN).await;
+
+            let stats = replay_events.read(|store| store.stats());
+            info!(
+                "⏪ Replay produced {} events | Pump.fun: {} launches, {} trades | Raydium: {} launches, {} trades",
+                stats.total_events,
+                stats.pump_fun.launches,
+                stats.pump_fun.trades,
+                stats.raydium.launches,
+                stats.raydium.trades
+            );
+            "⏪ Replay"
+        });
+    }
+
+    if config.platforms.pump_fun && !replaying {
+        let mut pump_monitor = PumpFunMonitor::new(&config, events.clone(), block_clock.clone()).await?
+            .with_recorder(recorder.clone());
+        tasks.spawn(async move {
+            if let Err(e) = pump_monitor.start_monitoring().await {
+                tracing::error!("❌ Pump.fun monitor error: {}", e);
+            }
+            "🔥 Pump.fun monitor"
+        });
+    }
+    
+    if config.platforms.raydium && !replaying {
+        let mut raydium_monitor = RaydiumBuyMonitor::new(&config, events.clone(), block_clock.clone()).await?
+            .with_recorder(recorder);
+        tasks.spawn(async move {
+            if let Err(e) = raydium_monitor.start_monitoring().await {
+                tracing::error!("❌ Raydium LaunchPad monitor error: {}", e);
+            }
+            "🌟 Raydium LaunchPad monitor"
+        });
+    }
+    
+    // Wait for the first task to stop (they run indefinitely unless there's an error) or Ctrl-C
     tokio::select! {
-        _ = pump_handle => {
-            info!("🔥 Pump.fun monitor task completed");
-        }
-        _ = raydium_handle => {
-            info!("🌟 Raydium LaunchPad monitor task completed");
+        finished = tasks.join_next() => {
+            if let Some(Ok(name)) = finished {
+                info!("{} task completed", name);
+            }
+        },
+        _ = tokio::signal::ctrl_c() => info!("🛑 Interrupted - shutting down"),
+    }
+
+    // Sinks print and write whatever is still queued before we go
+    events.close_lossless();
+    while !sinks.is_finished() {
+        match tasks.join_next().await {
+            Some(Ok(SINKS_TASK)) | None => break,
+            _ => {},
         }
     }
     
+    if let Some(parquet) = parquet {
+        parquet.finish().await;
+    }
+    
+    Ok(())
+}
+
+/// Trackers and enrichers that follow the monitors' events. Shared by live runs, replays and backtests.
+/// Offline, each gets a lossless feed, so how far one falls behind a fast replay can't change its output.
+fn spawn_enrichers(config: &Config, events: &EventHub, block_clock: &BlockClock, offline: bool) -> Result<()> {
+    let feed = || if offline { events.subscribe_lossless() } else { EventFeed::Live(events.subscribe()) };
+
+    // Processed-first events get status updates / retractions as they settle
+    if config.lifecycle.enabled {
+        let tracker = CommitmentTracker::new(config, events.clone(), feed())?;
+        tokio::spawn(tracker.run());
+    }
+    
+    // Live reserves for every new pump.fun curve / LaunchLab pool
+    if config.curves.enabled && (config.platforms.pump_fun || config.platforms.raydium) {
+        let curve_tracker = BondingCurveTracker::new(config, events.clone(), feed(), block_clock.clone())?;
+        tokio::spawn(curve_tracker.run());
+    }
+    
+    // Velocity / ETA per curve - needs the curve tracker's progress
+    if config.predictions.enabled && config.curves.enabled {
+        let predictor = CompletionPredictor::new(config, events.clone(), feed());
+        tokio::spawn(predictor.run());
+    }
+    
+    // Mint account + Metaplex metadata, so LaunchLab tokens get a name/symbol/creator too
+    if config.identity.enabled {
+        let enricher = TokenIdentityEnricher::new(config, events.clone(), feed())?;
+        tokio::spawn(enricher.run());
+    }
+    
+    // Creator history summarised on every launch (after identity, which finds LaunchLab creators)
+    if config.creators.enabled {
+        let profiler = CreatorProfiler::new(config, events.clone(), feed());
+        tokio::spawn(profiler.run());
+    }
+    
+    // Creator's own buys/sells in the first minutes after launch
+    if config.dev_watch.enabled {
+        let watcher = DevWatcher::new(config, events.clone(), feed());
+        tokio::spawn(watcher.run());
+    }
+    
+    // Same-slot snipers and shared funding at launch
+    if config.bundles.enabled {
+        let detector = BundleDetector::new(config, events.clone(), feed())?;
+        tokio::spawn(detector.run());
+    }
+    
+    // Supply concentration at 1/5/15 minutes (by default) after launch
+    if config.holders.enabled {
+        let snapshotter = HolderSnapshotter::new(config, events.clone(), feed())?;
+        tokio::spawn(snapshotter.run());
+    }
+    
+    // Image, description and socials arrive as a separate event after the launch
+    if config.metadata.enabled {
+        let fetcher = MetadataFetcher::new(config, events.clone(), feed())?;
+        tokio::spawn(fetcher.run());
+    }
+    
+    Ok(())
+}
+
+fn load_scripts(config: &Config, events: &EventHub) -> Result<Option<ScriptHost>> {
+    if !config.scripts.enabled {
+        return Ok(None);
+    }
+    let host = ScriptHost::new(config, events.clone())?;
+    info!("📜 {} script hook(s) loaded", host.script_count());
+    Ok(Some(host))
+}
+
+/// Feed a recording's frames that `keep` accepts through fresh monitors, until the monitors have processed all of them.
+async fn replay_recording(
+    config: &Config,
+    events: &EventHub,
+    block_clock: &BlockClock,
+    path: PathBuf,
+    speed: f64,
+    keep: impl FnMut(&Frame) -> bool + Send + 'static,
+) -> Result<ReplayStats> {
+    let mut targets = ReplayTargets::default();
+    let mut monitors = JoinSet::new();
+    if config.platforms.pump_fun {
+        let mut pump_monitor = PumpFunMonitor::new(config, events.clone(), block_clock.clone()).await?;
+        let (tx, rx) = mpsc::channel(REPLAY_CHANNEL_CAPACITY);
+        targets.pump_fun = Some(tx);
+        monitors.spawn(async move { pump_monitor.replay(rx).await });
+    }
+    if config.platforms.raydium {
+        let transactions = ws_recording::read_transactions(&path)?;
+        let mut raydium_monitor = RaydiumBuyMonitor::new(config, events.clone(), block_clock.clone()).await?;
+        let (tx, rx) = mpsc::channel(REPLAY_CHANNEL_CAPACITY);
+        targets.raydium = Some(tx);
+        monitors.spawn(async move { raydium_monitor.replay(rx, transactions).await });
+    }
+
+    let stats = ws_recording::replay_filtered(path, speed, targets, keep).await;
+    while monitors.join_next().await.is_some() {}
+    stats
+}
+
+/// `backtest`: history from the RPC (or an archive) through the monitors, enrichers,
+/// filters, rules and scripts, into the output file. Nothing is printed, served or alerted.
+async fn run_backtest(config: Config, args: BacktestArgs) -> Result<()> {
+    let started = Instant::now();
+    let range = BacktestRange::from_args(&args)?;
+    info!("🕰️ Backtest over {:?}", range);
+
+    let (archive, history) = match &args.archive {
+        Some(path) => {
+            info!("🕰️ Reading history from {}", path.display());
+            (path.clone(), None)
+        },
+        None => {
+            info!("🕰️ Fetching history from {}", mask_url(&config.rpc_url()));
+            let path = args.save_archive.clone().unwrap_or_else(|| {
+                std::env::temp_dir().join(format!("blazing-backtest-{}.jsonl.gz", std::process::id()))
+            });
+            let stats = HistoryFetcher::new(&config)?.fetch(range, &path).await?;
+            (path, Some(stats))
+        },
+    };
+
+    // The clock doesn't poll here - anchor it on the history's own block times
+    let events = EventHub::new();
+    let block_clock = BlockClock::new(&config)?;
+    for (slot, time_ms) in backtest::clock_anchors(&archive, range)? {
+        block_clock.anchor(slot, time_ms);
+    }
+    spawn_enrichers(&config, &events, &block_clock, true)?;
+
+    // The JSON lines sink appends - start each run from an empty file
+    std::fs::File::create(&args.output).with_context(|| format!("creating {}", args.output.display()))?;
+    let scripts = load_scripts(&config, &events)?;
+    let (_runtime_tx, runtime_rx) = watch::channel(config.runtime()?);
+    // History replays as fast as the monitors decode it - nothing may be dropped on the way out
+    let sinks = tokio::spawn(sinks::run_sinks(events.clone(), events.subscribe_lossless(), runtime_rx, scripts));
+    let parquet = config.parquet.enabled.then(|| ParquetExporter::new(&config, events.subscribe_lossless()).spawn());
+
+    let stats = replay_recording(&config, &events, &block_clock, archive.clone(), 0.0, move |frame| range.contains_frame(frame)).await?;
+    // Bundles are scored `window_secs` after their launch, the rest follows within moments
+    let quiet = if config.bundles.enabled { BACKTEST_QUIET + Duration::from_secs(config.bundles.window_secs) } else { BACKTEST_QUIET };
+    backtest::wait_until_quiet(&events, quiet).await;
+    events.close_lossless();
+    sinks.await.context("output sinks panicked")?;
+    if let Some(parquet) = parquet {
+        parquet.finish().await;
+    }
+
+    let (events_written, written_by_type) = backtest::count_output(&args.output)?;
+    let store = events.read(|store| store.stats());
+    let summary = BacktestSummary {
+        range,
+        archive: (args.archive.is_some() || args.save_archive.is_some()).then(|| archive.display().to_string()),
+        history,
+        pump_fun_frames: stats.pump_fun_frames,
+        raydium_frames: stats.raydium_frames,
+        events_produced: store.total_events,
+        pump_fun: store.pump_fun,
+        raydium: store.raydium,
+        tokens: store.tracked_tokens,
+        events_written,
+        written_by_type,
+        elapsed_secs: started.elapsed().as_secs_f64(),
+    };
+    if args.archive.is_none() && args.save_archive.is_none() {
+        std::fs::remove_file(&archive).ok();
+    }
+
+    let summary_path = args.summary.clone().unwrap_or_else(|| args.output.with_extension("summary.json"));
+    std::fs::write(&summary_path, serde_json::to_string_pretty(&summary)?)
+        .with_context(|| format!("writing {}", summary_path.display()))?;
+    info!(
+        "🕰️ Backtest done in {:.1}s: {} events produced, {} written to {} | Pump.fun: {} launches | Raydium: {} launches | summary: {}",
+        summary.elapsed_secs,
+        summary.events_produced,
+        summary.events_written,
+        args.output.display(),
+        summary.pump_fun.launches,
+        summary.raydium.launches,
+        summary_path.display()
+    );
+    Ok(())
+}
+
+/// `export`: a recording decoded through the monitors, or an events JSON lines file, as Parquet.
+async fn run_export(config: Config, args: ExportArgs) -> Result<()> {
+    if let Some(path) = &args.events {
+        let file = std::fs::File::open(path).with_context(|| format!("opening {}", path.display()))?;
+        let mut writer = PartitionedWriter::new(&config.parquet);
+        let mut skipped = 0;
+        for line in BufReader::new(file).lines() {
+            // Scripted events carry extra fields - they're ignored here
+            match serde_json::from_str::<MonitorEvent>(&line?) {
+                Ok(event) => writer.push(&event)?,
+                Err(_) => skipped += 1,
+            }
+        }
+        writer.flush_all()?;
+        info!(
+            "🧱 Exported {} rows in {} files to {} ({} lines skipped)",
+            writer.rows_written(), writer.files_written(), config.parquet.dir.display(), skipped
+        );
+        return Ok(());
+    }
+
+    let Some(path) = args.recording else { return Ok(()) };
+    info!("🧱 Decoding {} into {}", path.display(), config.parquet.dir.display());
+    let events = EventHub::new();
+    // Offline - block times come from the recording itself
+    let block_clock = BlockClock::new(&config)?;
+    for (slot, time_ms) in backtest::clock_anchors(&path, BacktestRange::All)? {
+        block_clock.anchor(slot, time_ms);
+    }
+    let parquet = ParquetExporter::new(&config, events.subscribe_lossless()).spawn();
+    let stats = replay_recording(&config, &events, &block_clock, path, 0.0, |_| true).await?;
+    events.close_lossless();
+    parquet.finish().await;
+    info!("🧱 Decoded {} Pump.fun / {} Raydium frames", stats.pump_fun_frames, stats.raydium_frames);
     Ok(())
 }
 
@@ -0,0 +1,357 @@
+use anyhow::{anyhow, Result};
+use reqwest::Client;
+use serde_json::Value;
+use sha2::{Digest, Sha256};
+use std::collections::{HashSet, VecDeque};
+use std::path::PathBuf;
+use std::sync::Arc;
+use tokio::sync::broadcast::error::RecvError;
+use tokio::sync::Semaphore;
+use tokio::time::Duration;
+use tracing::{info, warn, debug};
+
+use crate::block_clock::now_ms;
+use crate::config::{Config, MetadataConfig};
+use crate::event_store::{EventFeed, EventHub};
+use crate::types::{EventTiming, MonitorEvent, Platform, TokenMetadata, TxStatus};
+
+// ========================================================================
+// RESOLVER
+// ========================================================================
+
+/// Fetches metadata JSON for a URI: gateway fallback for IPFS/Arweave,
+/// per-attempt timeout, body size limit and an on-disk cache. Cheap to clone.
+#[derive(Clone)]
+pub struct MetadataResolver {
+    http_client: Client,
+    settings: Arc<MetadataConfig>,
+}
+
+impl MetadataResolver {
+    pub fn new(settings: &MetadataConfig) -> Result<Self> {
+        let http_client = Client::builder()
+            .timeout(Duration::from_secs(settings.timeout_secs))
+            .build()?;
+
+        if !settings.cache_dir.as_os_str().is_empty() {
+            std::fs::create_dir_all(&settings.cache_dir)?;
+        }
+
+        Ok(Self {
+            http_client,
+            settings: Arc::new(settings.clone()),
+        })
+    }
+
+    /// Metadata JSON behind `uri`, from the cache when we've seen it before.
+    pub async fn resolve(&self, uri: &str) -> Result<Value> {
+        let cache_path = self.cache_path(uri);
+        if let Some(path) = &cache_path {
+            if let Ok(cached) = tokio::fs::read(path).await {
+                if let Ok(json) = serde_json::from_slice(&cached) {
+                    return Ok(json);
+                }
+            }
+        }
+
+        let urls = self.candidate_urls(uri);
+        if urls.is_empty() {
+            return Err(anyhow!("unsupported metadata URI: {}", uri));
+        }
+
+        let mut last_error = None;
+        for url in urls {
+            // Gateways sometimes answer 200 with an HTML error page - treat it as a miss
+            let fetched = self.fetch_limited(&url).await
+                .and_then(|body| Ok((serde_json::from_slice::<Value>(&body)?, body)));
+            match fetched {
+                Ok((json, body)) => {
+                    if let Some(path) = &cache_path {
+                        if let Err(e) = tokio::fs::write(path, &body).await {
+                            warn!("⚠️ Couldn't cache metadata at {}: {}", path.display(), e);
+                        }
+                    }
+                    return Ok(json);
+                },
+                Err(e) => {
+                    debug!("🖼️ {} failed: {}", url, e);
+                    last_error = Some(e);
+                },
+            }
+        }
+        Err(last_error.unwrap_or_else(|| anyhow!("no gateway answered")))
+    }
+
+    /// URLs to try for a URI, in order. IPFS and Arweave content can come
+    /// from any gateway; plain HTTPS only from its own host.
+    pub fn candidate_urls(&self, uri: &str) -> Vec<String> {
+        let mut urls = Vec::new();
+        if uri.starts_with("https://") || uri.starts_with("http://") {
+            urls.push(uri.to_string());
+        }
+
+        let (gateways, path) = if let Some(path) = ipfs_path(uri) {
+            (&self.settings.ipfs_gateways, path)
+        } else if let Some(path) = arweave_path(uri) {
+            (&self.settings.arweave_gateways, path)
+        } else {
+            return urls;
+        };
+        for url in gateways.iter().map(|gateway| format!("{}{}", gateway, path)) {
+            if !urls.contains(&url) {
+                urls.push(url);
+            }
+        }
+        urls
+    }
+
+    /// HTTP URL for an `ipfs://` / `ar://` link (images), first gateway wins.
+    pub fn gateway_url(&self, link: &str) -> String {
+        if link.starts_with("ipfs://") || link.starts_with("ar://") {
+            if let Some(url) = self.candidate_urls(link).into_iter().next() {
+                return url;
+            }
+        }
+        link.to_string()
+    }
+
+    async fn fetch_limited(&self, url: &str) -> Result<Vec<u8>> {
+        let max_bytes = self.settings.max_bytes;
+        let mut response = self.http_client.get(url).send().await?.error_for_status()?;
+        if response.content_length().is_some_and(|length| length as usize > max_bytes) {
+            return Err(anyhow!("metadata larger than {} bytes", max_bytes));
+        }
+
+        // Content-Length can be missing or wrong - enforce the limit while reading
+        let mut body = Vec::new();
+        while let Some(chunk) = response.chunk().await? {
+            body.extend_from_slice(&chunk);
+            if body.len() > max_bytes {
+                return Err(anyhow!("metadata larger than {} bytes", max_bytes));
+            }
+        }
+        Ok(body)
+    }
+
+    fn cache_path(&self, uri: &str) -> Option<PathBuf> {
+        if self.settings.cache_dir.as_os_str().is_empty() {
+            return None;
+        }
+        Some(self.settings.cache_dir.join(format!("{:x}.json", Sha256::digest(uri.as_bytes()))))
+    }
+}
+
+// CID (+ path) from ipfs://CID, ipfs://ipfs/CID or any https://host/ipfs/CID gateway URL
+fn ipfs_path(uri: &str) -> Option<&str> {
+    if let Some(rest) = uri.strip_prefix("ipfs://") {
+        return Some(rest.strip_prefix("ipfs/").unwrap_or(rest));
+    }
+    uri.split_once("/ipfs/").map(|(_, path)| path).filter(|path| !path.is_empty())
+}
+
+// Transaction id from ar://ID or https://arweave.net/ID
+fn arweave_path(uri: &str) -> Option<&str> {
+    uri.strip_prefix("ar://")
+        .or_else(|| uri.strip_prefix("https://arweave.net/"))
+        .or_else(|| uri.strip_prefix("https://www.arweave.net/"))
+        .filter(|path| !path.is_empty())
+}
+
+/// Typed view of a metadata JSON. Socials are read from the top level
+/// (pump.fun) or from Metaplex-style `extensions`.
+pub fn parse_metadata(json: &Value, resolver: &MetadataResolver) -> TokenMetadataFields {
+    let text = |value: Option<&Value>| {
+        value.and_then(|v| v.as_str()).map(str::trim).filter(|s| !s.is_empty()).map(str::to_string)
+    };
+    let social = |key: &str| text(json.get(key)).or_else(|| text(json.get("extensions").and_then(|e| e.get(key))));
+
+    TokenMetadataFields {
+        name: text(json.get("name")),
+        symbol: text(json.get("symbol")),
+        description: text(json.get("description")),
+        image: text(json.get("image")).map(|image| resolver.gateway_url(&image)),
+        twitter: social("twitter"),
+        telegram: social("telegram"),
+        website: social("website"),
+    }
+}
+
+/// The JSON-derived part of `TokenMetadata`.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct TokenMetadataFields {
+    pub name: Option<String>,
+    pub symbol: Option<String>,
+    pub description: Option<String>,
+    pub image: Option<String>,
+    pub twitter: Option<String>,
+    pub telegram: Option<String>,
+    pub website: Option<String>,
+}
+
+// ========================================================================
+// FETCHER
+// ========================================================================
+
+// (mint, URI) pairs remembered for dedup - a URI can arrive on the launch and again on
+// its identity, and a different Metaplex URI still gets its own try
+const MAX_REQUESTED_URIS: usize = 10_000;
+
+/// Resolves every launch URI (or the Metaplex URI from the token's identity)
+/// in the background and publishes a `Metadata` event when it lands - the
+/// launch itself is never held back.
+pub struct MetadataFetcher {
+    resolver: MetadataResolver,
+    events: EventHub,
+    receiver: EventFeed,
+    limiter: Arc<Semaphore>,
+    requested: HashSet<(String, String)>,
+    requested_order: VecDeque<(String, String)>,
+}
+
+impl MetadataFetcher {
+    /// Subscribes immediately so no launch is missed before `run`.
+    pub fn new(config: &Config, events: EventHub, feed: EventFeed) -> Result<Self> {
+        Ok(Self {
+            resolver: MetadataResolver::new(&config.metadata)?,
+            limiter: Arc::new(Semaphore::new(config.metadata.max_concurrent.max(1))),
+            receiver: feed,
+            events,
+            requested: HashSet::new(),
+            requested_order: VecDeque::new(),
+        })
+    }
+
+    pub async fn run(mut self) {
+        info!("🖼️ Metadata fetcher started (max {} concurrent)", self.limiter.available_permits());
+
+        loop {
+            let (mint, platform, uri) = match self.receiver.recv().await {
+                Ok(MonitorEvent::Launch(launch)) => (launch.contract_address, launch.platform, launch.uri),
+                Ok(MonitorEvent::Identity(identity)) => (identity.mint, identity.platform, identity.uri),
+                Ok(_) => continue,
+                Err(RecvError::Lagged(skipped)) => {
+                    warn!("⚠️ Metadata fetcher lagging, skipped {} events", skipped);
+                    continue;
+                },
+                Err(RecvError::Closed) => break,
+            };
+            let Some(uri) = uri else { continue };
+            if !self.first_request(&mint, &uri) {
+                continue;
+            }
+
+            let resolver = self.resolver.clone();
+            let events = self.events.clone();
+            let limiter = self.limiter.clone();
+            tokio::spawn(async move {
+                let Ok(_permit) = limiter.acquire_owned().await else { return };
+                fetch_and_publish(&resolver, &events, mint, platform, uri).await;
+            });
+        }
+    }
+
+    fn first_request(&mut self, mint: &str, uri: &str) -> bool {
+        let key = (mint.to_string(), uri.to_string());
+        if !self.requested.insert(key.clone()) {
+            return false;
+        }
+        self.requested_order.push_back(key);
+        if self.requested_order.len() > MAX_REQUESTED_URIS {
+            if let Some(oldest) = self.requested_order.pop_front() {
+                self.requested.remove(&oldest);
+            }
+        }
+        true
+    }
+}
+
+async fn fetch_and_publish(resolver: &MetadataResolver, events: &EventHub, mint: String, platform: Platform, uri: String) {
+    let json = match resolver.resolve(&uri).await {
+        Ok(json) => json,
+        Err(e) => {
+            debug!("🖼️ No metadata for {} ({}): {}", mint, uri, e);
+            return;
+        },
+    };
+
+    let fields = parse_metadata(&json, resolver);
+    let timing = EventTiming {
+        slot: None,
+        block_time_ms: None,
+        detected_at_ms: now_ms(),
+        status: TxStatus::default(),
+    };
+    debug!(
+        "🖼️ [{}] Metadata | CA: {} | X: {} | TG: {} | Web: {}",
+        platform,
+        mint,
+        fields.twitter.as_deref().unwrap_or("-"),
+        fields.telegram.as_deref().unwrap_or("-"),
+        fields.website.as_deref().unwrap_or("-")
+    );
+
+    events.publish(MonitorEvent::Metadata(TokenMetadata {
+        mint,
+        uri,
+        name: fields.name,
+        symbol: fields.symbol,
+        description: fields.description,
+        image: fields.image,
+        twitter: fields.twitter,
+        telegram: fields.telegram,
+        website: fields.website,
+        platform,
+        timestamp: timing.detected_at_ms / 1000,
+        timing,
+    }));
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn resolver() -> MetadataResolver {
+        let settings = MetadataConfig { cache_dir: PathBuf::new(), ..MetadataConfig::default() };
+        MetadataResolver::new(&settings).unwrap()
+    }
+
+    #[test]
+    fn a_different_uri_for_the_same_mint_is_tried() {
+        let events = EventHub::new();
+        let mut fetcher = MetadataFetcher::new(&Config::default(), events.clone(), EventFeed::Live(events.subscribe())).unwrap();
+        assert!(fetcher.first_request("mint", "https://launch/uri"));
+        assert!(!fetcher.first_request("mint", "https://launch/uri"));
+        assert!(fetcher.first_request("mint", "https://metaplex/uri"));
+        assert!(fetcher.first_request("other", "https://launch/uri"));
+    }
+
+    #[test]
+    fn ipfs_uris_fall_back_across_gateways() {
+        let urls = resolver().candidate_urls("https://ipfs.io/ipfs/QmHash");
+        assert_eq!(urls, vec![
+            "https://ipfs.io/ipfs/QmHash",
+            "https://dweb.link/ipfs/QmHash",
+            "https://gateway.pinata.cloud/ipfs/QmHash",
+        ]);
+        assert_eq!(resolver().candidate_urls("ipfs://ipfs/QmHash")[0], "https://ipfs.io/ipfs/QmHash");
+        assert_eq!(resolver().candidate_urls("ar://TxId"), vec!["https://arweave.net/TxId", "https://ar-io.net/TxId"]);
+        assert_eq!(resolver().candidate_urls("https://example.com/meta.json"), vec!["https://example.com/meta.json"]);
+        assert!(resolver().candidate_urls("data:application/json,{}").is_empty());
+    }
+
+    #[test]
+    fn reads_socials_from_top_level_or_extensions() {
+        let resolver = resolver();
+        let pump = parse_metadata(&json!({
+            "name": "Cat", "symbol": "CAT", "image": "ipfs://QmImage",
+            "twitter": "https://x.com/cat", "website": " "
+        }), &resolver);
+        assert_eq!(pump.image.as_deref(), Some("https://ipfs.io/ipfs/QmImage"));
+        assert_eq!(pump.twitter.as_deref(), Some("https://x.com/cat"));
+        assert_eq!(pump.website, None);
+
+        let metaplex = parse_metadata(&json!({ "extensions": { "telegram": "https://t.me/cat" } }), &resolver);
+        assert_eq!(metaplex.telegram.as_deref(), Some("https://t.me/cat"));
+    }
+}
@@ -0,0 +1,211 @@
+use prometheus::{
+    Encoder, Gauge, GaugeVec, IntGauge, HistogramOpts, HistogramVec, IntCounterVec, IntGaugeVec, Opts, Registry, TextEncoder,
+};
+use std::sync::LazyLock;
+
+use crate::rpc_scheduler::Priority;
+use crate::types::{MonitorEvent, Platform};
+
+static METRICS: LazyLock<Metrics> = LazyLock::new(Metrics::new);
+
+/// Process-wide metrics registry, scraped from `/metrics` on the API server.
+pub fn metrics() -> &'static Metrics {
+    &METRICS
+}
+
+pub struct Metrics {
+    registry: Registry,
+    events_decoded: IntCounterVec,
+    decode_errors: IntCounterVec,
+    ws_reconnects: IntCounterVec,
+    ws_connected: IntGaugeVec,
+    ws_uptime: GaugeVec,
+    emit_latency: HistogramVec,
+    detection_latency: HistogramVec,
+    raydium_fetches: IntCounterVec,
+    cache_entries: IntGaugeVec,
+    rpc_requests: IntCounterVec,
+    rpc_queued: IntGaugeVec,
+    rpc_in_flight: IntGauge,
+    rpc_rate: Gauge,
+}
+
+impl Metrics {
+    fn new() -> Self {
+        let registry = Registry::new_custom(Some("blazing".to_string()), None)
+            .expect("valid registry prefix");
+
+        let events_decoded = IntCounterVec::new(
+            Opts::new("events_decoded_total", "Events decoded and emitted, by platform and event type"),
+            &["platform", "event_type"],
+        ).expect("valid metric");
+        let decode_errors = IntCounterVec::new(
+            Opts::new("decode_errors_total", "WebSocket notifications that failed to decode, by reason"),
+            &["platform", "reason"],
+        ).expect("valid metric");
+        let ws_reconnects = IntCounterVec::new(
+            Opts::new("ws_reconnects_total", "WebSocket connections that ended and were re-established"),
+            &["platform"],
+        ).expect("valid metric");
+        let ws_connected = IntGaugeVec::new(
+            Opts::new("ws_connected", "1 while the WebSocket subscription is live"),
+            &["platform"],
+        ).expect("valid metric");
+        let ws_uptime = GaugeVec::new(
+            Opts::new("ws_uptime_seconds", "Age of the current WebSocket connection"),
+            &["platform"],
+        ).expect("valid metric");
+        let emit_latency = HistogramVec::new(
+            HistogramOpts::new("notification_to_emit_seconds", "Time from WebSocket notification to event emit")
+                .buckets(vec![0.0005, 0.001, 0.005, 0.01, 0.05, 0.1, 0.25, 0.5, 1.0, 2.5, 5.0, 10.0]),
+            &["platform"],
+        ).expect("valid metric");
+        let detection_latency = HistogramVec::new(
+            HistogramOpts::new("detection_latency_seconds", "Local detection time minus on-chain block time")
+                .buckets(vec![0.25, 0.5, 0.75, 1.0, 1.5, 2.0, 3.0, 5.0, 10.0, 30.0, 60.0]),
+            &["platform", "event_type"],
+        ).expect("valid metric");
+        let raydium_fetches = IntCounterVec::new(
+            Opts::new("raydium_fetches_total", "Raydium getTransaction outcomes (throttled, rate_limited, timeout, success, ...)"),
+            &["outcome"],
+        ).expect("valid metric");
+        let cache_entries = IntGaugeVec::new(
+            Opts::new("cache_entries", "Entries in the in-memory dedup caches"),
+            &["platform", "cache"],
+        ).expect("valid metric");
+
+        let rpc_requests = IntCounterVec::new(
+            Opts::new("rpc_requests_total", "Scheduled RPC requests by priority and outcome (success, rate_limited, busy, coalesced, ...)"),
+            &["priority", "outcome"],
+        ).expect("valid metric");
+        let rpc_queued = IntGaugeVec::new(
+            Opts::new("rpc_queued", "RPC requests waiting for a token or an in-flight slot"),
+            &["priority"],
+        ).expect("valid metric");
+        let rpc_in_flight = IntGauge::new("rpc_in_flight", "RPC requests currently in flight").expect("valid metric");
+        let rpc_rate = Gauge::new("rpc_requests_per_second", "Current RPC token refill rate, lowered after 429s").expect("valid metric");
+
+        registry.register(Box::new(events_decoded.clone())).expect("unique metric");
+        registry.register(Box::new(decode_errors.clone())).expect("unique metric");
+        registry.register(Box::new(ws_reconnects.clone())).expect("unique metric");
+        registry.register(Box::new(ws_connected.clone())).expect("unique metric");
+        registry.register(Box::new(ws_uptime.clone())).expect("unique metric");
+        registry.register(Box::new(emit_latency.clone())).expect("unique metric");
+        registry.register(Box::new(detection_latency.clone())).expect("unique metric");
+        registry.register(Box::new(raydium_fetches.clone())).expect("unique metric");
+        registry.register(Box::new(cache_entries.clone())).expect("unique metric");
+        registry.register(Box::new(rpc_requests.clone())).expect("unique metric");
+        registry.register(Box::new(rpc_queued.clone())).expect("unique metric");
+        registry.register(Box::new(rpc_in_flight.clone())).expect("unique metric");
+        registry.register(Box::new(rpc_rate.clone())).expect("unique metric");
+
+        Self {
+            registry,
+            events_decoded,
+            decode_errors,
+            ws_reconnects,
+            ws_connected,
+            ws_uptime,
+            emit_latency,
+            detection_latency,
+            raydium_fetches,
+            cache_entries,
+            rpc_requests,
+            rpc_queued,
+            rpc_in_flight,
+            rpc_rate,
+        }
+    }
+
+    pub fn event_decoded(&self, event: &MonitorEvent) {
+        self.events_decoded
+            .with_label_values(&[event.platform().label(), event.kind()])
+            .inc();
+
+        // Slot clock estimates can land slightly ahead of us - clamp at zero
+        if let Some(latency_ms) = event.timing().latency_ms() {
+            self.detection_latency
+                .with_label_values(&[event.platform().label(), event.kind()])
+                .observe(latency_ms.max(0) as f64 / 1000.0);
+        }
+    }
+
+    pub fn decode_error(&self, platform: Platform, error: &anyhow::Error) {
+        self.decode_errors
+            .with_label_values(&[platform.label(), decode_error_reason(error)])
+            .inc();
+    }
+
+    /// For failures caught before an `anyhow::Error` exists (e.g. skipped base64 payloads).
+    pub fn decode_error_reason(&self, platform: Platform, reason: &str) {
+        self.decode_errors.with_label_values(&[platform.label(), reason]).inc();
+    }
+
+    pub fn ws_connected(&self, platform: Platform) {
+        self.ws_connected.with_label_values(&[platform.label()]).set(1);
+        self.ws_uptime.with_label_values(&[platform.label()]).set(0.0);
+    }
+
+    pub fn ws_disconnected(&self, platform: Platform) {
+        self.ws_connected.with_label_values(&[platform.label()]).set(0);
+        self.ws_uptime.with_label_values(&[platform.label()]).set(0.0);
+        self.ws_reconnects.with_label_values(&[platform.label()]).inc();
+    }
+
+    pub fn ws_uptime(&self, platform: Platform, uptime: std::time::Duration) {
+        self.ws_uptime.with_label_values(&[platform.label()]).set(uptime.as_secs_f64());
+    }
+
+    pub fn emit_latency(&self, platform: Platform, latency: std::time::Duration) {
+        self.emit_latency
+            .with_label_values(&[platform.label()])
+            .observe(latency.as_secs_f64());
+    }
+
+    pub fn raydium_fetch(&self, outcome: &str) {
+        self.raydium_fetches.with_label_values(&[outcome]).inc();
+    }
+
+    pub fn cache_size(&self, platform: Platform, cache: &str, entries: usize) {
+        self.cache_entries
+            .with_label_values(&[platform.label(), cache])
+            .set(entries as i64);
+    }
+
+    pub fn rpc_request(&self, priority: Priority, outcome: &str) {
+        self.rpc_requests.with_label_values(&[priority.label(), outcome]).inc();
+    }
+
+    pub fn rpc_queued(&self, priority: Priority, waiting: usize) {
+        self.rpc_queued.with_label_values(&[priority.label()]).set(waiting as i64);
+    }
+
+    pub fn rpc_in_flight(&self, requests: usize) {
+        self.rpc_in_flight.set(requests as i64);
+    }
+
+    pub fn rpc_rate(&self, requests_per_sec: f64) {
+        self.rpc_rate.set(requests_per_sec);
+    }
+
+    /// Prometheus text exposition format.
+    pub fn render(&self) -> String {
+        let mut buffer = Vec::new();
+        if let Err(e) = TextEncoder::new().encode(&self.registry.gather(), &mut buffer) {
+            tracing::warn!("⚠️ Failed to encode metrics: {}", e);
+        }
+        String::from_utf8(buffer).unwrap_or_default()
+    }
+}
+
+fn decode_error_reason(error: &anyhow::Error) -> &'static str {
+    if error.is::<serde_json::Error>() {
+        "invalid_json"
+    } else if error.is::<std::string::FromUtf8Error>() {
+        "invalid_utf8"
+    } else if error.is::<std::array::TryFromSliceError>() || error.to_string().starts_with("Invalid") {
+        "truncated_data"
+    } else {
+        "other"
+    }
+}
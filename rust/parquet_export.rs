@@ -0,0 +1,544 @@
+use anyhow::{Context, Result};
+use arrow::array::{ArrayRef, BooleanArray, StringArray, TimestampMillisecondArray, TimestampSecondArray, UInt64Array};
+use arrow::datatypes::{DataType, Field, Schema, SchemaRef, TimeUnit};
+use arrow::record_batch::RecordBatch;
+use parquet::arrow::ArrowWriter;
+use parquet::basic::Compression;
+use parquet::file::properties::WriterProperties;
+use std::collections::HashMap;
+use std::fs::File;
+use std::path::{Path, PathBuf};
+use std::sync::mpsc::{self as std_mpsc, RecvTimeoutError};
+use std::sync::{Arc, OnceLock};
+use std::time::{Duration, Instant};
+use tokio::sync::broadcast::error::RecvError;
+use tokio::sync::oneshot;
+use tokio::task::JoinHandle;
+use tracing::{info, warn, error};
+
+use crate::block_clock::now_ms;
+use crate::config::{Config, ParquetConfig};
+use crate::event_store::EventFeed;
+use crate::types::{EventTiming, MonitorEvent, Platform};
+
+const CHECK_INTERVAL_SECS: u64 = 1;
+
+// ========================================================================
+// SCHEMAS
+// ========================================================================
+
+/// One Parquet dataset per event type, each under `<dir>/<table>/`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Table {
+    Launches,
+    Trades,
+    Completions,
+    Migrations,
+}
+
+impl Table {
+    pub const ALL: [Table; 4] = [Table::Launches, Table::Trades, Table::Completions, Table::Migrations];
+
+    pub fn of(event: &MonitorEvent) -> Option<Table> {
+        match event {
+            MonitorEvent::Launch(_) => Some(Table::Launches),
+            MonitorEvent::Trade(_) => Some(Table::Trades),
+            MonitorEvent::Completion(_) => Some(Table::Completions),
+            MonitorEvent::Migration(_) => Some(Table::Migrations),
+            _ => None,
+        }
+    }
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            Table::Launches => "launches",
+            Table::Trades => "trades",
+            Table::Completions => "completions",
+            Table::Migrations => "migrations",
+        }
+    }
+
+    /// Column order and types are part of the contract with readers - append, never reorder.
+    pub fn schema(&self) -> SchemaRef {
+        static SCHEMAS: OnceLock<HashMap<Table, SchemaRef>> = OnceLock::new();
+        SCHEMAS.get_or_init(|| {
+            Table::ALL.into_iter().map(|table| (table, Arc::new(Schema::new(table.fields())))).collect()
+        })[self].clone()
+    }
+
+    fn fields(&self) -> Vec<Field> {
+        let text = |name: &str| Field::new(name, DataType::Utf8, true);
+        let amount = |name: &str| Field::new(name, DataType::UInt64, true);
+        let mut fields = vec![
+            Field::new("signature", DataType::Utf8, false),
+            Field::new("mint", DataType::Utf8, false),
+            Field::new("platform", DataType::Utf8, false),
+        ];
+        fields.extend(match self {
+            Table::Launches => vec![text("name"), text("symbol"), text("creator"), text("uri")],
+            Table::Trades => vec![text("trader"), Field::new("is_buy", DataType::Boolean, false), amount("sol_amount"), amount("token_amount")],
+            Table::Completions => vec![text("bonding_curve"), text("user")],
+            Table::Migrations => vec![text("pool"), amount("sol_amount"), amount("token_amount")],
+        });
+        fields.extend([
+            Field::new("timestamp", DataType::Timestamp(TimeUnit::Second, Some("UTC".into())), false),
+            Field::new("slot", DataType::UInt64, true),
+            Field::new("block_time", DataType::Timestamp(TimeUnit::Millisecond, Some("UTC".into())), true),
+            Field::new("detected_at", DataType::Timestamp(TimeUnit::Millisecond, Some("UTC".into())), false),
+            Field::new("status", DataType::Utf8, false),
+        ]);
+        fields
+    }
+
+    /// `rows` must all be this table's event type; others are skipped.
+    pub fn batch(&self, rows: &[MonitorEvent]) -> Result<RecordBatch> {
+        let text = |values: Vec<Option<&str>>| -> ArrayRef { Arc::new(StringArray::from(values)) };
+        let amount = |values: Vec<Option<u64>>| -> ArrayRef { Arc::new(UInt64Array::from(values)) };
+
+        let mut common = Vec::new();
+        let specific: Vec<ArrayRef> = match self {
+            Table::Launches => {
+                let launches: Vec<_> = rows.iter().filter_map(|row| match row { MonitorEvent::Launch(l) => Some(l), _ => None }).collect();
+                common.extend(launches.iter().map(|l| (&l.signature, &l.contract_address, l.platform, l.timestamp, &l.timing)));
+                vec![
+                    text(launches.iter().map(|l| l.name.as_deref()).collect()),
+                    text(launches.iter().map(|l| l.symbol.as_deref()).collect()),
+                    text(launches.iter().map(|l| l.creator.as_deref()).collect()),
+                    text(launches.iter().map(|l| l.uri.as_deref()).collect()),
+                ]
+            },
+            Table::Trades => {
+                let trades: Vec<_> = rows.iter().filter_map(|row| match row { MonitorEvent::Trade(t) => Some(t), _ => None }).collect();
+                common.extend(trades.iter().map(|t| (&t.signature, &t.mint, t.platform, t.timestamp, &t.timing)));
+                vec![
+                    text(trades.iter().map(|t| t.trader.as_deref()).collect()),
+                    Arc::new(BooleanArray::from(trades.iter().map(|t| t.is_buy).collect::<Vec<_>>())),
+                    amount(trades.iter().map(|t| t.sol_amount).collect()),
+                    amount(trades.iter().map(|t| t.token_amount).collect()),
+                ]
+            },
+            Table::Completions => {
+                let completions: Vec<_> = rows.iter().filter_map(|row| match row { MonitorEvent::Completion(c) => Some(c), _ => None }).collect();
+                common.extend(completions.iter().map(|c| (&c.signature, &c.mint, c.platform, c.timestamp, &c.timing)));
+                vec![
+                    text(completions.iter().map(|c| c.bonding_curve.as_deref()).collect()),
+                    text(completions.iter().map(|c| c.user.as_deref()).collect()),
+                ]
+            },
+            Table::Migrations => {
+                let migrations: Vec<_> = rows.iter().filter_map(|row| match row { MonitorEvent::Migration(m) => Some(m), _ => None }).collect();
+                common.extend(migrations.iter().map(|m| (&m.signature, &m.mint, m.platform, m.timestamp, &m.timing)));
+                vec![
+                    text(migrations.iter().map(|m| m.pool.as_deref()).collect()),
+                    amount(migrations.iter().map(|m| m.sol_amount).collect()),
+                    amount(migrations.iter().map(|m| m.token_amount).collect()),
+                ]
+            },
+        };
+
+        let mut columns: Vec<ArrayRef> = vec![
+            text(common.iter().map(|(signature, ..)| Some(signature.as_str())).collect()),
+            text(common.iter().map(|(_, mint, ..)| Some(mint.as_str())).collect()),
+            text(common.iter().map(|(_, _, platform, ..)| Some(platform.label())).collect()),
+        ];
+        columns.extend(specific);
+        columns.extend([
+            Arc::new(TimestampSecondArray::from(common.iter().map(|(.., timestamp, _)| *timestamp as i64).collect::<Vec<_>>()).with_timezone("UTC")) as ArrayRef,
+            amount(common.iter().map(|(.., timing)| timing.slot).collect()),
+            Arc::new(TimestampMillisecondArray::from(common.iter().map(|(.., timing)| timing.block_time_ms.map(|ms| ms as i64)).collect::<Vec<_>>()).with_timezone("UTC")),
+            Arc::new(TimestampMillisecondArray::from(common.iter().map(|(.., timing)| timing.detected_at_ms as i64).collect::<Vec<_>>()).with_timezone("UTC")),
+            text(common.iter().map(|(.., timing)| Some(timing.status.label())).collect()),
+        ]);
+        RecordBatch::try_new(self.schema(), columns).with_context(|| format!("building {} batch", self.name()))
+    }
+}
+
+// ========================================================================
+// PARTITIONED WRITER
+// ========================================================================
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+struct Partition {
+    table: Table,
+    // UTC date of the block time (event timestamp when unknown)
+    date: String,
+    platform: Platform,
+}
+
+impl Partition {
+    fn of(event: &MonitorEvent) -> Option<Self> {
+        let table = Table::of(event)?;
+        let timing: &EventTiming = event.timing();
+        let time_ms = timing.block_time_ms.unwrap_or_else(|| event_timestamp(event) * 1000);
+        Some(Self { table, date: utc_date(time_ms), platform: event.platform() })
+    }
+
+    // Hive-style, so DuckDB / pandas / Spark pick up `date` and `platform` as columns
+    fn dir(&self, root: &Path) -> PathBuf {
+        root.join(self.table.name())
+            .join(format!("date={}", self.date))
+            .join(format!("platform={}", self.platform.label()))
+    }
+}
+
+fn event_timestamp(event: &MonitorEvent) -> u64 {
+    match event {
+        MonitorEvent::Launch(launch) => launch.timestamp,
+        MonitorEvent::Trade(trade) => trade.timestamp,
+        MonitorEvent::Completion(completion) => completion.timestamp,
+        MonitorEvent::Migration(migration) => migration.timestamp,
+        other => other.timing().detected_at_ms / 1000,
+    }
+}
+
+/// `YYYY-MM-DD` (UTC) of a unix time in milliseconds.
+pub fn utc_date(time_ms: u64) -> String {
+    // Days since 1970-01-01 -> civil date, years counted from March
+    let days = time_ms / 86_400_000 + 719_468;
+    let era = days / 146_097;
+    let day_of_era = days - era * 146_097;
+    let year_of_era = (day_of_era - day_of_era / 1460 + day_of_era / 36_524 - day_of_era / 146_096) / 365;
+    let day_of_year = day_of_era - (365 * year_of_era + year_of_era / 4 - year_of_era / 100);
+    let month_index = (5 * day_of_year + 2) / 153;
+    let day = day_of_year - (153 * month_index + 2) / 5 + 1;
+    let month = if month_index < 10 { month_index + 3 } else { month_index - 9 };
+    let year = year_of_era + era * 400 + u64::from(month <= 2);
+    format!("{:04}-{:02}-{:02}", year, month, day)
+}
+
+struct Buffer {
+    rows: Vec<MonitorEvent>,
+    since: Instant,
+}
+
+/// Buffers events per (type, date, platform) and writes each buffer out as a
+/// new Parquet file when it is full or old enough. Files appear atomically
+/// (written as `.tmp`, then renamed), so readers never see a partial one.
+pub struct PartitionedWriter {
+    settings: ParquetConfig,
+    properties: WriterProperties,
+    buffers: HashMap<Partition, Buffer>,
+    files_written: u64,
+    rows_written: u64,
+}
+
+impl PartitionedWriter {
+    pub fn new(settings: &ParquetConfig) -> Self {
+        let properties = WriterProperties::builder()
+            .set_max_row_group_size(settings.row_group_size.max(1))
+            .set_compression(Compression::SNAPPY)
+            .build();
+        Self { settings: settings.clone(), properties, buffers: HashMap::new(), files_written: 0, rows_written: 0 }
+    }
+
+    /// Buffer a launch, trade, completion or migration (other events are ignored).
+    /// Writes the partition out if it reached `max_file_rows`.
+    pub fn push(&mut self, event: &MonitorEvent) -> Result<()> {
+        let Some(partition) = Partition::of(event) else { return Ok(()) };
+        let buffer = self.buffers.entry(partition.clone()).or_insert_with(|| Buffer { rows: Vec::new(), since: Instant::now() });
+        buffer.rows.push(event.clone());
+        if buffer.rows.len() >= self.settings.max_file_rows.max(1) {
+            self.write_partition(&partition)?;
+        }
+        Ok(())
+    }
+
+    /// Write out partitions buffered for longer than the flush interval.
+    pub fn flush_due(&mut self) -> Result<()> {
+        let interval = Duration::from_secs(self.settings.flush_interval_secs);
+        let due: Vec<Partition> = self.buffers.iter()
+            .filter(|(_, buffer)| buffer.since.elapsed() >= interval)
+            .map(|(partition, _)| partition.clone())
+            .collect();
+        due.iter().try_for_each(|partition| self.write_partition(partition))
+    }
+
+    pub fn flush_all(&mut self) -> Result<()> {
+        let all: Vec<Partition> = self.buffers.keys().cloned().collect();
+        all.iter().try_for_each(|partition| self.write_partition(partition))
+    }
+
+    pub fn files_written(&self) -> u64 {
+        self.files_written
+    }
+
+    pub fn rows_written(&self) -> u64 {
+        self.rows_written
+    }
+
+    fn write_partition(&mut self, partition: &Partition) -> Result<()> {
+        let Some(buffer) = self.buffers.remove(partition) else { return Ok(()) };
+        let dir = partition.dir(&self.settings.dir);
+        std::fs::create_dir_all(&dir).with_context(|| format!("creating {}", dir.display()))?;
+
+        // Millisecond timestamp + per-process counter keeps names unique and sortable
+        let name = format!("part-{}-{}-{}.parquet", now_ms(), std::process::id(), self.files_written);
+        let path = dir.join(&name);
+        let temp = dir.join(format!(".{}.tmp", name));
+
+        let batch = partition.table.batch(&buffer.rows)?;
+        let file = File::create(&temp).with_context(|| format!("creating {}", temp.display()))?;
+        let mut writer = ArrowWriter::try_new(file, partition.table.schema(), Some(self.properties.clone()))?;
+        writer.write(&batch)?;
+        writer.close()?;
+        std::fs::rename(&temp, &path).with_context(|| format!("renaming {}", temp.display()))?;
+
+        self.files_written += 1;
+        self.rows_written += batch.num_rows() as u64;
+        Ok(())
+    }
+}
+
+// ========================================================================
+// EXPORTER TASK
+// ========================================================================
+
+/// Follows the event hub and feeds a `PartitionedWriter`, which lives on its
+/// own thread so encoding and disk writes never hold up a runtime worker.
+pub struct ParquetExporter {
+    writer: PartitionedWriter,
+    feed: EventFeed,
+}
+
+/// Stops the exporter after writing out everything buffered.
+pub struct ParquetHandle {
+    shutdown: oneshot::Sender<()>,
+    task: JoinHandle<()>,
+}
+
+impl ParquetHandle {
+    pub async fn finish(self) {
+        let _ = self.shutdown.send(());
+        let _ = self.task.await;
+    }
+}
+
+impl ParquetExporter {
+    /// Live runs pass the hub's broadcast, offline runs a lossless feed (see `EventHub::subscribe_lossless`).
+    pub fn new(config: &Config, feed: EventFeed) -> Self {
+        Self { writer: PartitionedWriter::new(&config.parquet), feed }
+    }
+
+    pub fn spawn(self) -> ParquetHandle {
+        let (shutdown, stop) = oneshot::channel();
+        ParquetHandle { shutdown, task: tokio::spawn(self.run(stop)) }
+    }
+
+    async fn run(self, mut stop: oneshot::Receiver<()>) {
+        let Self { writer, mut feed } = self;
+        info!(
+            "🧱 Parquet export to {} (row groups of {}, flushed every {}s)",
+            writer.settings.dir.display(), writer.settings.row_group_size, writer.settings.flush_interval_secs
+        );
+
+        let (rows, receiver) = std_mpsc::channel::<MonitorEvent>();
+        let (closed_tx, closed) = oneshot::channel();
+        let started = std::thread::Builder::new()
+            .name("parquet-writer".to_string())
+            .spawn(move || {
+                write_rows(writer, receiver);
+                let _ = closed_tx.send(());
+            });
+        if let Err(e) = started {
+            error!("❌ Parquet export not started: {}", e);
+            return;
+        }
+
+        loop {
+            tokio::select! {
+                event = feed.recv() => match event {
+                    Ok(event) => {
+                        if Table::of(&event).is_some() && rows.send(event).is_err() {
+                            break; // Writer thread is gone
+                        }
+                    },
+                    Err(RecvError::Lagged(skipped)) => warn!("⚠️ Parquet export lagging, skipped {} events", skipped),
+                    Err(RecvError::Closed) => break,
+                },
+                _ = &mut stop => break,
+            }
+        }
+
+        // Whatever was published before the stop still goes out
+        while let Some(event) = feed.try_recv() {
+            if Table::of(&event).is_some() {
+                let _ = rows.send(event);
+            }
+        }
+        drop(rows);
+        let _ = closed.await;
+    }
+}
+
+/// Writer thread: buffers rows until the exporter hangs up, writing out
+/// partitions as they fill or age, then everything left.
+fn write_rows(mut writer: PartitionedWriter, rows: std_mpsc::Receiver<MonitorEvent>) {
+    let check_interval = Duration::from_secs(CHECK_INTERVAL_SECS);
+    let mut last_check = Instant::now();
+    loop {
+        match rows.recv_timeout(check_interval) {
+            Ok(event) => {
+                if let Err(e) = writer.push(&event) {
+                    error!("❌ Parquet write failed: {:#}", e);
+                }
+            },
+            Err(RecvTimeoutError::Timeout) => {},
+            Err(RecvTimeoutError::Disconnected) => break,
+        }
+        if last_check.elapsed() >= check_interval {
+            if let Err(e) = writer.flush_due() {
+                error!("❌ Parquet flush failed: {:#}", e);
+            }
+            last_check = Instant::now();
+        }
+    }
+
+    match writer.flush_all() {
+        Ok(()) => info!("🧱 Parquet export closed: {} rows in {} files", writer.rows_written(), writer.files_written()),
+        Err(e) => error!("❌ Parquet flush failed: {:#}", e),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::event_store::EventHub;
+    use crate::types::{TokenLaunch, TradeEvent, TxStatus};
+    use parquet::arrow::arrow_reader::ParquetRecordBatchReaderBuilder;
+
+    fn timing(block_time_ms: Option<u64>) -> EventTiming {
+        EventTiming { slot: Some(7), block_time_ms, detected_at_ms: 1_700_000_001_000, status: TxStatus::Confirmed }
+    }
+
+    fn launch(mint: &str, platform: Platform, block_time_ms: Option<u64>) -> MonitorEvent {
+        MonitorEvent::Launch(TokenLaunch {
+            contract_address: mint.to_string(),
+            name: Some("Token".to_string()),
+            symbol: None,
+            creator: Some("creator".to_string()),
+            uri: None,
+            signature: format!("sig-{}", mint),
+            platform,
+            timestamp: 1_700_000_000,
+            timing: timing(block_time_ms),
+        })
+    }
+
+    fn trade(mint: &str) -> MonitorEvent {
+        MonitorEvent::Trade(TradeEvent {
+            mint: mint.to_string(),
+            trader: None,
+            is_buy: true,
+            sol_amount: Some(5),
+            token_amount: None,
+            signature: "trade".to_string(),
+            platform: Platform::PumpFun,
+            timestamp: 1_700_000_000,
+            timing: timing(None),
+        })
+    }
+
+    fn settings(name: &str) -> ParquetConfig {
+        let dir = std::env::temp_dir().join(format!("parquet-export-{}-{}", std::process::id(), name));
+        ParquetConfig { enabled: true, dir, row_group_size: 2, flush_interval_secs: 60, max_file_rows: 100 }
+    }
+
+    fn files(dir: &Path) -> Vec<PathBuf> {
+        let mut files: Vec<PathBuf> = std::fs::read_dir(dir).unwrap().map(|entry| entry.unwrap().path()).collect();
+        files.sort();
+        files
+    }
+
+    fn read(path: &Path) -> (SchemaRef, usize, Vec<RecordBatch>) {
+        let builder = ParquetRecordBatchReaderBuilder::try_new(File::open(path).unwrap()).unwrap();
+        let row_groups = builder.metadata().num_row_groups();
+        let schema = builder.schema().clone();
+        (schema, row_groups, builder.build().unwrap().map(Result::unwrap).collect())
+    }
+
+    #[test]
+    fn formats_utc_dates() {
+        assert_eq!(utc_date(0), "1970-01-01");
+        assert_eq!(utc_date(1_700_000_000_000), "2023-11-14");
+        assert_eq!(utc_date(1_709_251_199_999), "2024-02-29");
+        assert_eq!(utc_date(1_709_251_200_000), "2024-03-01");
+    }
+
+    #[test]
+    fn partitions_by_type_date_and_platform() {
+        let settings = settings("partitions");
+        let mut writer = PartitionedWriter::new(&settings);
+        writer.push(&launch("a", Platform::PumpFun, Some(1_700_000_000_000))).unwrap();
+        writer.push(&launch("b", Platform::PumpFun, Some(1_700_100_000_000))).unwrap();
+        writer.push(&launch("c", Platform::Raydium, None)).unwrap();
+        writer.push(&trade("a")).unwrap();
+        writer.flush_all().unwrap();
+        assert_eq!((writer.files_written(), writer.rows_written()), (4, 4));
+
+        let launches = settings.dir.join("launches");
+        assert_eq!(files(&launches.join("date=2023-11-14").join("platform=pump_fun")).len(), 1);
+        assert_eq!(files(&launches.join("date=2023-11-16").join("platform=pump_fun")).len(), 1);
+        // No block time - the event timestamp decides the date
+        assert_eq!(files(&launches.join("date=2023-11-14").join("platform=raydium")).len(), 1);
+        assert_eq!(files(&settings.dir.join("trades").join("date=2023-11-14").join("platform=pump_fun")).len(), 1);
+        std::fs::remove_dir_all(settings.dir).ok();
+    }
+
+    #[test]
+    fn writes_stable_schema_in_row_groups() {
+        let mut settings = settings("row-groups");
+        settings.max_file_rows = 5;
+        let mut writer = PartitionedWriter::new(&settings);
+        for index in 0..5 {
+            writer.push(&launch(&format!("mint{}", index), Platform::PumpFun, Some(1_700_000_000_000))).unwrap();
+        }
+        // Full partitions go out without waiting for a flush
+        assert_eq!(writer.files_written(), 1);
+
+        let partition = settings.dir.join("launches").join("date=2023-11-14").join("platform=pump_fun");
+        let written = files(&partition);
+        assert_eq!(written.len(), 1);
+        assert!(written[0].extension().is_some_and(|ext| ext == "parquet"));
+        let (schema, row_groups, batches) = read(&written[0]);
+        assert_eq!(schema.as_ref(), Table::Launches.schema().as_ref());
+        assert_eq!(row_groups, 3);
+
+        let rows: usize = batches.iter().map(|batch| batch.num_rows()).sum();
+        assert_eq!(rows, 5);
+        let mints = batches[0].column_by_name("mint").unwrap().as_any().downcast_ref::<StringArray>().unwrap();
+        assert_eq!(mints.value(0), "mint0");
+        let symbols = batches[0].column_by_name("symbol").unwrap();
+        assert!(symbols.is_null(0));
+        std::fs::remove_dir_all(settings.dir).ok();
+    }
+
+    // Default #[tokio::test] runtime is current-thread, where block_in_place panics
+    #[tokio::test]
+    async fn exporter_writes_everything_published_before_the_feed_closes() {
+        let config = Config { parquet: settings("exporter"), ..Config::default() };
+        let events = EventHub::new();
+        let parquet = ParquetExporter::new(&config, events.subscribe_lossless()).spawn();
+        for index in 0..3 {
+            events.publish(launch(&format!("mint{}", index), Platform::PumpFun, Some(1_700_000_000_000)));
+        }
+        events.close_lossless();
+        parquet.task.await.unwrap();
+
+        let partition = config.parquet.dir.join("launches").join("date=2023-11-14").join("platform=pump_fun");
+        let rows: usize = files(&partition).iter().flat_map(|path| read(path).2).map(|batch| batch.num_rows()).sum();
+        assert_eq!(rows, 3);
+        std::fs::remove_dir_all(&config.parquet.dir).ok();
+    }
+
+    #[test]
+    fn every_table_builds_against_its_schema() {
+        for table in Table::ALL {
+            let batch = table.batch(&[]).unwrap();
+            assert_eq!(batch.num_rows(), 0);
+            let schema = batch.schema();
+            let names: Vec<&str> = schema.fields().iter().map(|f| f.name().as_str()).collect();
+            assert_eq!(&names[..3], ["signature", "mint", "platform"]);
+            assert_eq!(names.last(), Some(&"status"));
+        }
+    }
+}
@@ -0,0 +1,514 @@
+//! Pump.fun bonding curve math: a constant-product curve over virtual reserves.
+//! Pure functions, no I/O - shared by the curve tracker and usable on its own.
+
+use anyhow::{anyhow, Result};
+use solana_sdk::pubkey::Pubkey;
+use std::str::FromStr;
+
+use crate::types::{PUMP_BONDING_CURVE_ACCOUNT_DISCRIMINATOR, PUMP_GLOBAL_ACCOUNT_DISCRIMINATOR, PUMP_FUN_PROGRAM_ID};
+
+pub const LAMPORTS_PER_SOL: u64 = 1_000_000_000;
+// Pump.fun mints always use 6 decimals
+pub const TOKEN_DECIMALS: u32 = 6;
+const BPS_DENOMINATOR: u128 = 10_000;
+
+// ========================================================================
+// ACCOUNTS
+// ========================================================================
+
+/// Decoded pump.fun `Global` account - curve parameters and fees.
+#[derive(Debug, Clone, PartialEq)]
+pub struct GlobalAccount {
+    pub fee_recipient: Pubkey,
+    pub initial_virtual_token_reserves: u64,
+    pub initial_virtual_sol_reserves: u64,
+    pub initial_real_token_reserves: u64,
+    pub token_total_supply: u64,
+    pub fee_basis_points: u64,
+    // Zero on layouts from before the creator-fee upgrade
+    pub creator_fee_basis_points: u64,
+}
+
+impl Default for GlobalAccount {
+    // Mainnet values, used until the real account has been read
+    fn default() -> Self {
+        Self {
+            fee_recipient: Pubkey::default(),
+            initial_virtual_token_reserves: 1_073_000_000_000_000,
+            initial_virtual_sol_reserves: 30_000_000_000,
+            initial_real_token_reserves: 793_100_000_000_000,
+            token_total_supply: 1_000_000_000_000_000,
+            fee_basis_points: 95,
+            creator_fee_basis_points: 5,
+        }
+    }
+}
+
+impl GlobalAccount {
+    pub fn decode(data: &[u8]) -> Result<Self> {
+        if data.len() < 113 {
+            return Err(anyhow!("Invalid data length for global: {}", data.len()));
+        }
+        if data[..8] != PUMP_GLOBAL_ACCOUNT_DISCRIMINATOR {
+            return Err(anyhow!("Unknown discriminator for global"));
+        }
+
+        // initialized (1) | authority (32) | fee_recipient (32) | 5 x u64 | withdraw_authority (32)
+        // | enable_migrate (1) | pool_migration_fee (8) | creator_fee_basis_points (8)
+        Ok(Self {
+            fee_recipient: Pubkey::new_from_array(data[41..73].try_into()?),
+            initial_virtual_token_reserves: u64_at(data, 73),
+            initial_virtual_sol_reserves: u64_at(data, 81),
+            initial_real_token_reserves: u64_at(data, 89),
+            token_total_supply: u64_at(data, 97),
+            fee_basis_points: u64_at(data, 105),
+            creator_fee_basis_points: if data.len() >= 162 { u64_at(data, 154) } else { 0 },
+        })
+    }
+
+    /// Everything a trader pays on top of the curve price.
+    pub fn total_fee_bps(&self) -> u64 {
+        self.fee_basis_points + self.creator_fee_basis_points
+    }
+}
+
+/// Decoded pump.fun `BondingCurve` account.
+#[derive(Debug, Clone, PartialEq)]
+pub struct BondingCurveAccount {
+    pub virtual_token_reserves: u64,
+    pub virtual_sol_reserves: u64,
+    pub real_token_reserves: u64,
+    pub real_sol_reserves: u64,
+    pub token_total_supply: u64,
+    pub complete: bool,
+    // Only present on curves created after the creator-fee upgrade
+    pub creator: Option<Pubkey>,
+}
+
+impl BondingCurveAccount {
+    pub fn decode(data: &[u8]) -> Result<Self> {
+        if data.len() < 8 + 5 * 8 + 1 {
+            return Err(anyhow!("Invalid data length for bonding curve: {}", data.len()));
+        }
+        if data[..8] != PUMP_BONDING_CURVE_ACCOUNT_DISCRIMINATOR {
+            return Err(anyhow!("Unknown discriminator for bonding curve"));
+        }
+
+        let creator = data.get(49..81).map(|bytes| Pubkey::new_from_array(bytes.try_into().expect("32 bytes")));
+        Ok(Self {
+            virtual_token_reserves: u64_at(data, 8),
+            virtual_sol_reserves: u64_at(data, 16),
+            real_token_reserves: u64_at(data, 24),
+            real_sol_reserves: u64_at(data, 32),
+            token_total_supply: u64_at(data, 40),
+            complete: data[48] != 0,
+            creator,
+        })
+    }
+
+    /// Fresh curve as created by the program.
+    pub fn initial(global: &GlobalAccount) -> Self {
+        Self {
+            virtual_token_reserves: global.initial_virtual_token_reserves,
+            virtual_sol_reserves: global.initial_virtual_sol_reserves,
+            real_token_reserves: global.initial_real_token_reserves,
+            real_sol_reserves: 0,
+            token_total_supply: global.token_total_supply,
+            complete: false,
+            creator: None,
+        }
+    }
+}
+
+/// Bonding curve PDA for a pump.fun mint.
+pub fn bonding_curve_address(mint: &Pubkey) -> Pubkey {
+    Pubkey::find_program_address(&[b"bonding-curve", mint.as_ref()], &pump_program_id()).0
+}
+
+/// The singleton `Global` PDA.
+pub fn global_address() -> Pubkey {
+    Pubkey::find_program_address(&[b"global"], &pump_program_id()).0
+}
+
+fn pump_program_id() -> Pubkey {
+    Pubkey::from_str(PUMP_FUN_PROGRAM_ID).expect("valid program id")
+}
+
+fn u64_at(data: &[u8], offset: usize) -> u64 {
+    u64::from_le_bytes(data[offset..offset + 8].try_into().expect("length checked"))
+}
+
+// ========================================================================
+// DERIVED METRICS
+// ========================================================================
+
+/// Human-facing numbers derived from the reserves.
+#[derive(Debug, Clone, PartialEq)]
+pub struct CurveMetrics {
+    pub price_sol: f64,
+    pub market_cap_sol: f64,
+    pub sol_raised: u64,
+    // Lamports still needed to buy out the curve (before fees)
+    pub sol_to_complete: u64,
+    pub progress_pct: f64,
+}
+
+impl BondingCurveAccount {
+    /// Spot price of one whole token in SOL.
+    pub fn price_sol(&self) -> f64 {
+        if self.virtual_token_reserves == 0 {
+            return 0.0;
+        }
+        let sol = self.virtual_sol_reserves as f64 / LAMPORTS_PER_SOL as f64;
+        let tokens = self.virtual_token_reserves as f64 / 10f64.powi(TOKEN_DECIMALS as i32);
+        sol / tokens
+    }
+
+    /// Fully diluted market cap in SOL at the spot price.
+    pub fn market_cap_sol(&self) -> f64 {
+        self.price_sol() * self.token_total_supply as f64 / 10f64.powi(TOKEN_DECIMALS as i32)
+    }
+
+    /// Lamports needed to buy every remaining real token, fees excluded.
+    pub fn sol_to_complete(&self) -> u64 {
+        if self.complete || self.real_token_reserves == 0 {
+            return 0;
+        }
+        buy_cost(self, self.real_token_reserves).unwrap_or(u64::MAX)
+    }
+
+    /// 0-100, share of the initially sellable tokens already bought.
+    pub fn progress_pct(&self, global: &GlobalAccount) -> f64 {
+        if self.complete || global.initial_real_token_reserves == 0 {
+            return 100.0;
+        }
+        let sold = global.initial_real_token_reserves.saturating_sub(self.real_token_reserves);
+        sold as f64 / global.initial_real_token_reserves as f64 * 100.0
+    }
+
+    pub fn metrics(&self, global: &GlobalAccount) -> CurveMetrics {
+        CurveMetrics {
+            price_sol: self.price_sol(),
+            market_cap_sol: self.market_cap_sol(),
+            sol_raised: self.real_sol_reserves,
+            sol_to_complete: self.sol_to_complete(),
+            progress_pct: self.progress_pct(global),
+        }
+    }
+}
+
+// ========================================================================
+// QUOTES
+// ========================================================================
+
+/// Result of a simulated trade. Amounts are lamports / token base units.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Quote {
+    pub amount_in: u64,
+    pub amount_out: u64,
+    pub fee: u64,
+    // Execution price vs. spot price before the trade, in percent
+    pub price_impact_pct: f64,
+    // Curve after the trade
+    pub after: BondingCurveAccount,
+}
+
+impl BondingCurveAccount {
+    /// Spend exactly `sol_in` lamports (fees included). Buys are capped at the
+    /// remaining real tokens, in which case less SOL is spent.
+    pub fn quote_buy_with_sol(&self, sol_in: u64, fee_bps: u64) -> Option<Quote> {
+        if self.complete || sol_in == 0 {
+            return None;
+        }
+
+        let net = (sol_in as u128 * BPS_DENOMINATOR / (BPS_DENOMINATOR + fee_bps as u128)) as u64;
+        let tokens = mul_div_floor(net, self.virtual_token_reserves, self.virtual_sol_reserves.checked_add(net)?)?;
+        let tokens = tokens.min(self.real_token_reserves);
+        if tokens == 0 {
+            return None;
+        }
+
+        // Capped buys cost less than offered
+        if tokens == self.real_token_reserves {
+            return self.quote_buy_tokens(tokens, fee_bps);
+        }
+
+        let cost = buy_cost(self, tokens)?.min(net);
+        Some(self.buy_quote(tokens, cost, fee_on(cost, fee_bps).min(sol_in - cost)))
+    }
+
+    /// Buy exactly `tokens_out` base units. `None` if the curve can't supply them.
+    pub fn quote_buy_tokens(&self, tokens_out: u64, fee_bps: u64) -> Option<Quote> {
+        if self.complete || tokens_out == 0 || tokens_out > self.real_token_reserves {
+            return None;
+        }
+        let cost = buy_cost(self, tokens_out)?;
+        let fee = fee_on(cost, fee_bps);
+        Some(self.buy_quote(tokens_out, cost, fee))
+    }
+
+    /// Sell exactly `tokens_in` base units; `amount_out` is SOL after fees.
+    pub fn quote_sell_tokens(&self, tokens_in: u64, fee_bps: u64) -> Option<Quote> {
+        if self.complete || tokens_in == 0 {
+            return None;
+        }
+        let gross = mul_div_floor(tokens_in, self.virtual_sol_reserves, self.virtual_token_reserves.checked_add(tokens_in)?)?;
+        if gross == 0 || gross > self.real_sol_reserves {
+            return None;
+        }
+        let fee = fee_on(gross, fee_bps);
+        Some(self.sell_quote(tokens_in, gross, fee))
+    }
+
+    /// Tokens to sell so that exactly `sol_out` lamports arrive after fees.
+    pub fn quote_sell_for_sol(&self, sol_out: u64, fee_bps: u64) -> Option<Quote> {
+        if self.complete || sol_out == 0 || sol_out > self.real_sol_reserves || fee_bps as u128 >= BPS_DENOMINATOR {
+            return None;
+        }
+        let mut gross = u64::try_from(ceil_div(sol_out as u128 * BPS_DENOMINATOR, BPS_DENOMINATOR - fee_bps as u128)).ok()?;
+        // Fee rounds up - nudge until the net covers the request
+        while gross - fee_on(gross, fee_bps) < sol_out {
+            gross = gross.checked_add(1)?;
+        }
+        if gross >= self.virtual_sol_reserves || gross > self.real_sol_reserves {
+            return None;
+        }
+
+        let mut tokens = mul_div_ceil(gross, self.virtual_token_reserves, self.virtual_sol_reserves - gross)?;
+        // The program floors the SOL it pays out - make sure these tokens really yield `gross`
+        let mut paid = mul_div_floor(tokens, self.virtual_sol_reserves, self.virtual_token_reserves.checked_add(tokens)?)?;
+        while paid < gross {
+            tokens = tokens.checked_add(1)?;
+            paid = mul_div_floor(tokens, self.virtual_sol_reserves, self.virtual_token_reserves.checked_add(tokens)?)?;
+        }
+        if paid > self.real_sol_reserves {
+            return None;
+        }
+        Some(self.sell_quote(tokens, paid, fee_on(paid, fee_bps)))
+    }
+
+    fn buy_quote(&self, tokens: u64, cost: u64, fee: u64) -> Quote {
+        let real_token_reserves = self.real_token_reserves - tokens;
+        let after = BondingCurveAccount {
+            virtual_token_reserves: self.virtual_token_reserves - tokens,
+            virtual_sol_reserves: self.virtual_sol_reserves + cost,
+            real_token_reserves,
+            real_sol_reserves: self.real_sol_reserves + cost,
+            complete: real_token_reserves == 0,
+            ..self.clone()
+        };
+        Quote {
+            amount_in: cost + fee,
+            amount_out: tokens,
+            fee,
+            price_impact_pct: price_impact(self.price_sol(), cost, tokens),
+            after,
+        }
+    }
+
+    fn sell_quote(&self, tokens: u64, gross: u64, fee: u64) -> Quote {
+        let after = BondingCurveAccount {
+            virtual_token_reserves: self.virtual_token_reserves + tokens,
+            virtual_sol_reserves: self.virtual_sol_reserves - gross,
+            real_token_reserves: self.real_token_reserves + tokens,
+            real_sol_reserves: self.real_sol_reserves - gross,
+            ..self.clone()
+        };
+        Quote {
+            amount_in: tokens,
+            amount_out: gross - fee,
+            fee,
+            price_impact_pct: price_impact(self.price_sol(), gross, tokens),
+            after,
+        }
+    }
+}
+
+// Lamports (pre-fee) to take `tokens` out of the curve, rounded in the curve's favour
+fn buy_cost(curve: &BondingCurveAccount, tokens: u64) -> Option<u64> {
+    let remaining = curve.virtual_token_reserves.checked_sub(tokens).filter(|r| *r > 0)?;
+    mul_div_ceil(tokens, curve.virtual_sol_reserves, remaining)
+}
+
+fn fee_on(amount: u64, fee_bps: u64) -> u64 {
+    ceil_div(amount as u128 * fee_bps as u128, BPS_DENOMINATOR) as u64
+}
+
+fn price_impact(spot_price_sol: f64, lamports: u64, tokens: u64) -> f64 {
+    if spot_price_sol == 0.0 || tokens == 0 {
+        return 0.0;
+    }
+    let execution = (lamports as f64 / LAMPORTS_PER_SOL as f64) / (tokens as f64 / 10f64.powi(TOKEN_DECIMALS as i32));
+    (execution / spot_price_sol - 1.0).abs() * 100.0
+}
+
+fn mul_div_floor(a: u64, b: u64, denominator: u64) -> Option<u64> {
+    if denominator == 0 {
+        return None;
+    }
+    u64::try_from(a as u128 * b as u128 / denominator as u128).ok()
+}
+
+fn mul_div_ceil(a: u64, b: u64, denominator: u64) -> Option<u64> {
+    if denominator == 0 {
+        return None;
+    }
+    u64::try_from(ceil_div(a as u128 * b as u128, denominator as u128)).ok()
+}
+
+fn ceil_div(numerator: u128, denominator: u128) -> u128 {
+    numerator.div_ceil(denominator)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use proptest::prelude::*;
+
+    fn fresh() -> BondingCurveAccount {
+        BondingCurveAccount::initial(&GlobalAccount::default())
+    }
+
+    // Any curve state reachable by buying from a fresh curve
+    fn curve_state() -> impl Strategy<Value = BondingCurveAccount> {
+        (0u64..=GlobalAccount::default().initial_real_token_reserves - 1).prop_map(|sold| {
+            if sold == 0 {
+                return fresh();
+            }
+            fresh().quote_buy_tokens(sold, 0).expect("within reserves").after
+        })
+    }
+
+    fn invariant(curve: &BondingCurveAccount) -> u128 {
+        curve.virtual_sol_reserves as u128 * curve.virtual_token_reserves as u128
+    }
+
+    #[test]
+    fn fresh_curve_matches_mainnet_numbers() {
+        let curve = fresh();
+        let global = GlobalAccount::default();
+
+        // ~0.000000028 SOL per token, ~28 SOL market cap, ~85 SOL to complete
+        assert!((curve.price_sol() - 2.796e-8).abs() < 1e-10);
+        assert!((curve.market_cap_sol() - 27.96).abs() < 0.01);
+        let to_complete = curve.sol_to_complete() as f64 / LAMPORTS_PER_SOL as f64;
+        assert!((to_complete - 85.0).abs() < 0.1, "{}", to_complete);
+        assert_eq!(curve.progress_pct(&global), 0.0);
+    }
+
+    #[test]
+    fn buying_everything_completes_the_curve() {
+        let curve = fresh();
+        let quote = curve.quote_buy_with_sol(200 * LAMPORTS_PER_SOL, 100).unwrap();
+        assert_eq!(quote.amount_out, curve.real_token_reserves);
+        assert!(quote.after.complete);
+        assert!(quote.amount_in < 200 * LAMPORTS_PER_SOL);
+        assert_eq!(quote.after.progress_pct(&GlobalAccount::default()), 100.0);
+        assert!(quote.after.quote_buy_with_sol(LAMPORTS_PER_SOL, 100).is_none());
+    }
+
+    #[test]
+    fn sell_for_more_sol_than_the_curve_holds_is_refused() {
+        let curve = fresh().quote_buy_with_sol(LAMPORTS_PER_SOL, 0).unwrap().after;
+        assert!(curve.quote_sell_for_sol(curve.real_sol_reserves + 1, 100).is_none());
+        assert!(curve.quote_sell_for_sol(u64::MAX, 100).is_none());
+        assert!(curve.quote_sell_for_sol(u64::MAX, 9_999).is_none());
+    }
+
+    #[test]
+    fn decodes_bonding_curve_account() {
+        let mut data = PUMP_BONDING_CURVE_ACCOUNT_DISCRIMINATOR.to_vec();
+        for value in [1u64, 2, 3, 4, 5] {
+            data.extend_from_slice(&value.to_le_bytes());
+        }
+        data.push(1);
+        data.extend_from_slice(&[7u8; 32]);
+
+        let curve = BondingCurveAccount::decode(&data).unwrap();
+        assert_eq!(curve.virtual_token_reserves, 1);
+        assert_eq!(curve.token_total_supply, 5);
+        assert!(curve.complete);
+        assert_eq!(curve.creator, Some(Pubkey::new_from_array([7u8; 32])));
+        assert!(BondingCurveAccount::decode(&data[..40]).is_err());
+    }
+
+    #[test]
+    fn decodes_global_account() {
+        let mut data = PUMP_GLOBAL_ACCOUNT_DISCRIMINATOR.to_vec();
+        data.push(1);
+        data.extend_from_slice(&[1u8; 32]);
+        data.extend_from_slice(&[2u8; 32]);
+        for value in [10u64, 20, 30, 40, 95] {
+            data.extend_from_slice(&value.to_le_bytes());
+        }
+        data.extend_from_slice(&[3u8; 32]);
+        data.push(1);
+        data.extend_from_slice(&0u64.to_le_bytes());
+        data.extend_from_slice(&5u64.to_le_bytes());
+
+        let global = GlobalAccount::decode(&data).unwrap();
+        assert_eq!(global.fee_recipient, Pubkey::new_from_array([2u8; 32]));
+        assert_eq!(global.initial_real_token_reserves, 30);
+        assert_eq!(global.total_fee_bps(), 100);
+    }
+
+    proptest! {
+        #[test]
+        fn buys_never_shrink_the_invariant(curve in curve_state(), sol in 1u64..100 * LAMPORTS_PER_SOL, fee in 0u64..500) {
+            if let Some(quote) = curve.quote_buy_with_sol(sol, fee) {
+                prop_assert!(invariant(&quote.after) >= invariant(&curve));
+                prop_assert!(quote.amount_in <= sol);
+                prop_assert!(quote.amount_out <= curve.real_token_reserves);
+                prop_assert!(quote.after.price_sol() >= curve.price_sol());
+            }
+        }
+
+        #[test]
+        fn sells_never_shrink_the_invariant(curve in curve_state(), tokens in 1u64..800_000_000_000_000, fee in 0u64..500) {
+            if let Some(quote) = curve.quote_sell_tokens(tokens, fee) {
+                prop_assert!(invariant(&quote.after) >= invariant(&curve));
+                prop_assert!(quote.after.price_sol() <= curve.price_sol());
+                prop_assert!(quote.after.real_sol_reserves <= curve.real_sol_reserves);
+            }
+        }
+
+        #[test]
+        fn round_trip_never_profits(curve in curve_state(), sol in 1_000u64..50 * LAMPORTS_PER_SOL, fee in 0u64..500) {
+            if let Some(buy) = curve.quote_buy_with_sol(sol, fee) {
+                if let Some(sell) = buy.after.quote_sell_tokens(buy.amount_out, fee) {
+                    prop_assert!(sell.amount_out <= buy.amount_in);
+                }
+            }
+        }
+
+        #[test]
+        fn more_sol_buys_at_least_as_many_tokens(curve in curve_state(), sol in 1u64..50 * LAMPORTS_PER_SOL, extra in 0u64..LAMPORTS_PER_SOL) {
+            let smaller = curve.quote_buy_with_sol(sol, 100).map_or(0, |q| q.amount_out);
+            let larger = curve.quote_buy_with_sol(sol + extra, 100).map_or(0, |q| q.amount_out);
+            prop_assert!(larger >= smaller);
+        }
+
+        #[test]
+        fn exact_token_buy_fits_the_sol_offered(curve in curve_state(), sol in 1_000u64..50 * LAMPORTS_PER_SOL, fee in 0u64..500) {
+            if let Some(by_sol) = curve.quote_buy_with_sol(sol, fee) {
+                let by_tokens = curve.quote_buy_tokens(by_sol.amount_out, fee).unwrap();
+                prop_assert!(by_tokens.amount_in <= sol);
+            }
+        }
+
+        #[test]
+        fn sell_for_sol_delivers_at_least_the_request(curve in curve_state(), sol in 1u64..10 * LAMPORTS_PER_SOL, fee in 0u64..500) {
+            if let Some(quote) = curve.quote_sell_for_sol(sol, fee) {
+                prop_assert!(quote.amount_out >= sol);
+                let check = curve.quote_sell_tokens(quote.amount_in, fee).unwrap();
+                prop_assert!(check.amount_out >= sol);
+            }
+        }
+
+        #[test]
+        fn progress_stays_in_range(curve in curve_state()) {
+            let progress = curve.progress_pct(&GlobalAccount::default());
+            prop_assert!((0.0..=100.0).contains(&progress));
+        }
+    }
+}
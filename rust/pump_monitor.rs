@@ -1,22 +1,39 @@
 use anyhow::Result;
 use solana_sdk::pubkey::Pubkey;
 use std::collections::HashSet;
-use tokio::time::{sleep, Duration};
-use tracing::{info, error, warn};
+use tokio::sync::mpsc;
+use tokio::time::{sleep, Duration, Instant};
+use tracing::{info, error, warn, debug};
 use base64::{Engine as _, engine::general_purpose};
 use serde_json::{json, Value};
 use tokio_tungstenite::{connect_async, tungstenite::protocol::Message};
 use futures_util::{SinkExt, StreamExt};
 
-use crate::types::{TokenLaunch, Platform, PUMP_CREATE_EVENT_DISCRIMINATOR, PUMP_FUN_PROGRAM_ID};
+use crate::block_clock::{now_ms, BlockClock};
+use crate::config::{Commitment, Config, PumpConfig};
+use crate::event_store::EventHub;
+use crate::metrics::metrics;
+use crate::ws_recording::{Frame, FrameRecorder, FrameSource};
+use crate::types::{
+    TokenLaunch, TradeEvent, CompletionEvent, MigrationEvent, Platform, MonitorEvent, EventTiming,
+    PUMP_CREATE_EVENT_DISCRIMINATOR, PUMP_TRADE_EVENT_DISCRIMINATOR, PUMP_COMPLETE_EVENT_DISCRIMINATOR,
+    PUMP_MIGRATION_EVENT_DISCRIMINATOR, PUMP_FUN_PROGRAM_ID, short_signature,
+};
 
 pub struct PumpFunMonitor {
     ws_url: String,
+    commitment: Commitment,
+    settings: PumpConfig,
     processed_signatures: HashSet<String>,
+    events: EventHub,
+    block_clock: BlockClock,
+    recorder: Option<FrameRecorder>,
 }
 
 impl PumpFunMonitor {
-    pub async fn new(ws_url: &str) -> Result<Self> {
+    pub async fn new(config: &Config, events: EventHub, block_clock: BlockClock) -> Result<Self> {
+        let ws_url = config.endpoints.ws_url.as_str();
+        
         // Convert HTTP URL to WebSocket URL if needed
         let ws_url = if ws_url.starts_with("wss://") || ws_url.starts_with("ws://") {
             ws_url.to_string()
@@ -33,26 +50,52 @@ impl PumpFunMonitor {
         
         Ok(Self {
             ws_url,
+            commitment: config.commitment,
+            settings: config.pump.clone(),
             processed_signatures: HashSet::new(),
+            events,
+            block_clock,
+            recorder: None,
         })
     }
 
+    /// Write every WebSocket text frame to a recording (see `--record`).
+    pub fn with_recorder(mut self, recorder: Option<FrameRecorder>) -> Self {
+        self.recorder = recorder;
+        self
+    }
+
+    /// Feed recorded frames through the same path as live ones, until the sender closes.
+    pub async fn replay(&mut self, mut frames: mpsc::Receiver<Frame>) {
+        info!("⏪ Replaying recorded Pump.fun frames");
+        while let Some(frame) = frames.recv().await {
+            // Detected when it was recorded, not now
+            if let Err(e) = self.process_websocket_message(&frame.text, frame.at_ms).await {
+                metrics().decode_error(Platform::PumpFun, &e);
+                warn!("⚠️ Error processing WebSocket message: {}", e);
+            }
+        }
+    }
+
     pub async fn start_monitoring(&mut self) -> Result<()> {
         info!("🎯 Starting Pump.fun WebSocket real-time monitoring");
         
         loop {
-            match self.start_websocket_monitoring().await {
+            let result = self.start_websocket_monitoring().await;
+            metrics().ws_disconnected(Platform::PumpFun);
+            
+            match result {
                 Ok(_) => {
                     info!("🔄 WebSocket connection ended, reconnecting...");
                 },
                 Err(e) => {
                     error!("❌ WebSocket error: {}", e);
-                    info!("🔄 Reconnecting in 5 seconds...");
-                    sleep(Duration::from_secs(5)).await;
+                    info!("🔄 Reconnecting in {} seconds...", self.settings.error_reconnect_delay_secs);
+                    sleep(Duration::from_secs(self.settings.error_reconnect_delay_secs)).await;
                 }
             }
             
-            sleep(Duration::from_secs(1)).await; // Brief pause before reconnect
+            sleep(Duration::from_secs(self.settings.reconnect_delay_secs)).await; // Brief pause before reconnect
         }
     }
 
@@ -73,7 +116,7 @@ impl PumpFunMonitor {
                     "mentions": [PUMP_FUN_PROGRAM_ID]
                 },
                 {
-                    "commitment": "confirmed"
+                    "commitment": self.commitment.as_str()
                 }
             ]
         });
@@ -81,11 +124,19 @@ impl PumpFunMonitor {
         write.send(Message::Text(subscription_request.to_string())).await?;
         info!("✅ Subscribed to Pump.fun program logs");
         
+        let connected_at = Instant::now();
+        metrics().ws_connected(Platform::PumpFun);
+        
         // Process incoming messages
         while let Some(message) = read.next().await {
+            metrics().ws_uptime(Platform::PumpFun, connected_at.elapsed());
             match message? {
                 Message::Text(text) => {
-                    if let Err(e) = self.process_websocket_message(&text).await {
+                    if let Some(recorder) = &self.recorder {
+                        recorder.record(FrameSource::PumpFun, &text);
+                    }
+                    if let Err(e) = self.process_websocket_message(&text, now_ms()).await {
+                        metrics().decode_error(Platform::PumpFun, &e);
                         warn!("⚠️ Error processing WebSocket message: {}", e);
                     }
                 },
@@ -100,7 +151,8 @@ impl PumpFunMonitor {
         Ok(())
     }
 
-    async fn process_websocket_message(&mut self, message: &str) -> Result<()> {
+    async fn process_websocket_message(&mut self, message: &str, detected_at_ms: u64) -> Result<()> {
+        let received_at = Instant::now();
         let data: Value = serde_json::from_str(message)?;
         
         // Handle subscription confirmation
@@ -114,7 +166,17 @@ impl PumpFunMonitor {
         // Handle log notifications
         if let Some(params) = data.get("params") {
             if let Some(result) = params.get("result") {
+                // Slot of the notification - basis for on-chain time
+                let slot = result.get("context").and_then(|c| c.get("slot")).and_then(|s| s.as_u64());
+                let mut timing = self.block_clock.timing_at(slot, detected_at_ms);
+                timing.status = self.commitment.into();
+                
                 if let Some(value) = result.get("value") {
+                    // Failed transactions still log their events but never take effect
+                    if value.get("err").is_some_and(|err| !err.is_null()) {
+                        return Ok(());
+                    }
+                    
                     if let Some(signature) = value.get("signature").and_then(|s| s.as_str()) {
                         if let Some(logs) = value.get("logs").and_then(|l| l.as_array()) {
                             
@@ -123,25 +185,40 @@ impl PumpFunMonitor {
                                 return Ok(());
                             }
                             
-                            // Look for Program data in logs (CreateEvent)
+                            // Look for Program data in logs (Create/Trade/Complete/Migration events)
+                            let mut events = Vec::new();
                             for log in logs {
-                                if let Some(log_str) = log.as_str() {
-                                    if let Some(token_launch) = self.parse_create_event_from_log(log_str, signature.to_string())? {
-                                        self.handle_token_launch(token_launch).await;
-                                        self.processed_signatures.insert(signature.to_string());
-                                        
-                                        // Keep memory usage manageable
-                                        if self.processed_signatures.len() > 1000 {
-                                            let recent: Vec<String> = self.processed_signatures
-                                                .iter().skip(500).cloned().collect();
-                                            self.processed_signatures.clear();
-                                            self.processed_signatures.extend(recent);
-                                        }
-                                        
-                                        return Ok(());
-                                    }
+                                let Some(log_str) = log.as_str() else { continue };
+                                // One undecodable event shouldn't cost the others in the transaction
+                                match parse_event_from_log(log_str, signature.to_string(), &timing) {
+                                    Ok(Some(event)) => events.push(event),
+                                    Ok(None) => {},
+                                    Err(e) => {
+                                        metrics().decode_error(Platform::PumpFun, &e);
+                                        debug!("⚠️ Skipping undecodable event in {}: {}", short_signature(signature), e);
+                                    },
                                 }
                             }
+                            
+                            if events.is_empty() {
+                                return Ok(());
+                            }
+                            
+                            for event in events {
+                                self.handle_event(event).await;
+                                metrics().emit_latency(Platform::PumpFun, received_at.elapsed());
+                            }
+                            self.processed_signatures.insert(signature.to_string());
+                            
+                            // Keep memory usage manageable
+                            if self.processed_signatures.len() > self.settings.max_processed_signatures {
+                                let drop_count = self.processed_signatures.len().saturating_sub(self.settings.keep_processed_signatures);
+                                let recent: Vec<String> = self.processed_signatures
+                                    .iter().skip(drop_count).cloned().collect();
+                                self.processed_signatures.clear();
+                                self.processed_signatures.extend(recent);
+                            }
+                            metrics().cache_size(Platform::PumpFun, "processed_signatures", self.processed_signatures.len());
                         }
                     }
                 }
@@ -151,79 +228,46 @@ impl PumpFunMonitor {
         Ok(())
     }
 
-    fn parse_create_event_from_log(&self, log: &str, signature: String) -> Result<Option<TokenLaunch>> {
-        // Look for "Program data:" in logs
-        if log.contains("Program data:") {
-            if let Some(data_part) = log.split("Program data: ").nth(1) {
-                if let Ok(data) = general_purpose::STANDARD.decode(data_part.trim()) {
-                    // Check for CreateEvent discriminator
-                    if data.len() >= 8 && data[0..8] == PUMP_CREATE_EVENT_DISCRIMINATOR {
-                        return Ok(Some(self.decode_create_event(&data, signature)?));
-                    }
-                }
-            }
+    async fn handle_event(&self, event: MonitorEvent) {
+        match event {
+            MonitorEvent::Launch(launch) => self.handle_token_launch(launch).await,
+            MonitorEvent::Trade(trade) => {
+                debug!(
+                    "💱 [{}] {} | Mint: {} | SOL: {} | TX: {}",
+                    trade.platform,
+                    if trade.is_buy { "BUY" } else { "SELL" },
+                    trade.mint,
+                    trade.sol_amount.unwrap_or(0),
+                    short_signature(&trade.signature)
+                );
+                self.events.publish(MonitorEvent::Trade(trade));
+            },
+            MonitorEvent::Completion(completion) => {
+                info!("🏁 [{}] Bonding curve complete | CA: {} | TX: {}", completion.platform, completion.mint, short_signature(&completion.signature));
+                self.events.publish(MonitorEvent::Completion(completion));
+            },
+            MonitorEvent::Migration(migration) => {
+                info!(
+                    "🔀 [{}] Migrated | CA: {} | Pool: {} | TX: {}",
+                    migration.platform,
+                    migration.mint,
+                    migration.pool.as_deref().unwrap_or("Unknown"),
+                    short_signature(&migration.signature)
+                );
+                self.events.publish(MonitorEvent::Migration(migration));
+            },
+            // Produced by the trackers, never decoded from logs
+            other @ (MonitorEvent::Status(_) | MonitorEvent::Retraction(_) | MonitorEvent::Curve(_)
+                | MonitorEvent::NearCompletion(_) | MonitorEvent::Metadata(_) | MonitorEvent::Identity(_) | MonitorEvent::Creator(_)
+                | MonitorEvent::DevBought(_) | MonitorEvent::DevSold(_) | MonitorEvent::Bundle(_)
+                | MonitorEvent::Holders(_) | MonitorEvent::Alert(_)) => {
+                self.events.publish(other)
+            },
         }
-        Ok(None)
-    }
-
-    fn decode_create_event(&self, data: &[u8], signature: String) -> Result<TokenLaunch> {
-        let mut offset = 8; // Skip discriminator
-
-        // Safely read name (string)
-        if offset + 4 > data.len() { return Err(anyhow::anyhow!("Invalid data length for name")); }
-        let name_length = u32::from_le_bytes([data[offset], data[offset+1], data[offset+2], data[offset+3]]) as usize;
-        offset += 4;
-        
-        if offset + name_length > data.len() { return Err(anyhow::anyhow!("Invalid name length")); }
-        let name = String::from_utf8(data[offset..offset + name_length].to_vec())?;
-        offset += name_length;
-
-        // Safely read symbol (string)
-        if offset + 4 > data.len() { return Err(anyhow::anyhow!("Invalid data length for symbol")); }
-        let symbol_length = u32::from_le_bytes([data[offset], data[offset+1], data[offset+2], data[offset+3]]) as usize;
-        offset += 4;
-        
-        if offset + symbol_length > data.len() { return Err(anyhow::anyhow!("Invalid symbol length")); }
-        let symbol = String::from_utf8(data[offset..offset + symbol_length].to_vec())?;
-        offset += symbol_length;
-
-        // Skip URI
-        if offset + 4 > data.len() { return Err(anyhow::anyhow!("Invalid data length for URI")); }
-        let uri_length = u32::from_le_bytes([data[offset], data[offset+1], data[offset+2], data[offset+3]]) as usize;
-        offset += 4 + uri_length;
-
-        // Read mint (32 bytes)
-        if offset + 32 > data.len() { return Err(anyhow::anyhow!("Invalid data length for mint")); }
-        let mint_bytes: [u8; 32] = data[offset..offset + 32].try_into()?;
-        let mint = Pubkey::new_from_array(mint_bytes);
-        offset += 32;
-
-        // Skip bonding curve (32 bytes)
-        offset += 32;
-
-        // Read creator (32 bytes)
-        if offset + 32 > data.len() { return Err(anyhow::anyhow!("Invalid data length for creator")); }
-        let creator_bytes: [u8; 32] = data[offset..offset + 32].try_into()?;
-        let creator = Pubkey::new_from_array(creator_bytes);
-
-        Ok(TokenLaunch {
-            contract_address: mint.to_string(),
-            name: Some(name),
-            symbol: Some(symbol),
-            creator: Some(creator.to_string()),
-            signature,
-            platform: Platform::PumpFun,
-            timestamp: std::time::SystemTime::now()
-                .duration_since(std::time::UNIX_EPOCH)?
-                .as_secs(),
-        })
     }
 
     async fn handle_token_launch(&self, launch: TokenLaunch) {
-        // 🎯 MAIN REQUIREMENT: Print CA to console
-        println!("CA: {}", launch.contract_address);
-        
-        // Additional detailed logging
+        // Detailed logging - the `CA:` line itself is printed by the console sink
         info!(
             "🚀 [{}] {} ({}) | CA: {} | Creator: {} | TX: {}", 
             launch.platform,
@@ -231,9 +275,198 @@ impl PumpFunMonitor {
             launch.symbol.as_deref().unwrap_or("???"),
             launch.contract_address,
             launch.creator.as_deref().unwrap_or("Unknown"),
-            &launch.signature[..8]
+            short_signature(&launch.signature)
         );
+        
+        self.events.publish(MonitorEvent::Launch(launch));
+    }
+}
+
+// ========================================================================
+// EVENT DECODING
+// ========================================================================
+
+/// Decode one log line, if it is a pump.fun event (`Program data: <base64>`).
+pub fn parse_event_from_log(log: &str, signature: String, timing: &EventTiming) -> Result<Option<MonitorEvent>> {
+    // Look for "Program data:" in logs
+    if log.contains("Program data:") {
+        if let Some(data_part) = log.split("Program data: ").nth(1) {
+            let Ok(data) = general_purpose::STANDARD.decode(data_part.trim()) else {
+                metrics().decode_error_reason(Platform::PumpFun, "invalid_base64");
+                return Ok(None);
+            };
+            if data.len() < 8 {
+                return Ok(None);
+            }
+            
+            // Dispatch on the Anchor event discriminator
+            let discriminator = &data[0..8];
+            if discriminator == PUMP_CREATE_EVENT_DISCRIMINATOR {
+                return Ok(Some(MonitorEvent::Launch(decode_create_event(&data, signature, timing.clone())?)));
+            } else if discriminator == PUMP_TRADE_EVENT_DISCRIMINATOR {
+                return Ok(Some(MonitorEvent::Trade(decode_trade_event(&data, signature, timing.clone())?)));
+            } else if discriminator == PUMP_COMPLETE_EVENT_DISCRIMINATOR {
+                return Ok(Some(MonitorEvent::Completion(decode_complete_event(&data, signature, timing.clone())?)));
+            } else if discriminator == PUMP_MIGRATION_EVENT_DISCRIMINATOR {
+                return Ok(Some(MonitorEvent::Migration(decode_migration_event(&data, signature, timing.clone())?)));
+            }
+        }
     }
+    Ok(None)
+}
+
+pub fn decode_create_event(data: &[u8], signature: String, timing: EventTiming) -> Result<TokenLaunch> {
+    let mut offset = 8; // Skip discriminator
+
+    // Safely read name (string)
+    if offset + 4 > data.len() { return Err(anyhow::anyhow!("Invalid data length for name")); }
+    let name_length = u32::from_le_bytes([data[offset], data[offset+1], data[offset+2], data[offset+3]]) as usize;
+    offset += 4;
+    
+    if offset + name_length > data.len() { return Err(anyhow::anyhow!("Invalid name length")); }
+    let name = String::from_utf8(data[offset..offset + name_length].to_vec())?;
+    offset += name_length;
+
+    // Safely read symbol (string)
+    if offset + 4 > data.len() { return Err(anyhow::anyhow!("Invalid data length for symbol")); }
+    let symbol_length = u32::from_le_bytes([data[offset], data[offset+1], data[offset+2], data[offset+3]]) as usize;
+    offset += 4;
+    
+    if offset + symbol_length > data.len() { return Err(anyhow::anyhow!("Invalid symbol length")); }
+    let symbol = String::from_utf8(data[offset..offset + symbol_length].to_vec())?;
+    offset += symbol_length;
+
+    // Read URI (string)
+    if offset + 4 > data.len() { return Err(anyhow::anyhow!("Invalid data length for URI")); }
+    let uri_length = u32::from_le_bytes([data[offset], data[offset+1], data[offset+2], data[offset+3]]) as usize;
+    offset += 4;
+
+    if offset + uri_length > data.len() { return Err(anyhow::anyhow!("Invalid URI length")); }
+    let uri = String::from_utf8_lossy(&data[offset..offset + uri_length]).trim().to_string();
+    offset += uri_length;
+
+    // Read mint (32 bytes)
+    if offset + 32 > data.len() { return Err(anyhow::anyhow!("Invalid data length for mint")); }
+    let mint_bytes: [u8; 32] = data[offset..offset + 32].try_into()?;
+    let mint = Pubkey::new_from_array(mint_bytes);
+    offset += 32;
+
+    // Skip bonding curve (32 bytes)
+    offset += 32;
+
+    // Read creator (32 bytes)
+    if offset + 32 > data.len() { return Err(anyhow::anyhow!("Invalid data length for creator")); }
+    let creator_bytes: [u8; 32] = data[offset..offset + 32].try_into()?;
+    let creator = Pubkey::new_from_array(creator_bytes);
+    offset += 32;
+
+    // Current layout: creator, timestamp, reserves... - the original one ends at user
+    offset += 32;
+    let timestamp = read_timestamp(data, offset);
+
+    Ok(TokenLaunch {
+        contract_address: mint.to_string(),
+        name: Some(name),
+        symbol: Some(symbol),
+        creator: Some(creator.to_string()),
+        uri: (!uri.is_empty()).then_some(uri),
+        signature,
+        platform: Platform::PumpFun,
+        timestamp: timestamp.unwrap_or_else(|| timing.unix_secs()),
+        timing,
+    })
+}
+
+pub fn decode_trade_event(data: &[u8], signature: String, timing: EventTiming) -> Result<TradeEvent> {
+    let mut offset = 8; // Skip discriminator
+
+    let mint = read_pubkey(data, &mut offset, "mint")?;
+    let sol_amount = read_u64(data, &mut offset, "sol_amount")?;
+    let token_amount = read_u64(data, &mut offset, "token_amount")?;
+    
+    if offset + 1 > data.len() { return Err(anyhow::anyhow!("Invalid data length for is_buy")); }
+    let is_buy = data[offset] != 0;
+    offset += 1;
+    
+    let user = read_pubkey(data, &mut offset, "user")?;
+    let timestamp = read_timestamp(data, offset);
+
+    Ok(TradeEvent {
+        mint: mint.to_string(),
+        trader: Some(user.to_string()),
+        is_buy,
+        sol_amount: Some(sol_amount),
+        token_amount: Some(token_amount),
+        signature,
+        platform: Platform::PumpFun,
+        timestamp: timestamp.unwrap_or_else(|| timing.unix_secs()),
+        timing,
+    })
+}
+
+pub fn decode_complete_event(data: &[u8], signature: String, timing: EventTiming) -> Result<CompletionEvent> {
+    let mut offset = 8; // Skip discriminator
+
+    let user = read_pubkey(data, &mut offset, "user")?;
+    let mint = read_pubkey(data, &mut offset, "mint")?;
+    let bonding_curve = read_pubkey(data, &mut offset, "bonding_curve")?;
+    let timestamp = read_timestamp(data, offset);
+
+    Ok(CompletionEvent {
+        mint: mint.to_string(),
+        bonding_curve: Some(bonding_curve.to_string()),
+        user: Some(user.to_string()),
+        signature,
+        platform: Platform::PumpFun,
+        timestamp: timestamp.unwrap_or_else(|| timing.unix_secs()),
+        timing,
+    })
+}
+
+pub fn decode_migration_event(data: &[u8], signature: String, timing: EventTiming) -> Result<MigrationEvent> {
+    let mut offset = 8; // Skip discriminator
+
+    // CompletePumpAmmMigrationEvent: user, mint, mint_amount, sol_amount, pool_migration_fee, bonding_curve, timestamp, pool
+    let _user = read_pubkey(data, &mut offset, "user")?;
+    let mint = read_pubkey(data, &mut offset, "mint")?;
+    let mint_amount = read_u64(data, &mut offset, "mint_amount")?;
+    let sol_amount = read_u64(data, &mut offset, "sol_amount")?;
+    offset += 8; // Skip pool_migration_fee
+    offset += 32; // Skip bonding_curve
+    let timestamp = read_timestamp(data, offset);
+    offset += 8;
+    let pool = read_pubkey(data, &mut offset, "pool")?;
+
+    Ok(MigrationEvent {
+        mint: mint.to_string(),
+        pool: Some(pool.to_string()),
+        sol_amount: Some(sol_amount),
+        token_amount: Some(mint_amount),
+        signature,
+        platform: Platform::PumpFun,
+        timestamp: timestamp.unwrap_or_else(|| timing.unix_secs()),
+        timing,
+    })
+}
+
+fn read_pubkey(data: &[u8], offset: &mut usize, field: &str) -> Result<Pubkey> {
+    if *offset + 32 > data.len() { return Err(anyhow::anyhow!("Invalid data length for {}", field)); }
+    let bytes: [u8; 32] = data[*offset..*offset + 32].try_into()?;
+    *offset += 32;
+    Ok(Pubkey::new_from_array(bytes))
+}
+
+fn read_u64(data: &[u8], offset: &mut usize, field: &str) -> Result<u64> {
+    if *offset + 8 > data.len() { return Err(anyhow::anyhow!("Invalid data length for {}", field)); }
+    let bytes: [u8; 8] = data[*offset..*offset + 8].try_into()?;
+    *offset += 8;
+    Ok(u64::from_le_bytes(bytes))
+}
+
+// On-chain unix seconds (i64) at `offset`, if the event is long enough to carry one
+fn read_timestamp(data: &[u8], offset: usize) -> Option<u64> {
+    let bytes: [u8; 8] = data.get(offset..offset + 8)?.try_into().ok()?;
+    u64::try_from(i64::from_le_bytes(bytes)).ok().filter(|secs| *secs > 0)
 }
 
 fn mask_ws_url(url: &str) -> String {
@@ -1,39 +1,95 @@
 use anyhow::Result;
-use std::collections::HashSet;
-use tokio::time::{sleep, Duration, timeout, Instant};
+use std::collections::{HashMap, HashSet};
+use tokio::sync::mpsc;
+use tokio::time::{sleep, Duration, Instant};
 use tracing::{info, error, warn, debug};
 use serde_json::{json, Value};
 use tokio_tungstenite::{connect_async, tungstenite::protocol::Message};
 use futures_util::{SinkExt, StreamExt};
-use reqwest::Client;
 
-use crate::types::{RAYDIUM_PROGRAM_ID};
+use crate::block_clock::{now_ms, BlockClock};
+use crate::config::{Commitment, Config, RaydiumConfig};
+use crate::event_store::EventHub;
+use crate::metrics::metrics;
+use crate::rpc_scheduler::{Priority, RpcError, RpcScheduler};
+use crate::launchlab_curve::WSOL_MINT;
+use crate::ws_recording::{Frame, FrameRecorder, FrameSource};
+use crate::types::{
+    TokenLaunch, TradeEvent, MonitorEvent, Platform, EventTiming, RAYDIUM_LAUNCHPAD_AUTHORITY, RAYDIUM_PROGRAM_ID,
+    short_signature,
+};
 
-// ONLY BUY instructions discriminators
+// Swap instruction discriminators - sells are only fetched with `include_sells`
 const BUY_EXACT_IN_DISCRIMINATOR: [u8; 8] = [250, 234, 13, 123, 213, 156, 19, 236];
 const BUY_EXACT_OUT_DISCRIMINATOR: [u8; 8] = [24, 211, 116, 40, 105, 3, 153, 56];
+const SELL_EXACT_IN_DISCRIMINATOR: [u8; 8] = [149, 39, 222, 155, 211, 124, 152, 26];
+const SELL_EXACT_OUT_DISCRIMINATOR: [u8; 8] = [95, 200, 71, 34, 8, 9, 11, 166];
 
-// Raydium LaunchPad Authority
-const RAYDIUM_LAUNCHPAD_AUTHORITY: &str = "WLHv2UAZm6z4KyaaELi5pjdbJh6RESMva1Rnn8pJVVh";
+// A getTransaction lookup back from the scheduler
+struct FetchedTransaction {
+    signature: String,
+    received_at: Instant,
+    timing: EventTiming,
+    response: Result<Value, RpcError>,
+}
+
+/// Mints already announced, forgotten least recently traded first so an
+/// active pool is never announced as a launch again.
+#[derive(Default)]
+struct SeenMints {
+    last_traded: HashMap<String, u64>,
+    trades: u64,
+}
+
+impl SeenMints {
+    /// Refresh a known mint. A buy of an unknown one adds it and returns true.
+    fn touch(&mut self, mint: &str, is_buy: bool) -> bool {
+        self.trades += 1;
+        if let Some(last) = self.last_traded.get_mut(mint) {
+            *last = self.trades;
+            return false;
+        }
+        if is_buy {
+            self.last_traded.insert(mint.to_string(), self.trades);
+        }
+        is_buy
+    }
 
-// 🎯 OPTIMIZED SETTINGS - Less aggressive monitoring
-const FETCH_DELAY_MS: u64 = 800;  // Increased from 200ms to 800ms
-const REQUEST_TIMEOUT_SECS: u64 = 5;  // Reduced from 10s to 5s  
-const MIN_TIME_BETWEEN_FETCHES_MS: u64 = 500;  // Minimum time between any HTTP requests
-const MAX_PENDING_FETCHES: usize = 3;  // Limit concurrent fetches
+    /// Keep the `keep` most recently traded mints.
+    fn prune(&mut self, keep: usize) {
+        let mut by_recency: Vec<u64> = self.last_traded.values().copied().collect();
+        by_recency.sort_unstable_by(|a, b| b.cmp(a));
+        if let Some(&oldest_kept) = by_recency.get(keep.saturating_sub(1)) {
+            self.last_traded.retain(|_, last| *last >= oldest_kept);
+        }
+    }
+
+    fn len(&self) -> usize {
+        self.last_traded.len()
+    }
+}
 
 pub struct RaydiumBuyMonitor {
     ws_url: String,
-    rpc_url: String,
-    http_client: Client,
+    commitment: Commitment,
+    // 🎯 OPTIMIZED SETTINGS - timeouts and cache sizes from config
+    settings: RaydiumConfig,
+    rpc: RpcScheduler,
+    // Lookups run concurrently and report back here, in completion order
+    fetched_tx: mpsc::UnboundedSender<FetchedTransaction>,
+    fetched_rx: mpsc::UnboundedReceiver<FetchedTransaction>,
     processed_signatures: HashSet<String>,
-    seen_mints: HashSet<String>,
-    last_fetch_time: Option<Instant>,
-    pending_fetches: usize,
+    seen_mints: SeenMints,
+    events: EventHub,
+    block_clock: BlockClock,
+    recorder: Option<FrameRecorder>,
+    // getTransaction responses from a recording, by signature, taken as they're used - set while replaying
+    recorded_transactions: Option<HashMap<String, Value>>,
 }
 
 impl RaydiumBuyMonitor {
-    pub async fn new(ws_url: &str) -> Result<Self> {
+    pub async fn new(config: &Config, events: EventHub, block_clock: BlockClock) -> Result<Self> {
+        let ws_url = config.endpoints.ws_url.as_str();
         let ws_url = if ws_url.starts_with("wss://") || ws_url.starts_with("ws://") {
             ws_url.to_string()
         } else {
@@ -44,47 +100,72 @@ impl RaydiumBuyMonitor {
             }
         };
         
-        let rpc_url = ws_url
-            .replace("wss://", "https://")
-            .replace("ws://", "http://");
-        
-        let http_client = Client::builder()
-            .pool_max_idle_per_host(3)  // Reduced from 5 to 3
-            .timeout(Duration::from_secs(REQUEST_TIMEOUT_SECS))
-            .build()?;
+        let settings = config.raydium.clone();
+        let rpc = RpcScheduler::shared(config)?
+            .with_timeout(Duration::from_secs(settings.request_timeout_secs));
+        let (fetched_tx, fetched_rx) = mpsc::unbounded_channel();
         
         info!("🛒 Raydium LaunchPad BUY Monitor - OPTIMIZED for lower resource usage");
         info!("📍 Program: {}", RAYDIUM_PROGRAM_ID);
         info!("🔑 Authority: {}", RAYDIUM_LAUNCHPAD_AUTHORITY);
-        info!("⚡ Fetch delay: {}ms | Timeout: {}s | Max concurrent: {}", 
-              FETCH_DELAY_MS, REQUEST_TIMEOUT_SECS, MAX_PENDING_FETCHES);
+        info!("⚡ Timeout: {}s | RPC: {} req/s, {} in flight, {} queued", 
+              settings.request_timeout_secs, config.rpc.requests_per_sec, config.rpc.max_in_flight, config.rpc.max_queued);
         
         Ok(Self {
             ws_url,
-            rpc_url,
-            http_client,
+            commitment: config.commitment,
+            settings,
+            rpc,
+            fetched_tx,
+            fetched_rx,
             processed_signatures: HashSet::new(),
-            seen_mints: HashSet::new(),
-            last_fetch_time: None,
-            pending_fetches: 0,
+            seen_mints: SeenMints::default(),
+            events,
+            block_clock,
+            recorder: None,
+            recorded_transactions: None,
         })
     }
 
+    /// Write every WebSocket text frame and fetched transaction to a recording (see `--record`).
+    pub fn with_recorder(mut self, recorder: Option<FrameRecorder>) -> Self {
+        self.recorder = recorder;
+        self
+    }
+
+    /// Feed recorded frames through the same path as live ones, until the sender closes.
+    /// Transactions come from the recording: the ones fetched then are fetched now,
+    /// without RPC calls.
+    pub async fn replay(&mut self, mut frames: mpsc::Receiver<Frame>, transactions: HashMap<String, Value>) {
+        info!("⏪ Replaying recorded Raydium frames ({} recorded transactions)", transactions.len());
+        self.recorded_transactions = Some(transactions);
+        while let Some(frame) = frames.recv().await {
+            // Detected when it was recorded, not now
+            if let Err(e) = self.process_websocket_message(&frame.text, frame.at_ms).await {
+                metrics().decode_error(Platform::Raydium, &e);
+                warn!("⚠️ Error: {}", e);
+            }
+        }
+    }
+
     pub async fn start_monitoring(&mut self) -> Result<()> {
         info!("🚀 Starting Raydium LaunchPad BUY Monitor (Optimized)");
         
         loop {
-            match self.start_websocket_monitoring().await {
+            let result = self.start_websocket_monitoring().await;
+            metrics().ws_disconnected(Platform::Raydium);
+            
+            match result {
                 Ok(_) => {
                     info!("🔄 Reconnecting...");
                 },
                 Err(e) => {
                     error!("❌ WebSocket error: {}", e);
-                    sleep(Duration::from_secs(10)).await;  // Increased reconnect delay
+                    sleep(Duration::from_secs(self.settings.error_reconnect_delay_secs)).await;
                 }
             }
             
-            sleep(Duration::from_secs(2)).await;  // Increased from 1s to 2s
+            sleep(Duration::from_secs(self.settings.reconnect_delay_secs)).await;
         }
     }
 
@@ -104,7 +185,7 @@ impl RaydiumBuyMonitor {
                     "mentions": [RAYDIUM_PROGRAM_ID]
                 },
                 {
-                    "commitment": "confirmed"
+                    "commitment": self.commitment.as_str()
                 }
             ]
         });
@@ -112,22 +193,37 @@ impl RaydiumBuyMonitor {
         write.send(Message::Text(subscription_request.to_string())).await?;
         info!("✅ Subscribed to Raydium LaunchPad: {}", RAYDIUM_PROGRAM_ID);
         
-        while let Some(message) = read.next().await {
-            match message? {
-                Message::Text(text) => {
-                    if let Err(e) = self.process_websocket_message(&text).await {
-                        warn!("⚠️ Error: {}", e);
+        let connected_at = Instant::now();
+        metrics().ws_connected(Platform::Raydium);
+        
+        loop {
+            tokio::select! {
+                message = read.next() => {
+                    let Some(message) = message else { break };
+                    metrics().ws_uptime(Platform::Raydium, connected_at.elapsed());
+                    match message? {
+                        Message::Text(text) => {
+                            if let Some(recorder) = &self.recorder {
+                                recorder.record(FrameSource::Raydium, &text);
+                            }
+                            if let Err(e) = self.process_websocket_message(&text, now_ms()).await {
+                                metrics().decode_error(Platform::Raydium, &e);
+                                warn!("⚠️ Error: {}", e);
+                            }
+                        },
+                        Message::Close(_) => break,
+                        _ => {}
                     }
                 },
-                Message::Close(_) => break,
-                _ => {}
+                Some(fetched) = self.fetched_rx.recv() => self.handle_fetched(fetched).await,
             }
         }
         
         Ok(())
     }
 
-    async fn process_websocket_message(&mut self, message: &str) -> Result<()> {
+    async fn process_websocket_message(&mut self, message: &str, detected_at_ms: u64) -> Result<()> {
+        let received_at = Instant::now();
         let data: Value = serde_json::from_str(message)?;
         
         if let Some(result) = data.get("result") {
@@ -139,7 +235,15 @@ impl RaydiumBuyMonitor {
         
         if let Some(params) = data.get("params") {
             if let Some(result) = params.get("result") {
+                let slot = result.get("context").and_then(|c| c.get("slot")).and_then(|s| s.as_u64());
+                let timing = self.block_clock.timing_at(slot, detected_at_ms);
+                
                 if let Some(value) = result.get("value") {
+                    // Failed buys never minted anything - don't spend a fetch on them
+                    if value.get("err").is_some_and(|err| !err.is_null()) {
+                        return Ok(());
+                    }
+                    
                     if let Some(signature) = value.get("signature").and_then(|s| s.as_str()) {
                         if let Some(logs) = value.get("logs").and_then(|l| l.as_array()) {
                             
@@ -148,26 +252,33 @@ impl RaydiumBuyMonitor {
                             }
                             
                             // 🎯 OPTIMIZED: More strict filtering before processing
-                            if self.is_buy_transaction_optimized(logs) {
-                                // 🎯 THROTTLING: Check if we should process this transaction
-                                if self.should_process_transaction().await {
-                                    info!("🛒 Found BUY transaction: {}", signature);
-                                    self.fetch_and_extract_mint_throttled(signature).await;
+                            if self.is_swap_transaction_optimized(logs) {
+                                if let Some(response) = self.recorded_transaction(signature) {
+                                    // Replay: fetched when recorded, so fetched now
+                                    self.handle_transaction_response(response, signature, received_at, timing).await;
+                                    self.processed_signatures.insert(signature.to_string());
+                                } else if self.recorded_transactions.is_some() {
+                                    // Replay: throttled or failed when recorded
+                                    metrics().raydium_fetch("throttled");
                                     self.processed_signatures.insert(signature.to_string());
                                 } else {
-                                    debug!("⏸️ Skipping transaction due to throttling: {}", signature);
-                                    // Still mark as processed to avoid reprocessing
+                                    // 🎯 THROTTLING: the scheduler drops it when too many are queued
+                                    info!("🛒 Found swap transaction: {}", signature);
+                                    self.spawn_fetch(signature, received_at, timing);
+                                    // Marked either way - a throttled one isn't retried
                                     self.processed_signatures.insert(signature.to_string());
                                 }
                                 
                                 // Memory management - more aggressive cleanup
-                                if self.processed_signatures.len() > 300 {  // Reduced from 500
+                                if self.processed_signatures.len() > self.settings.max_processed_signatures {
+                                    let drop_count = self.processed_signatures.len().saturating_sub(self.settings.keep_processed_signatures);
                                     let recent: Vec<String> = self.processed_signatures
-                                        .iter().skip(150).cloned().collect();  // Keep only 150
+                                        .iter().skip(drop_count).cloned().collect();
                                     self.processed_signatures.clear();
                                     self.processed_signatures.extend(recent);
                                     debug!("🧹 Cleaned up processed signatures");
                                 }
+                                metrics().cache_size(Platform::Raydium, "processed_signatures", self.processed_signatures.len());
                             }
                         }
                     }
@@ -179,16 +290,19 @@ impl RaydiumBuyMonitor {
     }
 
     // 🎯 OPTIMIZED: More strict filtering to reduce false positives
-    fn is_buy_transaction_optimized(&self, logs: &[Value]) -> bool {
+    fn is_swap_transaction_optimized(&self, logs: &[Value]) -> bool {
         let mut has_buy_indicator = false;
         let mut has_raydium_invoke = false;
         
         for log in logs {
             if let Some(log_str) = log.as_str() {
-                // Look for BUY activity indicators
+                // Look for BUY (and, when enabled, SELL) activity indicators
                 if log_str.contains("buy_exact_in") || log_str.contains("buy_exact_out") {
                     has_buy_indicator = true;
                 }
+                if self.settings.include_sells && (log_str.contains("sell_exact_in") || log_str.contains("sell_exact_out")) {
+                    has_buy_indicator = true;
+                }
                 
                 // Verify it's actually Raydium program
                 if log_str.contains("invoke [1]") && log_str.contains(RAYDIUM_PROGRAM_ID) {
@@ -205,167 +319,313 @@ impl RaydiumBuyMonitor {
         false
     }
 
-    // 🎯 THROTTLING: Determine if we should process this transaction
-    async fn should_process_transaction(&mut self) -> bool {
-        // Check if too many pending fetches
-        if self.pending_fetches >= MAX_PENDING_FETCHES {
-            return false;
-        }
-        
-        // Check minimum time between fetches
-        if let Some(last_time) = self.last_fetch_time {
-            let elapsed = last_time.elapsed();
-            if elapsed < Duration::from_millis(MIN_TIME_BETWEEN_FETCHES_MS) {
-                return false;
-            }
+    // Each signature is handled once, so its response is freed as it's used
+    fn recorded_transaction(&mut self, signature: &str) -> Option<Value> {
+        self.recorded_transactions.as_mut()?.remove(signature)
+    }
+
+    // getTransaction doesn't accept "processed"
+    fn fetch_commitment(&self) -> Commitment {
+        match self.commitment {
+            Commitment::Finalized => Commitment::Finalized,
+            _ => Commitment::Confirmed,
         }
-        
-        true
     }
 
-    async fn fetch_and_extract_mint_throttled(&mut self, signature: &str) {
-        self.pending_fetches += 1;
-        self.last_fetch_time = Some(Instant::now());
-        
-        // Longer rate limiting delay
-        sleep(Duration::from_millis(FETCH_DELAY_MS)).await;
-        
-        let request = json!({
-            "jsonrpc": "2.0",
-            "id": 1,
-            "method": "getTransaction",
-            "params": [
-                signature,
-                {
-                    "encoding": "jsonParsed",
-                    "commitment": "confirmed",
-                    "maxSupportedTransactionVersion": 0
-                }
-            ]
+    fn spawn_fetch(&self, signature: &str, received_at: Instant, timing: EventTiming) {
+        let params = json!([
+            signature,
+            {
+                "encoding": "jsonParsed",
+                "commitment": self.fetch_commitment().as_str(),
+                "maxSupportedTransactionVersion": 0
+            }
+        ]);
+        let rpc = self.rpc.clone();
+        let fetched = self.fetched_tx.clone();
+        let signature = signature.to_string();
+        tokio::spawn(async move {
+            let response = rpc.call(Priority::Trade, "getTransaction", params).await;
+            let _ = fetched.send(FetchedTransaction { signature, received_at, timing, response });
         });
-        
-        let result = timeout(Duration::from_secs(REQUEST_TIMEOUT_SECS), 
-            self.http_client.post(&self.rpc_url).json(&request).send()
-        ).await;
-        
-        match result {
-            Ok(Ok(response)) => {
-                if let Ok(response_json) = response.json::<Value>().await {
-                    if let Some(result) = response_json.get("result") {
-                        if !result.is_null() {
-                            self.extract_mint_from_buy_transaction(result, signature).await;
-                        }
-                    } else if let Some(error) = response_json.get("error") {
-                        if let Some(code) = error.get("code").and_then(|c| c.as_i64()) {
-                            if code == 429 {
-                                warn!("⚠️ Rate limited - increasing delay");
-                                sleep(Duration::from_secs(5)).await;  // Longer penalty
-                            }
-                        }
-                    }
+    }
+
+    async fn handle_fetched(&mut self, fetched: FetchedTransaction) {
+        let FetchedTransaction { signature, received_at, timing, response } = fetched;
+        match response {
+            Ok(response_json) => {
+                if let Some(recorder) = &self.recorder {
+                    recorder.record_transaction(&signature, &response_json.to_string());
                 }
+                self.handle_transaction_response(response_json, &signature, received_at, timing).await;
+            },
+            Err(e) => {
+                metrics().raydium_fetch(match e {
+                    RpcError::Busy => "throttled",
+                    RpcError::RateLimited => "rate_limited",
+                    RpcError::Timeout => "timeout",
+                    RpcError::Http(_) => "http_error",
+                    RpcError::InvalidResponse(_) => "invalid_response",
+                });
+                debug!("⚠️ Failed to fetch transaction {}: {}", signature, e);
             },
-            _ => {
-                debug!("⚠️ Failed to fetch transaction {}", signature);
-            }
         }
-        
-        self.pending_fetches = self.pending_fetches.saturating_sub(1);
     }
 
-    async fn extract_mint_from_buy_transaction(&mut self, transaction_data: &Value, signature: &str) {
-        if let Some(transaction) = transaction_data.get("transaction") {
-            if let Some(message) = transaction.get("message") {
-                if let Some(instructions) = message.get("instructions").and_then(|i| i.as_array()) {
-                    
-                    // First, verify this is a BUY transaction
-                    let mut is_buy = false;
-                    
-                    for instruction in instructions {
-                        if let Some(program_id) = instruction.get("programId").and_then(|p| p.as_str()) {
-                            if program_id == RAYDIUM_PROGRAM_ID {
-                                if let Some(data) = instruction.get("data").and_then(|d| d.as_str()) {
-                                    if self.is_buy_instruction(data) {
-                                        is_buy = true;
-                                        debug!("✅ Confirmed BUY instruction in {}", signature);
-                                        break;
-                                    }
-                                }
-                            }
-                        }
-                    }
-                    
-                    if !is_buy {
-                        debug!("❌ Not a BUY transaction: {}", signature);
-                        return;
-                    }
-                    
-                    // Now find the mint from transferChecked with LaunchPad Authority
-                    for instruction in instructions {
-                        if let Some(program) = instruction.get("program").and_then(|p| p.as_str()) {
-                            if program == "spl-token" {
-                                if let Some(parsed) = instruction.get("parsed") {
-                                    if let Some(instruction_type) = parsed.get("type").and_then(|t| t.as_str()) {
-                                        if instruction_type == "transferChecked" {
-                                            if let Some(info) = parsed.get("info") {
-                                                if let Some(authority) = info.get("authority").and_then(|a| a.as_str()) {
-                                                    if authority == RAYDIUM_LAUNCHPAD_AUTHORITY {
-                                                        if let Some(mint) = info.get("mint").and_then(|m| m.as_str()) {
-                                                            self.handle_buy_mint(mint, signature).await;
-                                                            return; // Found the mint, stop looking
-                                                        }
-                                                    }
-                                                }
-                                            }
-                                        }
-                                    }
-                                }
-                            }
-                        }
-                    }
-                    
-                    debug!("⚠️ No LaunchPad transferChecked found in BUY transaction: {}", signature);
+    async fn handle_transaction_response(&mut self, response_json: Value, signature: &str, received_at: Instant, mut timing: EventTiming) {
+        if let Some(result) = response_json.get("result") {
+            if !result.is_null() {
+                metrics().raydium_fetch("success");
+                // No slot clock anchor yet - fall back to the transaction's own block time
+                // The fetched transaction is at least this final
+                timing.status = self.fetch_commitment().into();
+                if timing.block_time_ms.is_none() {
+                    timing.block_time_ms = result.get("blockTime").and_then(|t| t.as_u64()).map(|t| t * 1000);
                 }
+                self.extract_swap(result, signature, received_at, timing).await;
+            } else {
+                metrics().raydium_fetch("not_found");
             }
+        } else if response_json.get("error").is_some() {
+            // 429s were already retried by the scheduler
+            metrics().raydium_fetch("rpc_error");
         }
     }
 
-    fn is_buy_instruction(&self, data: &str) -> bool {
-        use base64::{Engine as _, engine::general_purpose};
-        
-        if let Ok(decoded_data) = general_purpose::STANDARD.decode(data) {
-            if decoded_data.len() >= 8 {
-                let discriminator = &decoded_data[0..8];
-                return discriminator == BUY_EXACT_IN_DISCRIMINATOR || 
-                       discriminator == BUY_EXACT_OUT_DISCRIMINATOR;
-            }
+    async fn extract_swap(&mut self, transaction_data: &Value, signature: &str, received_at: Instant, timing: EventTiming) {
+        match decode_swap(transaction_data) {
+            Some(swap) if swap.is_buy || self.settings.include_sells => {
+                self.handle_swap(swap, signature, received_at, timing).await;
+            },
+            Some(_) => debug!("⏭️ Skipping SELL transaction: {}", signature),
+            None => debug!("⚠️ No LaunchPad swap found in transaction: {}", signature),
         }
-        false
     }
 
-    async fn handle_buy_mint(&mut self, mint_address: &str, signature: &str) {
-        // 🎯 MAIN REQUIREMENT: Print CA to console
-        println!("CA: {}", mint_address);
-        
-        let is_new_mint = !self.seen_mints.contains(mint_address);
-        if is_new_mint {
-            self.seen_mints.insert(mint_address.to_string());
-        }
+    async fn handle_swap(&mut self, swap: LaunchLabSwap, signature: &str, received_at: Instant, timing: EventTiming) {
+        let mint_address = swap.mint.as_str();
+        // A sell can't be the first trade - only buys double as launch detection
+        let is_new_mint = self.seen_mints.touch(mint_address, swap.is_buy);
         
         info!(
-            "🛒 [BUY{}] Mint: {} | TX: {}", 
+            "🛒 [{}{}] Mint: {} | Trader: {} | TX: {}", 
+            if swap.is_buy { "BUY" } else { "SELL" },
             if is_new_mint { " - NEW" } else { "" },
             mint_address,
-            &signature[..8]
+            swap.trader.as_deref().unwrap_or("Unknown"),
+            short_signature(signature)
         );
         
+        let timestamp = timing.unix_secs();
+        
+        // First buy we see for a mint doubles as its launch detection
+        if is_new_mint {
+            self.events.publish(MonitorEvent::Launch(TokenLaunch {
+                contract_address: mint_address.to_string(),
+                name: None,
+                symbol: None,
+                creator: None,
+                uri: None,
+                signature: signature.to_string(),
+                platform: Platform::Raydium,
+                timestamp,
+                timing: timing.clone(),
+            }));
+        }
+        
+        self.events.publish(MonitorEvent::Trade(TradeEvent {
+            mint: swap.mint,
+            trader: swap.trader,
+            is_buy: swap.is_buy,
+            sol_amount: swap.sol_amount,
+            token_amount: swap.token_amount,
+            signature: signature.to_string(),
+            platform: Platform::Raydium,
+            timestamp,
+            timing,
+        }));
+        metrics().emit_latency(Platform::Raydium, received_at.elapsed());
+        
         // More aggressive memory management for seen_mints
-        if self.seen_mints.len() > 2000 {  // Reduced from 3000
-            let recent: Vec<String> = self.seen_mints
-                .iter().skip(1000).cloned().collect();  // Keep only 1000
-            self.seen_mints.clear();
-            self.seen_mints.extend(recent);
+        if self.seen_mints.len() > self.settings.max_seen_mints {
+            self.seen_mints.prune(self.settings.keep_seen_mints);
             debug!("🧹 Cleaned up seen mints cache");
         }
+        metrics().cache_size(Platform::Raydium, "seen_mints", self.seen_mints.len());
+    }
+}
+// ========================================================================
+// SWAP DECODING
+// ========================================================================
+
+/// A LaunchLab buy or sell, read from a `jsonParsed` transaction.
+#[derive(Debug, Clone, PartialEq)]
+pub struct LaunchLabSwap {
+    pub mint: String,
+    pub trader: Option<String>,
+    pub is_buy: bool,
+    pub sol_amount: Option<u64>,
+    pub token_amount: Option<u64>,
+}
+
+// One transferChecked leg of the swap
+struct TokenTransfer<'a> {
+    mint: &'a str,
+    authority: &'a str,
+    amount: Option<u64>,
+}
+
+/// Side from the LaunchLab instruction, mint/amounts/trader from its token transfers.
+/// Buys: the vault authority sends the token and the trader sends WSOL; sells the reverse.
+pub fn decode_swap(transaction_data: &Value) -> Option<LaunchLabSwap> {
+    let message = transaction_data.get("transaction")?.get("message")?;
+    let instructions = message.get("instructions")?.as_array()?;
+
+    let is_buy = instructions.iter()
+        .filter(|instruction| instruction.get("programId").and_then(|p| p.as_str()) == Some(RAYDIUM_PROGRAM_ID))
+        .filter_map(|instruction| instruction.get("data").and_then(|d| d.as_str()))
+        .find_map(swap_side)?;
+
+    // The program moves tokens via CPI, so the transfers are usually inner instructions
+    let inner = transaction_data.get("meta")
+        .and_then(|meta| meta.get("innerInstructions"))
+        .and_then(|inner| inner.as_array())
+        .into_iter()
+        .flatten()
+        .filter_map(|group| group.get("instructions").and_then(|i| i.as_array()))
+        .flatten();
+    let transfers: Vec<TokenTransfer> = instructions.iter().chain(inner).filter_map(token_transfer).collect();
+
+    let from_vault = |transfer: &&TokenTransfer| transfer.authority == RAYDIUM_LAUNCHPAD_AUTHORITY;
+    let token_leg = transfers.iter()
+        .filter(|transfer| transfer.mint != WSOL_MINT)
+        .find(|transfer| from_vault(transfer) == is_buy)?;
+    let sol_leg = transfers.iter()
+        .filter(|transfer| transfer.mint == WSOL_MINT)
+        .find(|transfer| from_vault(transfer) != is_buy);
+
+    let trader_leg = if is_buy { sol_leg } else { Some(token_leg) };
+    let trader = trader_leg.map(|transfer| transfer.authority.to_string()).or_else(|| fee_payer(message));
+
+    Some(LaunchLabSwap {
+        mint: token_leg.mint.to_string(),
+        trader,
+        is_buy,
+        sol_amount: sol_leg.and_then(|transfer| transfer.amount),
+        token_amount: token_leg.amount,
+    })
+}
+
+// jsonParsed leaves unknown programs' data base58-encoded; accept base64 as well
+fn swap_side(data: &str) -> Option<bool> {
+    use base64::{Engine as _, engine::general_purpose};
+
+    let decoded = solana_sdk::bs58::decode(data).into_vec().ok()
+        .or_else(|| general_purpose::STANDARD.decode(data).ok())?;
+    let discriminator: [u8; 8] = decoded.get(..8)?.try_into().ok()?;
+    match discriminator {
+        BUY_EXACT_IN_DISCRIMINATOR | BUY_EXACT_OUT_DISCRIMINATOR => Some(true),
+        SELL_EXACT_IN_DISCRIMINATOR | SELL_EXACT_OUT_DISCRIMINATOR => Some(false),
+        _ => None,
+    }
+}
+
+fn token_transfer(instruction: &Value) -> Option<TokenTransfer<'_>> {
+    let program = instruction.get("program")?.as_str()?;
+    if program != "spl-token" && program != "spl-token-2022" {
+        return None;
+    }
+    let parsed = instruction.get("parsed")?;
+    if parsed.get("type")?.as_str()? != "transferChecked" {
+        return None;
     }
-} 
\ No newline at end of file
+    let info = parsed.get("info")?;
+    Some(TokenTransfer {
+        mint: info.get("mint")?.as_str()?,
+        authority: info.get("authority")?.as_str()?,
+        amount: info.get("tokenAmount")
+            .and_then(|amount| amount.get("amount"))
+            .and_then(|amount| amount.as_str())
+            .and_then(|amount| amount.parse().ok()),
+    })
+}
+
+fn fee_payer(message: &Value) -> Option<String> {
+    let first = message.get("accountKeys")?.as_array()?.first()?;
+    first.get("pubkey").unwrap_or(first).as_str().map(str::to_string)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const MINT: &str = "So1ana1111111111111111111111111111111111111";
+    const TRADER: &str = "Trader1111111111111111111111111111111111111";
+
+    fn transfer(mint: &str, authority: &str, amount: u64) -> Value {
+        json!({
+            "program": "spl-token",
+            "parsed": {
+                "type": "transferChecked",
+                "info": { "mint": mint, "authority": authority, "tokenAmount": { "amount": amount.to_string() } }
+            }
+        })
+    }
+
+    fn transaction(discriminator: [u8; 8], transfers: Vec<Value>) -> Value {
+        json!({
+            "transaction": { "message": {
+                "accountKeys": [{ "pubkey": TRADER, "signer": true }],
+                "instructions": [{ "programId": RAYDIUM_PROGRAM_ID, "data": solana_sdk::bs58::encode(discriminator).into_string() }]
+            }},
+            "meta": { "innerInstructions": [{ "index": 0, "instructions": transfers }] }
+        })
+    }
+
+    #[test]
+    fn seen_mints_forget_the_least_recently_traded() {
+        let mut seen = SeenMints::default();
+        assert!(!seen.touch("sold", false));
+        assert!(seen.touch("old", true));
+        assert!(seen.touch("active", true));
+        assert!(seen.touch("newer", true));
+        assert!(!seen.touch("active", true));
+        assert!(!seen.touch("old", false));
+
+        seen.prune(2);
+        assert_eq!(seen.len(), 2);
+        // The first seen but still trading stay - "newer" hasn't traded since its launch
+        assert!(!seen.touch("old", true));
+        assert!(!seen.touch("active", true));
+        assert!(seen.touch("newer", true));
+    }
+
+    #[test]
+    fn decodes_buy_from_inner_transfers() {
+        let tx = transaction(BUY_EXACT_IN_DISCRIMINATOR, vec![
+            transfer(WSOL_MINT, TRADER, 500_000_000),
+            transfer(MINT, RAYDIUM_LAUNCHPAD_AUTHORITY, 12_345),
+        ]);
+        assert_eq!(decode_swap(&tx), Some(LaunchLabSwap {
+            mint: MINT.to_string(),
+            trader: Some(TRADER.to_string()),
+            is_buy: true,
+            sol_amount: Some(500_000_000),
+            token_amount: Some(12_345),
+        }));
+    }
+
+    #[test]
+    fn decodes_sell_and_ignores_other_programs() {
+        let tx = transaction(SELL_EXACT_OUT_DISCRIMINATOR, vec![
+            transfer(MINT, TRADER, 9_000),
+            transfer(WSOL_MINT, RAYDIUM_LAUNCHPAD_AUTHORITY, 100_000),
+        ]);
+        let swap = decode_swap(&tx).unwrap();
+        assert!(!swap.is_buy);
+        assert_eq!((swap.token_amount, swap.sol_amount), (Some(9_000), Some(100_000)));
+        assert_eq!(swap.trader.as_deref(), Some(TRADER));
+
+        assert_eq!(decode_swap(&transaction([0; 8], vec![transfer(MINT, TRADER, 1)])), None);
+    }
+}
@@ -0,0 +1,542 @@
+use anyhow::anyhow;
+use reqwest::{header::RETRY_AFTER, Client, StatusCode};
+use serde_json::{json, Value};
+use std::collections::{BTreeSet, HashMap};
+use std::sync::{Arc, LazyLock, Mutex, MutexGuard, Weak};
+use tokio::sync::{oneshot, Notify};
+use tokio::time::{sleep, Duration, Instant};
+use tracing::{debug, warn};
+
+use crate::config::{Config, RpcConfig};
+use crate::metrics::metrics;
+
+// After a 429 the rate drops to half, but never below this share of the configured rate...
+const MIN_RATE_FRACTION: f64 = 0.1;
+// ...and climbs back by this share of it with every successful request
+const RECOVERY_FRACTION: f64 = 0.05;
+
+/// Queue order - a waiting request of a higher priority always goes first.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum Priority {
+    /// Whatever a fresh launch's events wait on: identity, curve accounts, bundle funding, commitment, block times
+    Launch,
+    /// Raydium swap lookups
+    Trade,
+    /// Holder snapshots and backtest history
+    Background,
+}
+
+impl Priority {
+    pub fn label(&self) -> &'static str {
+        match self {
+            Priority::Launch => "launch",
+            Priority::Trade => "trade",
+            Priority::Background => "background",
+        }
+    }
+}
+
+/// Why a call produced no JSON-RPC response. Cloned to every coalesced caller.
+#[derive(Debug, Clone, PartialEq)]
+pub enum RpcError {
+    /// The priority's queue was full - nothing was sent
+    Busy,
+    /// Still rate limited after every retry
+    RateLimited,
+    Timeout,
+    /// Transport errors and unexpected HTTP statuses
+    Http(String),
+    /// A body that isn't a JSON-RPC response
+    InvalidResponse(String),
+}
+
+impl std::fmt::Display for RpcError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            RpcError::Busy => write!(f, "RPC queue full"),
+            RpcError::RateLimited => write!(f, "rate limited (HTTP 429)"),
+            RpcError::Timeout => write!(f, "request timed out"),
+            RpcError::Http(e) => write!(f, "HTTP error: {}", e),
+            RpcError::InvalidResponse(e) => write!(f, "invalid response: {}", e),
+        }
+    }
+}
+
+impl std::error::Error for RpcError {}
+
+// What one HTTP attempt came back with
+enum Attempt {
+    RateLimited(Option<Duration>),
+    // 5xx and transport errors - worth another try
+    Transient(RpcError),
+    Failed(RpcError),
+}
+
+// ========================================================================
+// ENDPOINT STATE
+// ========================================================================
+
+type Waiters = Vec<oneshot::Sender<Result<Value, RpcError>>>;
+
+struct State {
+    // (priority, arrival) - the first entry is the only one allowed to go
+    queue: BTreeSet<(Priority, u64)>,
+    next_ticket: u64,
+    tokens: f64,
+    refilled_at: Instant,
+    // Adaptive - the configured rate until a 429, then halved and slowly restored
+    rate: f64,
+    paused_until: Option<Instant>,
+    rate_limit_streak: u32,
+    in_flight: usize,
+    // Identical requests queued or in flight -> callers waiting on the first one
+    coalesced: HashMap<String, Waiters>,
+}
+
+/// One RPC URL: its token bucket, queue and in-flight count.
+struct Endpoint {
+    url: String,
+    http_client: Client,
+    settings: RpcConfig,
+    state: Mutex<State>,
+    changed: Notify,
+}
+
+// Waiting ticket - leaves the queue when admitted or dropped
+struct Queued {
+    endpoint: Arc<Endpoint>,
+    key: (Priority, u64),
+}
+
+impl Drop for Queued {
+    fn drop(&mut self) {
+        let mut state = self.endpoint.lock_state();
+        if state.queue.remove(&self.key) {
+            metrics().rpc_queued(self.key.0, state.queue.iter().filter(|(priority, _)| *priority == self.key.0).count());
+            drop(state);
+            self.endpoint.changed.notify_waiters();
+        }
+    }
+}
+
+// Admitted request - frees its in-flight slot when dropped
+struct InFlight {
+    endpoint: Arc<Endpoint>,
+}
+
+impl Drop for InFlight {
+    fn drop(&mut self) {
+        let mut state = self.endpoint.lock_state();
+        state.in_flight -= 1;
+        metrics().rpc_in_flight(state.in_flight);
+        drop(state);
+        self.endpoint.changed.notify_waiters();
+    }
+}
+
+// First caller of a coalesced request - hands its result to the others
+struct Leader {
+    endpoint: Arc<Endpoint>,
+    // Taken by `finish`
+    key: Option<String>,
+}
+
+impl Leader {
+    fn finish(mut self, result: &Result<Value, RpcError>) {
+        let Some(key) = self.key.take() else { return };
+        let waiters = self.endpoint.lock_state().coalesced.remove(&key).unwrap_or_default();
+        for waiter in waiters {
+            let _ = waiter.send(result.clone());
+        }
+    }
+}
+
+impl Drop for Leader {
+    // Cancelled before finishing - followers see their sender dropped
+    fn drop(&mut self) {
+        if let Some(key) = self.key.take() {
+            self.endpoint.lock_state().coalesced.remove(&key);
+        }
+    }
+}
+
+impl Endpoint {
+    fn new(url: &str, settings: &RpcConfig) -> anyhow::Result<Self> {
+        let http_client = Client::builder()
+            .pool_max_idle_per_host(settings.max_in_flight.max(1))
+            .build()?;
+        let rate = settings.requests_per_sec.max(0.01);
+
+        Ok(Self {
+            url: url.to_string(),
+            http_client,
+            settings: settings.clone(),
+            state: Mutex::new(State {
+                queue: BTreeSet::new(),
+                next_ticket: 0,
+                tokens: settings.burst.max(1) as f64,
+                refilled_at: Instant::now(),
+                rate,
+                paused_until: None,
+                rate_limit_streak: 0,
+                in_flight: 0,
+                coalesced: HashMap::new(),
+            }),
+            changed: Notify::new(),
+        })
+    }
+
+    fn lock_state(&self) -> MutexGuard<'_, State> {
+        self.state.lock().unwrap_or_else(|poisoned| poisoned.into_inner())
+    }
+
+    fn configured_rate(&self) -> f64 {
+        self.settings.requests_per_sec.max(0.01)
+    }
+
+    /// A place in line, or `Busy` when the priority already has `max_queued` waiting.
+    fn enqueue(self: &Arc<Self>, priority: Priority) -> Result<Queued, RpcError> {
+        let mut state = self.lock_state();
+        let waiting = state.queue.iter().filter(|(queued, _)| *queued == priority).count();
+        if waiting >= self.settings.max_queued {
+            return Err(RpcError::Busy);
+        }
+        let ticket = state.next_ticket;
+        state.next_ticket += 1;
+        state.queue.insert((priority, ticket));
+        metrics().rpc_queued(priority, waiting + 1);
+        Ok(Queued { endpoint: self.clone(), key: (priority, ticket) })
+    }
+
+    /// Back in line after a 429, ahead of everything of its priority that arrived later.
+    fn requeue(self: &Arc<Self>, key: (Priority, u64)) -> Queued {
+        let mut state = self.lock_state();
+        state.queue.insert(key);
+        metrics().rpc_queued(key.0, state.queue.iter().filter(|(priority, _)| *priority == key.0).count());
+        Queued { endpoint: self.clone(), key }
+    }
+
+    /// Wait until `queued` is first in line, a token is available, the endpoint isn't
+    /// backing off and there's an in-flight slot.
+    async fn admit(self: &Arc<Self>, queued: Queued) -> InFlight {
+        loop {
+            // Registered before checking, so a change in between still wakes us
+            let changed = self.changed.notified();
+            let wait = {
+                let mut state = self.lock_state();
+                let now = Instant::now();
+                let elapsed = now.duration_since(state.refilled_at).as_secs_f64();
+                state.tokens = (state.tokens + elapsed * state.rate).min(self.settings.burst.max(1) as f64);
+                state.refilled_at = now;
+
+                if state.queue.first() != Some(&queued.key) || state.in_flight >= self.settings.max_in_flight.max(1) {
+                    None
+                } else if let Some(until) = state.paused_until.filter(|until| *until > now) {
+                    Some(until - now)
+                } else if state.tokens < 1.0 {
+                    Some(Duration::from_secs_f64((1.0 - state.tokens) / state.rate))
+                } else {
+                    state.tokens -= 1.0;
+                    state.in_flight += 1;
+                    metrics().rpc_in_flight(state.in_flight);
+                    drop(state);
+                    // Leaving the queue lets the next request in line try
+                    drop(queued);
+                    return InFlight { endpoint: self.clone() };
+                }
+            };
+
+            match wait {
+                Some(wait) => tokio::select! {
+                    _ = changed => {},
+                    _ = sleep(wait) => {},
+                },
+                None => changed.await,
+            }
+        }
+    }
+
+    fn succeeded(&self) {
+        let mut state = self.lock_state();
+        state.rate_limit_streak = 0;
+        let configured = self.configured_rate();
+        if state.rate < configured {
+            state.rate = (state.rate + configured * RECOVERY_FRACTION).min(configured);
+            metrics().rpc_rate(state.rate);
+        }
+    }
+
+    /// Halve the rate and pause the whole endpoint - for `Retry-After` when given,
+    /// otherwise a backoff that doubles with every 429 in a row.
+    fn rate_limited(&self, retry_after: Option<Duration>) {
+        let mut state = self.lock_state();
+        let backoff = Duration::from_millis(self.settings.backoff_ms)
+            .saturating_mul(1 << state.rate_limit_streak.min(16))
+            .min(Duration::from_secs(self.settings.max_backoff_secs));
+        let pause = retry_after.unwrap_or(backoff);
+        state.rate_limit_streak += 1;
+        state.rate = (state.rate / 2.0).max(self.configured_rate() * MIN_RATE_FRACTION);
+        // One probe when the pause ends, then the lower rate
+        state.tokens = state.tokens.min(1.0);
+        let until = Instant::now() + pause;
+        state.paused_until = Some(state.paused_until.map_or(until, |paused| paused.max(until)));
+        metrics().rpc_rate(state.rate);
+        warn!("⚠️ RPC rate limited - pausing {:?}, now {:.1} req/s", pause, state.rate);
+    }
+
+    async fn send(&self, body: &Value, timeout: Duration) -> Result<Value, Attempt> {
+        let response = self.http_client.post(&self.url).json(body).timeout(timeout).send().await
+            .map_err(|e| Attempt::Transient(transport_error(e)))?;
+        let status = response.status();
+        if status == StatusCode::TOO_MANY_REQUESTS {
+            return Err(Attempt::RateLimited(retry_after(&response)));
+        }
+        if status.is_server_error() {
+            return Err(Attempt::Transient(RpcError::Http(format!("HTTP {}", status))));
+        }
+        if !status.is_success() {
+            return Err(Attempt::Failed(RpcError::Http(format!("HTTP {}", status))));
+        }
+
+        let text = response.text().await.map_err(|e| Attempt::Transient(transport_error(e)))?;
+        let response: Value = serde_json::from_str(&text)
+            .map_err(|e| Attempt::Failed(RpcError::InvalidResponse(e.to_string())))?;
+        // Some providers answer 200 with a JSON-RPC 429
+        if response.get("error").and_then(|e| e.get("code")).and_then(|c| c.as_i64()) == Some(429) {
+            return Err(Attempt::RateLimited(None));
+        }
+        Ok(response)
+    }
+}
+
+fn transport_error(error: reqwest::Error) -> RpcError {
+    if error.is_timeout() {
+        RpcError::Timeout
+    } else {
+        RpcError::Http(error.to_string())
+    }
+}
+
+// Only the delay-seconds form - HTTP dates fall back to our own backoff
+fn retry_after(response: &reqwest::Response) -> Option<Duration> {
+    let seconds: u64 = response.headers().get(RETRY_AFTER)?.to_str().ok()?.trim().parse().ok()?;
+    Some(Duration::from_secs(seconds))
+}
+
+// ========================================================================
+// SCHEDULER
+// ========================================================================
+
+// One endpoint per URL while anything still holds it
+static ENDPOINTS: LazyLock<Mutex<HashMap<String, Weak<Endpoint>>>> = LazyLock::new(Default::default);
+
+/// Every HTTP JSON-RPC call goes through here: a token bucket and in-flight limit per
+/// endpoint, a priority queue, adaptive backoff on 429s, and identical requests that
+/// are already queued or in flight answered once.
+///
+/// Handles are cheap to clone. Timeout and retries are per handle; everything else is
+/// shared by every handle for the URL, with the `[rpc]` settings of the first one.
+#[derive(Clone)]
+pub struct RpcScheduler {
+    endpoint: Arc<Endpoint>,
+    timeout: Duration,
+    max_retries: u32,
+}
+
+impl RpcScheduler {
+    /// The scheduler for `config.rpc_url()`, shared with every other subsystem using it.
+    pub fn shared(config: &Config) -> anyhow::Result<Self> {
+        let url = config.rpc_url();
+        let mut endpoints = ENDPOINTS.lock().unwrap_or_else(|poisoned| poisoned.into_inner());
+        let endpoint = match endpoints.get(&url).and_then(Weak::upgrade) {
+            Some(endpoint) => endpoint,
+            None => {
+                let endpoint = Arc::new(Endpoint::new(&url, &config.rpc)?);
+                endpoints.retain(|_, endpoint| endpoint.strong_count() > 0);
+                endpoints.insert(url, Arc::downgrade(&endpoint));
+                metrics().rpc_rate(endpoint.configured_rate());
+                endpoint
+            },
+        };
+
+        Ok(Self {
+            endpoint,
+            timeout: Duration::from_secs(config.rpc.request_timeout_secs),
+            max_retries: config.rpc.max_retries,
+        })
+    }
+
+    pub fn with_timeout(mut self, timeout: Duration) -> Self {
+        self.timeout = timeout;
+        self
+    }
+
+    /// Attempts after the first on 429s, 5xx and transport errors.
+    pub fn with_retries(mut self, max_retries: u32) -> Self {
+        self.max_retries = max_retries;
+        self
+    }
+
+    /// The whole JSON-RPC response. JSON-RPC errors other than 429 are left for the caller.
+    pub async fn call(&self, priority: Priority, method: &str, params: Value) -> Result<Value, RpcError> {
+        let key = format!("{}:{}", method, params);
+        let following = {
+            let mut state = self.endpoint.lock_state();
+            match state.coalesced.get_mut(&key) {
+                Some(waiters) => {
+                    let (tx, rx) = oneshot::channel();
+                    waiters.push(tx);
+                    Some(rx)
+                },
+                None => {
+                    state.coalesced.insert(key.clone(), Vec::new());
+                    None
+                },
+            }
+        };
+        if let Some(result) = following {
+            metrics().rpc_request(priority, "coalesced");
+            return result.await.unwrap_or_else(|_| Err(RpcError::Http("coalesced request was cancelled".to_string())));
+        }
+        let leader = Leader { endpoint: self.endpoint.clone(), key: Some(key) };
+
+        let request = json!({ "jsonrpc": "2.0", "id": 1, "method": method, "params": params });
+        let result = self.execute(priority, method, &request).await;
+        leader.finish(&result);
+        result
+    }
+
+    /// `result` of the call, with JSON-RPC errors as errors.
+    pub async fn result(&self, priority: Priority, method: &str, params: Value) -> anyhow::Result<Value> {
+        let response = self.call(priority, method, params).await?;
+        if let Some(error) = response.get("error") {
+            return Err(anyhow!("RPC error: {}", error));
+        }
+        response.get("result").cloned().ok_or_else(|| anyhow!("missing result"))
+    }
+
+    async fn execute(&self, priority: Priority, method: &str, request: &Value) -> Result<Value, RpcError> {
+        let endpoint = &self.endpoint;
+        let mut queued = endpoint.enqueue(priority).inspect_err(|_| metrics().rpc_request(priority, "busy"))?;
+        let mut backoff = Duration::from_millis(endpoint.settings.backoff_ms);
+        let mut attempt = 0;
+        loop {
+            let key = queued.key;
+            let in_flight = endpoint.admit(queued).await;
+            let outcome = endpoint.send(request, self.timeout).await;
+            drop(in_flight);
+
+            let error = match outcome {
+                Ok(response) => {
+                    endpoint.succeeded();
+                    metrics().rpc_request(priority, "success");
+                    return Ok(response);
+                },
+                Err(Attempt::RateLimited(retry_after)) => {
+                    metrics().rpc_request(priority, "rate_limited");
+                    endpoint.rate_limited(retry_after);
+                    RpcError::RateLimited
+                },
+                Err(Attempt::Transient(error)) => {
+                    metrics().rpc_request(priority, if error == RpcError::Timeout { "timeout" } else { "http_error" });
+                    if attempt < self.max_retries {
+                        debug!("⏳ {} failed ({}), retrying in {:?}", method, error, backoff);
+                        sleep(backoff).await;
+                        backoff *= 2;
+                    }
+                    error
+                },
+                Err(Attempt::Failed(error)) => {
+                    metrics().rpc_request(priority, "invalid_response");
+                    return Err(error);
+                },
+            };
+
+            if attempt >= self.max_retries {
+                return Err(error);
+            }
+            attempt += 1;
+            queued = endpoint.requeue(key);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn endpoint(settings: RpcConfig) -> Arc<Endpoint> {
+        Arc::new(Endpoint::new("http://127.0.0.1:1/", &settings).unwrap())
+    }
+
+    #[tokio::test]
+    async fn admits_by_priority_then_arrival() {
+        let endpoint = endpoint(RpcConfig { max_in_flight: 1, ..RpcConfig::default() });
+        let blocker = endpoint.admit(endpoint.enqueue(Priority::Launch).unwrap()).await;
+
+        let (order_tx, mut order) = tokio::sync::mpsc::unbounded_channel();
+        for (priority, name) in [(Priority::Background, "history"), (Priority::Trade, "swap"), (Priority::Launch, "identity"), (Priority::Trade, "swap 2")] {
+            let queued = endpoint.enqueue(priority).unwrap();
+            let (endpoint, order_tx) = (endpoint.clone(), order_tx.clone());
+            tokio::spawn(async move {
+                let _in_flight = endpoint.admit(queued).await;
+                order_tx.send(name).unwrap();
+            });
+        }
+        drop(blocker);
+
+        let mut admitted = Vec::new();
+        for _ in 0..4 {
+            admitted.push(order.recv().await.unwrap());
+        }
+        assert_eq!(admitted, ["identity", "swap", "swap 2", "history"]);
+    }
+
+    #[tokio::test]
+    async fn rejects_past_max_queued_per_priority() {
+        let endpoint = endpoint(RpcConfig { max_queued: 2, ..RpcConfig::default() });
+        let first = endpoint.enqueue(Priority::Trade).unwrap();
+        let _second = endpoint.enqueue(Priority::Trade).unwrap();
+        assert!(matches!(endpoint.enqueue(Priority::Trade), Err(RpcError::Busy)));
+        assert!(endpoint.enqueue(Priority::Launch).is_ok());
+
+        // A dropped ticket frees its place
+        drop(first);
+        assert!(endpoint.enqueue(Priority::Trade).is_ok());
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn refills_tokens_at_the_configured_rate() {
+        let endpoint = endpoint(RpcConfig { requests_per_sec: 2.0, burst: 2, ..RpcConfig::default() });
+        let started = Instant::now();
+        for _ in 0..4 {
+            drop(endpoint.admit(endpoint.enqueue(Priority::Launch).unwrap()).await);
+        }
+        // Two from the burst, then one every 500ms
+        assert_eq!(started.elapsed().as_millis(), 1000);
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn backs_off_and_recovers_after_rate_limits() {
+        let endpoint = endpoint(RpcConfig { requests_per_sec: 10.0, backoff_ms: 100, ..RpcConfig::default() });
+        endpoint.rate_limited(None);
+        endpoint.rate_limited(None);
+        assert_eq!(endpoint.lock_state().rate, 2.5);
+
+        // Second 429 in a row doubles the pause
+        let started = Instant::now();
+        drop(endpoint.admit(endpoint.enqueue(Priority::Launch).unwrap()).await);
+        assert_eq!(started.elapsed().as_millis(), 200);
+
+        // Retry-After wins over the backoff
+        endpoint.rate_limited(Some(Duration::from_secs(3)));
+        let started = Instant::now();
+        drop(endpoint.admit(endpoint.enqueue(Priority::Launch).unwrap()).await);
+        assert_eq!(started.elapsed().as_secs(), 3);
+
+        for _ in 0..100 {
+            endpoint.succeeded();
+        }
+        assert_eq!(endpoint.lock_state().rate, 10.0);
+    }
+}
@@ -0,0 +1,422 @@
+use anyhow::{bail, Context, Result};
+use regex::Regex;
+use serde::Deserialize;
+use serde_json::Value;
+use std::cell::OnceCell;
+use std::collections::{BTreeMap, HashMap, HashSet};
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+
+use crate::event_store::EventHub;
+use crate::types::MonitorEvent;
+
+const LAMPORTS_PER_SOL: f64 = 1_000_000_000.0;
+
+// ========================================================================
+// CONFIG
+// ========================================================================
+
+/// `[rules]` - named lists and named rule sets that sinks refer to.
+#[derive(Debug, Clone, Default, PartialEq, Deserialize)]
+#[serde(default)]
+pub struct RuleConfig {
+    // Name -> file with one entry per line, `#` starts a comment
+    pub lists: BTreeMap<String, PathBuf>,
+    pub sets: BTreeMap<String, Rule>,
+}
+
+/// A rule as written in TOML: `{ all = [...] }`, `{ any = [...] }`,
+/// `{ not = {...} }` or a condition on one field.
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+#[serde(untagged)]
+pub enum Rule {
+    All { all: Vec<Rule> },
+    Any { any: Vec<Rule> },
+    Not { not: Box<Rule> },
+    Condition(Condition),
+}
+
+/// Every operator given must hold, so `{ field = "sol", gte = 1, lt = 5 }` is a range.
+#[derive(Debug, Clone, Default, PartialEq, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct Condition {
+    pub field: String,
+    pub eq: Option<Value>,
+    pub ne: Option<Value>,
+    pub gt: Option<f64>,
+    pub gte: Option<f64>,
+    pub lt: Option<f64>,
+    pub lte: Option<f64>,
+    pub matches: Option<String>,
+    pub in_list: Option<String>,
+    pub exists: Option<bool>,
+}
+
+// ========================================================================
+// COMPILED RULES
+// ========================================================================
+
+#[derive(Debug)]
+enum Compiled {
+    All(Vec<Compiled>),
+    Any(Vec<Compiled>),
+    Not(Box<Compiled>),
+    Test { field: Field, checks: Vec<Check> },
+}
+
+#[derive(Debug)]
+enum Field {
+    Kind,
+    Platform,
+    Mint,
+    // Launch creator of the mint, looked up for non-launch events
+    Creator,
+    // `sol_amount` in SOL
+    Sol,
+    Event(Vec<String>),
+    Token(Vec<String>),
+}
+
+#[derive(Debug)]
+enum Check {
+    Eq(Value),
+    Ne(Value),
+    Gt(f64),
+    Gte(f64),
+    Lt(f64),
+    Lte(f64),
+    Matches(Regex),
+    InList(Arc<HashSet<String>>),
+    Exists(bool),
+}
+
+impl Field {
+    fn parse(name: &str) -> Self {
+        match name {
+            "kind" => Field::Kind,
+            "platform" => Field::Platform,
+            "mint" => Field::Mint,
+            "creator" => Field::Creator,
+            "sol" => Field::Sol,
+            _ => match name.strip_prefix("token.") {
+                Some(path) => Field::Token(split_path(path)),
+                None => Field::Event(split_path(name)),
+            },
+        }
+    }
+}
+
+fn split_path(path: &str) -> Vec<String> {
+    path.split('.').map(str::to_string).collect()
+}
+
+/// Compiled `[rules]`: regexes built and list files read once per (re)load.
+#[derive(Debug, Clone, Default)]
+pub struct RuleBook {
+    config: RuleConfig,
+    lists: BTreeMap<String, Arc<HashSet<String>>>,
+    sets: HashMap<String, Arc<Compiled>>,
+}
+
+// Compiled rules follow from the config; list contents can change on their own
+impl PartialEq for RuleBook {
+    fn eq(&self, other: &Self) -> bool {
+        self.config == other.config && self.lists == other.lists
+    }
+}
+
+impl RuleBook {
+    pub fn load(config: &RuleConfig) -> Result<Self> {
+        let mut lists = BTreeMap::new();
+        for (name, path) in &config.lists {
+            lists.insert(name.clone(), Arc::new(read_list(path)?));
+        }
+
+        let mut book = Self { config: config.clone(), lists, sets: HashMap::new() };
+        for (name, rule) in &config.sets {
+            let compiled = book.compile(rule).with_context(|| format!("rule set `{}`", name))?;
+            book.sets.insert(name.clone(), Arc::new(compiled));
+        }
+        Ok(book)
+    }
+
+    pub fn contains(&self, set: &str) -> bool {
+        self.sets.contains_key(set)
+    }
+
+    /// Whether the event passes the named set; no set means no restriction.
+    pub fn allows(&self, set: Option<&str>, subject: &Subject) -> bool {
+        let Some(name) = set else { return true };
+        // Unknown names are rejected on load
+        self.sets.get(name).is_none_or(|rule| evaluate(rule, subject))
+    }
+
+    fn compile(&self, rule: &Rule) -> Result<Compiled> {
+        Ok(match rule {
+            Rule::All { all } => Compiled::All(all.iter().map(|r| self.compile(r)).collect::<Result<_>>()?),
+            Rule::Any { any } => Compiled::Any(any.iter().map(|r| self.compile(r)).collect::<Result<_>>()?),
+            Rule::Not { not } => Compiled::Not(Box::new(self.compile(not)?)),
+            Rule::Condition(condition) => self.compile_condition(condition)?,
+        })
+    }
+
+    fn compile_condition(&self, condition: &Condition) -> Result<Compiled> {
+        let mut checks = Vec::new();
+        if let Some(value) = &condition.eq { checks.push(Check::Eq(value.clone())); }
+        if let Some(value) = &condition.ne { checks.push(Check::Ne(value.clone())); }
+        if let Some(value) = condition.gt { checks.push(Check::Gt(value)); }
+        if let Some(value) = condition.gte { checks.push(Check::Gte(value)); }
+        if let Some(value) = condition.lt { checks.push(Check::Lt(value)); }
+        if let Some(value) = condition.lte { checks.push(Check::Lte(value)); }
+        if let Some(pattern) = &condition.matches {
+            let regex = Regex::new(pattern).with_context(|| format!("invalid regex for `{}`", condition.field))?;
+            checks.push(Check::Matches(regex));
+        }
+        if let Some(list) = &condition.in_list {
+            let Some(entries) = self.lists.get(list) else {
+                bail!("unknown list `{}` (add it under [rules.lists])", list);
+            };
+            checks.push(Check::InList(entries.clone()));
+        }
+        if let Some(value) = condition.exists { checks.push(Check::Exists(value)); }
+
+        if checks.is_empty() {
+            bail!("condition on `{}` has no operator", condition.field);
+        }
+        Ok(Compiled::Test { field: Field::parse(&condition.field), checks })
+    }
+}
+
+fn read_list(path: &Path) -> Result<HashSet<String>> {
+    let contents = std::fs::read_to_string(path)
+        .with_context(|| format!("reading list {}", path.display()))?;
+    Ok(contents.lines()
+        .map(|line| line.split('#').next().unwrap_or("").trim())
+        .filter(|line| !line.is_empty())
+        .map(str::to_string)
+        .collect())
+}
+
+// ========================================================================
+// EVALUATION
+// ========================================================================
+
+/// One event under evaluation. The JSON views and the token record are built
+/// on first use and shared by every sink's rule set.
+pub struct Subject<'a> {
+    event: &'a MonitorEvent,
+    events: &'a EventHub,
+    data: OnceCell<Value>,
+    token: OnceCell<Value>,
+    creator: OnceCell<Value>,
+}
+
+impl<'a> Subject<'a> {
+    pub fn new(event: &'a MonitorEvent, events: &'a EventHub) -> Self {
+        Self { event, events, data: OnceCell::new(), token: OnceCell::new(), creator: OnceCell::new() }
+    }
+
+    fn data(&self) -> &Value {
+        self.data.get_or_init(|| {
+            serde_json::to_value(self.event).ok()
+                .and_then(|mut value| value.get_mut("data").map(Value::take))
+                .unwrap_or(Value::Null)
+        })
+    }
+
+    fn token(&self) -> &Value {
+        self.token.get_or_init(|| {
+            self.events.read(|store| store.token(self.event.mint()))
+                .and_then(|record| serde_json::to_value(record).ok())
+                .unwrap_or(Value::Null)
+        })
+    }
+
+    fn resolve(&self, field: &Field) -> Option<Value> {
+        match field {
+            Field::Kind => Some(Value::from(self.event.kind())),
+            Field::Platform => Some(Value::from(self.event.platform().label())),
+            Field::Mint => Some(Value::from(self.event.mint())),
+            Field::Creator => {
+                let creator = self.creator.get_or_init(|| {
+                    self.events.creator_of(self.event).map(Value::from).unwrap_or(Value::Null)
+                });
+                present(creator).cloned()
+            },
+            Field::Sol => self.data().get("sol_amount")
+                .and_then(Value::as_f64)
+                .map(|lamports| Value::from(lamports / LAMPORTS_PER_SOL)),
+            Field::Event(path) => lookup(self.data(), path).cloned(),
+            Field::Token(path) => lookup(self.token(), path).cloned(),
+        }
+    }
+}
+
+fn lookup<'v>(root: &'v Value, path: &[String]) -> Option<&'v Value> {
+    let value = path.iter().try_fold(root, |value, key| value.get(key.as_str()))?;
+    present(value)
+}
+
+// Null and empty strings count as missing (metadata often has `"twitter": ""`)
+fn present(value: &Value) -> Option<&Value> {
+    match value {
+        Value::Null => None,
+        Value::String(text) if text.is_empty() => None,
+        other => Some(other),
+    }
+}
+
+fn evaluate(rule: &Compiled, subject: &Subject) -> bool {
+    match rule {
+        Compiled::All(rules) => rules.iter().all(|rule| evaluate(rule, subject)),
+        Compiled::Any(rules) => rules.iter().any(|rule| evaluate(rule, subject)),
+        Compiled::Not(rule) => !evaluate(rule, subject),
+        Compiled::Test { field, checks } => {
+            let value = subject.resolve(field);
+            checks.iter().all(|check| check_value(check, value.as_ref()))
+        },
+    }
+}
+
+fn check_value(check: &Check, value: Option<&Value>) -> bool {
+    let number = value.and_then(Value::as_f64);
+    let text = value.and_then(Value::as_str);
+    match check {
+        Check::Exists(expected) => value.is_some() == *expected,
+        Check::Eq(expected) => value.is_some_and(|value| values_equal(value, expected)),
+        Check::Ne(expected) => !value.is_some_and(|value| values_equal(value, expected)),
+        Check::Gt(limit) => number.is_some_and(|n| n > *limit),
+        Check::Gte(limit) => number.is_some_and(|n| n >= *limit),
+        Check::Lt(limit) => number.is_some_and(|n| n < *limit),
+        Check::Lte(limit) => number.is_some_and(|n| n <= *limit),
+        Check::Matches(regex) => text.is_some_and(|text| regex.is_match(text)),
+        Check::InList(entries) => text.is_some_and(|text| entries.contains(text)),
+    }
+}
+
+// 5 and 5.0 are the same number to a rule author
+fn values_equal(value: &Value, expected: &Value) -> bool {
+    match (value.as_f64(), expected.as_f64()) {
+        (Some(a), Some(b)) => a == b,
+        _ => value == expected,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::{EventTiming, Platform, TokenLaunch, TokenMetadata, TradeEvent};
+
+    fn book(toml_rules: &str) -> RuleBook {
+        let config: RuleConfig = toml::from_str(toml_rules).unwrap();
+        RuleBook::load(&config).unwrap()
+    }
+
+    fn launch(symbol: &str, creator: &str) -> MonitorEvent {
+        MonitorEvent::Launch(TokenLaunch {
+            contract_address: "mint".to_string(),
+            name: None,
+            symbol: Some(symbol.to_string()),
+            creator: Some(creator.to_string()),
+            uri: None,
+            signature: "sig".to_string(),
+            platform: Platform::PumpFun,
+            timestamp: 0,
+            timing: EventTiming::default(),
+        })
+    }
+
+    fn metadata(twitter: &str) -> MonitorEvent {
+        MonitorEvent::Metadata(TokenMetadata {
+            mint: "mint".to_string(),
+            uri: "https://example.com/meta.json".to_string(),
+            name: None,
+            symbol: None,
+            description: None,
+            image: None,
+            twitter: Some(twitter.to_string()),
+            telegram: None,
+            website: None,
+            platform: Platform::PumpFun,
+            timestamp: 0,
+            timing: EventTiming::default(),
+        })
+    }
+
+    fn buy(platform: Platform, lamports: u64) -> MonitorEvent {
+        MonitorEvent::Trade(TradeEvent {
+            mint: "mint".to_string(),
+            trader: Some("buyer".to_string()),
+            is_buy: true,
+            sol_amount: Some(lamports),
+            token_amount: Some(1),
+            signature: "sig".to_string(),
+            platform,
+            timestamp: 0,
+            timing: EventTiming::default(),
+        })
+    }
+
+    #[test]
+    fn launchlab_buys_over_five_sol() {
+        let rules = book(r#"
+            [sets]
+            big_buys = { all = [
+                { field = "kind", eq = "trade" },
+                { field = "platform", eq = "raydium" },
+                { field = "is_buy", eq = true },
+                { field = "sol", gt = 5 },
+            ] }
+        "#);
+        let events = EventHub::new();
+        let allows = |event: &MonitorEvent| rules.allows(Some("big_buys"), &Subject::new(event, &events));
+
+        assert!(allows(&buy(Platform::Raydium, 6_000_000_000)));
+        assert!(!allows(&buy(Platform::Raydium, 5_000_000_000)));
+        assert!(!allows(&buy(Platform::PumpFun, 6_000_000_000)));
+        assert!(rules.allows(None, &Subject::new(&buy(Platform::PumpFun, 1), &events)));
+    }
+
+    #[test]
+    fn metadata_rule_reads_launch_and_blocklist() {
+        let dir = std::env::temp_dir().join(format!("rules-test-{}", std::process::id()));
+        std::fs::create_dir_all(&dir).unwrap();
+        let blocklist = dir.join("blocked.txt");
+        std::fs::write(&blocklist, "# known ruggers\nbad_dev\n\n").unwrap();
+
+        let rules = book(&format!(r#"
+            [lists]
+            blocked = "{}"
+
+            [sets.socials]
+            all = [
+                {{ field = "kind", eq = "metadata" }},
+                {{ field = "token.launch.symbol", matches = "(?i)^pepe" }},
+                {{ not = {{ field = "creator", in_list = "blocked" }} }},
+                {{ field = "twitter", exists = true }},
+            ]
+        "#, blocklist.display()));
+
+        let check = |creator: &str, symbol: &str, twitter: &str| {
+            let events = EventHub::new();
+            events.publish(launch(symbol, creator));
+            let event = metadata(twitter);
+            rules.allows(Some("socials"), &Subject::new(&event, &events))
+        };
+        assert!(check("good_dev", "PEPE2", "https://x.com/pepe"));
+        assert!(!check("bad_dev", "PEPE2", "https://x.com/pepe"));
+        assert!(!check("good_dev", "DOGE", "https://x.com/pepe"));
+        assert!(!check("good_dev", "PEPE2", ""));
+
+        std::fs::remove_dir_all(dir).ok();
+    }
+
+    #[test]
+    fn rejects_bad_rules() {
+        let load = |text: &str| RuleBook::load(&toml::from_str::<RuleConfig>(text).unwrap());
+        assert!(load(r#"sets.x = { field = "symbol", matches = "(" }"#).is_err());
+        assert!(load(r#"sets.x = { field = "creator", in_list = "missing" }"#).is_err());
+        assert!(load(r#"sets.x = { field = "symbol" }"#).is_err());
+        assert!(toml::from_str::<RuleConfig>(r#"sets.x = { field = "sol", gtt = 5 }"#).is_err());
+    }
+}
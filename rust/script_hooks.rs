@@ -0,0 +1,420 @@
+use anyhow::{bail, Context, Result};
+use rhai::module_resolvers::DummyModuleResolver;
+use rhai::serde::{from_dynamic, to_dynamic};
+use rhai::{CallFnOptions, Dynamic, Engine, Map, Scope, AST};
+use serde::Serialize;
+use serde_json::Value;
+use std::path::Path;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{Arc, Mutex};
+use std::time::Instant;
+use tracing::{info, warn, error, debug};
+
+use crate::block_clock::now_ms;
+use crate::config::{Config, ScriptConfig};
+use crate::event_store::EventHub;
+use crate::types::{EventTiming, MonitorEvent, ScriptAlert};
+
+// Entry point every script defines: `fn on_event(event) { ... }`
+const HOOK_FN: &str = "on_event";
+// Checking the clock on every operation is wasteful
+const DEADLINE_CHECK_EVERY: u64 = 256;
+
+// ========================================================================
+// SCRIPTED EVENT
+// ========================================================================
+
+/// An event after the script chain: possibly enriched data, tags and a route.
+#[derive(Debug, Clone, Serialize)]
+pub struct ScriptedEvent {
+    #[serde(rename = "type")]
+    pub kind: String,
+    pub data: Value,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub tags: Vec<String>,
+    // Sink names the event is limited to; None sends it everywhere
+    #[serde(skip)]
+    pub sinks: Option<Vec<String>>,
+}
+
+impl ScriptedEvent {
+    fn from_event(event: &MonitorEvent) -> Self {
+        let data = serde_json::to_value(event).ok()
+            .and_then(|mut value| value.get_mut("data").map(Value::take))
+            .unwrap_or(Value::Null);
+        Self { kind: event.kind().to_string(), data, tags: Vec::new(), sinks: None }
+    }
+
+    pub fn routes_to(&self, sink: &str) -> bool {
+        self.sinks.as_ref().is_none_or(|sinks| sinks.iter().any(|s| s == sink))
+    }
+
+    fn to_map(&self, event: &MonitorEvent) -> Result<Map> {
+        let mut map = Map::new();
+        map.insert("kind".into(), self.kind.clone().into());
+        map.insert("platform".into(), event.platform().label().into());
+        map.insert("mint".into(), event.mint().into());
+        map.insert("data".into(), to_dynamic(&self.data)?);
+        map.insert("tags".into(), to_dynamic(&self.tags)?);
+        map.insert("sinks".into(), match &self.sinks {
+            Some(sinks) => to_dynamic(sinks)?,
+            None => Dynamic::UNIT,
+        });
+        Ok(map)
+    }
+
+    fn from_map(kind: &str, map: &Map) -> Result<Self> {
+        let field = |name: &str| map.get(name).cloned().unwrap_or(Dynamic::UNIT);
+        let data: Value = from_dynamic(&field("data")).context("`data`")?;
+        let tags = field("tags");
+        let tags: Vec<String> = if tags.is_unit() { Vec::new() } else { from_dynamic(&tags).context("`tags`")? };
+        let sinks = field("sinks");
+        let sinks = if sinks.is_unit() { None } else { Some(from_dynamic(&sinks).context("`sinks`")?) };
+        Ok(Self { kind: kind.to_string(), data, tags, sinks })
+    }
+}
+
+// ========================================================================
+// HOST
+// ========================================================================
+
+struct Script {
+    name: String,
+    ast: AST,
+    consecutive_errors: u32,
+    disabled: bool,
+}
+
+// What a script asked the host to do while handling one event
+#[derive(Default)]
+struct CallState {
+    alerts: Vec<(Option<String>, String)>,
+}
+
+/// Runs the `[scripts]` chain over events on their way to the sinks. Scripts
+/// never see the WebSocket path - a slow or failing one only delays output.
+pub struct ScriptHost {
+    settings: ScriptConfig,
+    events: EventHub,
+    engine: Engine,
+    scripts: Vec<Script>,
+    // Milliseconds since `epoch` after which the running script is aborted
+    epoch: Instant,
+    deadline: Arc<AtomicU64>,
+    state: Arc<Mutex<CallState>>,
+}
+
+impl ScriptHost {
+    pub fn new(config: &Config, events: EventHub) -> Result<Self> {
+        let settings = config.scripts.clone();
+        let epoch = Instant::now();
+        let deadline = Arc::new(AtomicU64::new(u64::MAX));
+        let state = Arc::new(Mutex::new(CallState::default()));
+        let engine = build_engine(&settings, &events, epoch, deadline.clone(), state.clone());
+
+        let mut scripts = Vec::new();
+        for path in &settings.paths {
+            scripts.push(load_script(&engine, path)?);
+        }
+
+        Ok(Self { settings, events, engine, scripts, epoch, deadline, state })
+    }
+
+    pub fn script_count(&self) -> usize {
+        self.scripts.len()
+    }
+
+    /// Run every script over the event. None when a script dropped it.
+    pub fn process(&mut self, event: &MonitorEvent) -> Option<ScriptedEvent> {
+        let mut scripted = ScriptedEvent::from_event(event);
+        // Alerts come from scripts - feeding them back in could loop
+        if matches!(event, MonitorEvent::Alert(_)) {
+            return Some(scripted);
+        }
+
+        for index in 0..self.scripts.len() {
+            if self.scripts[index].disabled {
+                continue;
+            }
+            match self.run_script(index, event, &scripted) {
+                Ok(Some(next)) => {
+                    self.scripts[index].consecutive_errors = 0;
+                    scripted = next;
+                },
+                Ok(None) => {
+                    self.scripts[index].consecutive_errors = 0;
+                    debug!("📜 {} dropped {} | CA: {}", self.scripts[index].name, event.kind(), event.mint());
+                    return None;
+                },
+                // The event continues as it was before this script
+                Err(e) => self.record_error(index, event, &e),
+            }
+        }
+        Some(scripted)
+    }
+
+    fn run_script(&mut self, index: usize, event: &MonitorEvent, input: &ScriptedEvent) -> Result<Option<ScriptedEvent>> {
+        let map = input.to_map(event)?;
+        self.lock_state().alerts.clear();
+
+        let budget = self.epoch.elapsed().as_millis() as u64 + self.settings.timeout_ms;
+        self.deadline.store(budget, Ordering::Relaxed);
+        let options = CallFnOptions::new().eval_ast(false);
+        let result = self.engine.call_fn_with_options::<Dynamic>(
+            options,
+            &mut Scope::new(),
+            &self.scripts[index].ast,
+            HOOK_FN,
+            (Dynamic::from_map(map),),
+        );
+        self.deadline.store(u64::MAX, Ordering::Relaxed);
+
+        let alerts = std::mem::take(&mut self.lock_state().alerts);
+        let output = result.map_err(|e| anyhow::anyhow!("{}", e))?;
+        self.publish_alerts(index, event, alerts);
+
+        // () and true keep the event as it came in, false drops it
+        if output.is_unit() {
+            return Ok(Some(input.clone()));
+        }
+        if let Ok(keep) = output.as_bool() {
+            return Ok(keep.then(|| input.clone()));
+        }
+        match output.try_cast::<Map>() {
+            Some(map) => Ok(Some(ScriptedEvent::from_map(&input.kind, &map)?)),
+            None => bail!("{} must return the event map, true/false or ()", HOOK_FN),
+        }
+    }
+
+    fn publish_alerts(&self, index: usize, event: &MonitorEvent, alerts: Vec<(Option<String>, String)>) {
+        let script = &self.scripts[index].name;
+        for (mint, message) in alerts {
+            let mint = mint.unwrap_or_else(|| event.mint().to_string());
+            warn!("🚨 [{}] {} | CA: {} | {}", event.platform(), script, mint, message);
+            let detected_at_ms = now_ms();
+            self.events.publish(MonitorEvent::Alert(ScriptAlert {
+                mint,
+                script: script.clone(),
+                message,
+                source_kind: event.kind().to_string(),
+                platform: event.platform(),
+                timestamp: detected_at_ms / 1000,
+                timing: EventTiming { detected_at_ms, ..event.timing().clone() },
+            }));
+        }
+    }
+
+    fn record_error(&mut self, index: usize, event: &MonitorEvent, error: &anyhow::Error) {
+        let limit = self.settings.max_consecutive_errors;
+        let script = &mut self.scripts[index];
+        script.consecutive_errors += 1;
+        warn!("⚠️ Script {} failed on {} | CA: {} | {:#}", script.name, event.kind(), event.mint(), error);
+        if limit > 0 && script.consecutive_errors >= limit {
+            script.disabled = true;
+            error!("❌ Script {} failed {} times in a row - disabled until restart", script.name, limit);
+        }
+    }
+
+    fn lock_state(&self) -> std::sync::MutexGuard<'_, CallState> {
+        self.state.lock().unwrap_or_else(|poisoned| poisoned.into_inner())
+    }
+}
+
+fn load_script(engine: &Engine, path: &Path) -> Result<Script> {
+    let source = std::fs::read_to_string(path)
+        .with_context(|| format!("reading script {}", path.display()))?;
+    let ast = engine.compile(&source)
+        .map_err(|e| anyhow::anyhow!("compiling script {}: {}", path.display(), e))?;
+    if !ast.iter_functions().any(|f| f.name == HOOK_FN && f.params.len() == 1) {
+        bail!("script {} has no `fn {}(event)`", path.display(), HOOK_FN);
+    }
+    let name = path.file_name().map_or_else(|| path.display().to_string(), |n| n.to_string_lossy().into_owned());
+    info!("📜 Loaded script {}", name);
+    Ok(Script { name, ast, consecutive_errors: 0, disabled: false })
+}
+
+// ========================================================================
+// ENGINE - sandbox limits and host API
+// ========================================================================
+
+fn build_engine(
+    settings: &ScriptConfig,
+    events: &EventHub,
+    epoch: Instant,
+    deadline: Arc<AtomicU64>,
+    state: Arc<Mutex<CallState>>,
+) -> Engine {
+    let mut engine = Engine::new();
+
+    // No imports, no eval, bounded everything
+    engine.set_module_resolver(DummyModuleResolver::new());
+    engine.disable_symbol("eval");
+    engine.set_max_operations(settings.max_operations);
+    engine.set_max_call_levels(32);
+    engine.set_max_expr_depths(64, 32);
+    engine.set_max_string_size(64 * 1024);
+    engine.set_max_array_size(10_000);
+    engine.set_max_map_size(10_000);
+
+    engine.on_progress(move |operations| {
+        if operations % DEADLINE_CHECK_EVERY != 0 {
+            return None;
+        }
+        let elapsed = epoch.elapsed().as_millis() as u64;
+        (elapsed > deadline.load(Ordering::Relaxed)).then(|| "script timed out".into())
+    });
+    engine.on_print(|text| info!("📜 {}", text));
+    engine.on_debug(|text, _, pos| debug!("📜 {} @ {}", text, pos));
+
+    // token(mint) -> the stored token record as a map, () when unknown
+    let hub = events.clone();
+    engine.register_fn("token", move |mint: &str| -> Dynamic {
+        hub.read(|store| store.token(mint))
+            .and_then(|record| to_dynamic(record).ok())
+            .unwrap_or(Dynamic::UNIT)
+    });
+
+    // creator(address) -> launch history of a creator wallet, () when unknown
+    let hub = events.clone();
+    engine.register_fn("creator", move |address: &str| -> Dynamic {
+        hub.read(|store| store.creator(address).and_then(|profile| to_dynamic(profile).ok()))
+            .unwrap_or(Dynamic::UNIT)
+    });
+
+    // alert(message) about the event's mint, or alert(mint, message)
+    let max_alerts = settings.max_alerts_per_event;
+    let alerts = state.clone();
+    engine.register_fn("alert", move |message: &str| {
+        push_alert(&alerts, max_alerts, None, message);
+    });
+    let alerts = state;
+    engine.register_fn("alert", move |mint: &str, message: &str| {
+        push_alert(&alerts, max_alerts, Some(mint), message);
+    });
+
+    // Lamports -> SOL without integer division surprises
+    engine.register_fn("sol", |lamports: i64| lamports as f64 / 1_000_000_000.0);
+
+    engine
+}
+
+fn push_alert(state: &Mutex<CallState>, max_alerts: usize, mint: Option<&str>, message: &str) {
+    let mut state = state.lock().unwrap_or_else(|poisoned| poisoned.into_inner());
+    if state.alerts.len() < max_alerts {
+        state.alerts.push((mint.map(str::to_string), message.to_string()));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::{Platform, TradeEvent};
+
+    fn host(sources: &[&str], tweak: impl FnOnce(&mut Config)) -> (ScriptHost, EventHub) {
+        let dir = std::env::temp_dir().join(format!("script-hooks-{}-{}", std::process::id(), sources.len()));
+        std::fs::create_dir_all(&dir).unwrap();
+        let mut config = Config::default();
+        config.scripts.enabled = true;
+        for (index, source) in sources.iter().enumerate() {
+            let path = dir.join(format!("{}.rhai", index));
+            std::fs::write(&path, source).unwrap();
+            config.scripts.paths.push(path);
+        }
+        tweak(&mut config);
+        let events = EventHub::new();
+        (ScriptHost::new(&config, events.clone()).unwrap(), events)
+    }
+
+    fn buy(lamports: u64) -> MonitorEvent {
+        MonitorEvent::Trade(TradeEvent {
+            mint: "mint".to_string(),
+            trader: Some("buyer".to_string()),
+            is_buy: true,
+            sol_amount: Some(lamports),
+            token_amount: Some(1),
+            signature: "sig".to_string(),
+            platform: Platform::Raydium,
+            timestamp: 0,
+            timing: EventTiming::default(),
+        })
+    }
+
+    #[test]
+    fn scripts_tag_enrich_route_and_drop() {
+        let (mut host, events) = host(&[
+            r#"
+            fn on_event(event) {
+                if sol(event.data.sol_amount) < 1.0 { return false; }
+                event.tags.push("whale");
+                event.data.sol = sol(event.data.sol_amount);
+                event.sinks = ["jsonl"];
+                alert("big buy");
+                event
+            }
+            "#,
+            // Returning nothing keeps the event as it is
+            "fn on_event(event) { }",
+        ], |_| {});
+        let mut receiver = events.subscribe();
+
+        assert!(host.process(&buy(500_000_000)).is_none());
+
+        let scripted = host.process(&buy(2_000_000_000)).unwrap();
+        assert_eq!(scripted.tags, vec!["whale".to_string()]);
+        assert_eq!(scripted.data["sol"], 2.0);
+        assert!(scripted.routes_to("jsonl"));
+        assert!(!scripted.routes_to("console"));
+        match receiver.try_recv() {
+            Ok(MonitorEvent::Alert(alert)) => {
+                assert_eq!(alert.message, "big buy");
+                assert_eq!(alert.source_kind, "trade");
+            },
+            other => panic!("expected alert, got {:?}", other),
+        }
+    }
+
+    #[test]
+    fn runaway_and_failing_scripts_are_isolated() {
+        let (mut host, _events) = host(&[
+            "fn on_event(event) { loop { } }",
+            r#"fn on_event(event) { throw "boom"; }"#,
+            r#"fn on_event(event) { event.tags.push("ok"); event }"#,
+        ], |config| {
+            // Unlimited operations, so only the wall clock stops the loop
+            config.scripts.max_operations = 0;
+            config.scripts.timeout_ms = 50;
+            config.scripts.max_consecutive_errors = 2;
+        });
+
+        let started = Instant::now();
+        let scripted = host.process(&buy(1)).unwrap();
+        assert!(started.elapsed().as_millis() < 1_000);
+        assert_eq!(scripted.tags, vec!["ok".to_string()]);
+
+        host.process(&buy(1));
+        assert!(host.scripts[0].disabled && host.scripts[1].disabled);
+        assert!(!host.scripts[2].disabled);
+    }
+
+    #[test]
+    fn example_script_tags_whales() {
+        let mut config = Config::default();
+        config.scripts.paths.push(Path::new(env!("CARGO_MANIFEST_DIR")).join("scripts/example.rhai"));
+        let mut host = ScriptHost::new(&config, EventHub::new()).unwrap();
+
+        let scripted = host.process(&buy(6_000_000_000)).unwrap();
+        assert_eq!(scripted.tags, vec!["whale".to_string()]);
+        assert!(host.process(&buy(1_000)).unwrap().tags.is_empty());
+    }
+
+    #[test]
+    fn rejects_scripts_without_hook() {
+        let dir = std::env::temp_dir().join(format!("script-hooks-missing-{}", std::process::id()));
+        std::fs::create_dir_all(&dir).unwrap();
+        let path = dir.join("empty.rhai");
+        std::fs::write(&path, "let x = 1;").unwrap();
+        let mut config = Config::default();
+        config.scripts.paths.push(path);
+        assert!(ScriptHost::new(&config, EventHub::new()).is_err());
+    }
+}
@@ -0,0 +1,240 @@
+use std::fs::{File, OpenOptions};
+use std::io::Write;
+use std::path::PathBuf;
+use tokio::sync::{broadcast::error::RecvError, watch};
+use tracing::{info, warn, error};
+
+use crate::config::RuntimeConfig;
+use crate::event_store::{EventFeed, EventHub};
+use crate::rules::Subject;
+use crate::script_hooks::{ScriptHost, ScriptedEvent};
+use crate::types::{MonitorEvent, Platform};
+
+// Warn when this many events wait for the sinks (again once it has halved)
+const BACKLOG_WARNING: usize = 10_000;
+
+/// Output sinks fed from the event hub. Filters and sink settings are re-read
+/// from the runtime config on every event, so reloads apply immediately.
+/// Script hooks (if any) run here too, after `[filters]` and before the rules.
+///
+/// The monitor passes a lossless `feed`, so no CA line is ever skipped, and the
+/// sinks return once it's closed and drained. A live broadcast feed skips
+/// whatever it lagged past (counted in the log).
+pub async fn run_sinks(events: EventHub, mut feed: EventFeed, runtime: watch::Receiver<RuntimeConfig>, mut scripts: Option<ScriptHost>) {
+    let mut jsonl = JsonlSink::default();
+    let mut backlog_warned = false;
+
+    loop {
+        let event = match feed.recv().await {
+            Ok(event) => event,
+            Err(RecvError::Lagged(skipped)) => {
+                warn!("⚠️ Sinks lagging, skipped {} events", skipped);
+                continue;
+            },
+            Err(RecvError::Closed) => break,
+        };
+
+        // Nothing is dropped, so a slow script or disk shows up as a growing queue
+        let queued = feed.queued();
+        if queued >= BACKLOG_WARNING && !backlog_warned {
+            warn!("⚠️ Sinks falling behind, {} events queued", queued);
+            backlog_warned = true;
+        } else if queued < BACKLOG_WARNING / 2 {
+            backlog_warned = false;
+        }
+
+        let passes = {
+            let settings = runtime.borrow();
+            let creator = if settings.filters.needs_creator() { events.creator_of(&event) } else { None };
+            settings.filters.matches(&event, creator.as_deref())
+        };
+        if !passes {
+            continue;
+        }
+
+        // Scripts are bounded by their timeout, but still run off the async workers -
+        // the host travels to a blocking thread and back with each event
+        let (event, scripted) = match scripts.take() {
+            Some(mut host) => {
+                let run = tokio::task::spawn_blocking(move || {
+                    let scripted = host.process(&event);
+                    (host, event, scripted)
+                });
+                match run.await {
+                    Ok((host, event, Some(scripted))) => {
+                        scripts = Some(host);
+                        (event, Some(scripted))
+                    },
+                    Ok((host, _, None)) => {
+                        scripts = Some(host);
+                        continue;
+                    },
+                    Err(e) => {
+                        error!("❌ Script hooks panicked, running without them: {}", e);
+                        continue;
+                    },
+                }
+            },
+            None => (event, None),
+        };
+        let routed = |sink: &str| scripted.as_ref().is_none_or(|scripted| scripted.routes_to(sink));
+
+        // Decide under the borrow, write after releasing it
+        let (console, jsonl_path) = {
+            let settings = runtime.borrow();
+            // Each sink has its own rule set
+            let subject = Subject::new(&event, &events);
+            let sinks = &settings.sinks;
+            let console = sinks.console && routed("console")
+                && settings.rules.allows(sinks.console_rules.as_deref(), &subject);
+            let jsonl_path = sinks.jsonl_path.clone()
+                .filter(|_| routed("jsonl") && settings.rules.allows(sinks.jsonl_rules.as_deref(), &subject));
+            let console = console.then(|| sinks.console_rules.is_some());
+            (console, jsonl_path)
+        };
+
+        let tags = scripted.as_ref().map_or(&[][..], |scripted| &scripted.tags[..]);
+        if let Some(line) = console.and_then(|ruled| console_line(&event, ruled, tags)) {
+            println!("{}", line);
+        }
+        jsonl.write(jsonl_path, &event, scripted.as_ref());
+    }
+}
+
+fn console_line(event: &MonitorEvent, ruled: bool, tags: &[String]) -> Option<String> {
+    let tags = if tags.is_empty() { String::new() } else { format!(" [{}]", tags.join(", ")) };
+    // 🎯 MAIN REQUIREMENT: Print CA to console
+    match event {
+        MonitorEvent::Launch(launch) => Some(format!("CA: {}{}", launch.contract_address, tags)),
+        // LaunchLab buys are what the Raydium side is watched for
+        MonitorEvent::Trade(trade) if trade.platform == Platform::Raydium && trade.is_buy => Some(format!("CA: {}{}", trade.mint, tags)),
+        // A console rule set decides which other events are worth a line
+        other if ruled => Some(format!("CA: {} ({}){}", other.mint(), other.kind(), tags)),
+        _ => None,
+    }
+}
+
+/// Appends events as JSON lines, reopening when the configured path changes.
+#[derive(Default)]
+struct JsonlSink {
+    path: Option<PathBuf>,
+    file: Option<File>,
+}
+
+impl JsonlSink {
+    /// Scripted events are written with their enriched data and tags.
+    fn write(&mut self, path: Option<PathBuf>, event: &MonitorEvent, scripted: Option<&ScriptedEvent>) {
+        if path != self.path {
+            self.file = path.as_ref().and_then(|path| {
+                match OpenOptions::new().create(true).append(true).open(path) {
+                    Ok(file) => {
+                        info!("📝 Writing events to {}", path.display());
+                        Some(file)
+                    },
+                    Err(e) => {
+                        error!("❌ Can't open {}: {}", path.display(), e);
+                        None
+                    },
+                }
+            });
+            self.path = path;
+        }
+
+        let Some(file) = self.file.as_mut() else { return };
+        let line = match scripted {
+            Some(scripted) => serde_json::to_string(scripted),
+            None => serde_json::to_string(event),
+        };
+        let result = line
+            .map_err(std::io::Error::other)
+            .and_then(|line| writeln!(file, "{}", line));
+        if let Err(e) = result {
+            warn!("⚠️ Failed to write event: {}", e);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::Config;
+    use crate::types::{EventTiming, TokenLaunch, TradeEvent};
+    use tokio::time::{sleep, Duration};
+
+    fn launch(mint: &str) -> MonitorEvent {
+        MonitorEvent::Launch(TokenLaunch {
+            contract_address: mint.to_string(),
+            name: None,
+            symbol: None,
+            creator: None,
+            uri: None,
+            signature: format!("sig-{}", mint),
+            platform: Platform::PumpFun,
+            timestamp: 0,
+            timing: EventTiming::default(),
+        })
+    }
+
+    fn trade(platform: Platform, is_buy: bool) -> MonitorEvent {
+        MonitorEvent::Trade(TradeEvent {
+            mint: "mint".to_string(),
+            trader: None,
+            is_buy,
+            sol_amount: None,
+            token_amount: None,
+            signature: "sig".to_string(),
+            platform,
+            timestamp: 0,
+            timing: EventTiming::default(),
+        })
+    }
+
+    #[test]
+    fn console_prints_launches_and_raydium_buys() {
+        let tags = ["hot".to_string()];
+        assert_eq!(console_line(&launch("a"), false, &[]).as_deref(), Some("CA: a"));
+        assert_eq!(console_line(&trade(Platform::Raydium, true), false, &tags).as_deref(), Some("CA: mint [hot]"));
+        assert_eq!(console_line(&trade(Platform::Raydium, false), false, &[]), None);
+        assert_eq!(console_line(&trade(Platform::PumpFun, true), false, &[]), None);
+        // With a console rule set, whatever passed it gets a line
+        assert_eq!(console_line(&trade(Platform::PumpFun, true), true, &[]).as_deref(), Some("CA: mint (trade)"));
+    }
+
+    // Default #[tokio::test] runtime is current-thread, where block_in_place panics
+    #[tokio::test]
+    async fn scripts_run_on_a_current_thread_runtime() {
+        let dir = std::env::temp_dir().join(format!("sinks-{}", std::process::id()));
+        std::fs::create_dir_all(&dir).unwrap();
+        let script = dir.join("tag.rhai");
+        std::fs::write(&script, r#"fn on_event(event) { if event.data.contract_address == "skip" { return false; } event.tags.push("seen"); event }"#).unwrap();
+        let output = dir.join("events.jsonl");
+        std::fs::remove_file(&output).ok();
+
+        let mut config = Config::default();
+        config.scripts.enabled = true;
+        config.scripts.paths.push(script);
+        config.sinks.console = false;
+        config.sinks.jsonl_path = Some(output.clone());
+        let events = EventHub::new();
+        let scripts = ScriptHost::new(&config, events.clone()).unwrap();
+        let (_runtime_tx, runtime_rx) = watch::channel(config.runtime().unwrap());
+
+        let sinks = tokio::spawn(run_sinks(events.clone(), EventFeed::Live(events.subscribe()), runtime_rx, Some(scripts)));
+        events.publish(launch("skip"));
+        events.publish(launch("keep"));
+
+        let mut lines = Vec::new();
+        for _ in 0..50 {
+            lines = std::fs::read_to_string(&output).unwrap_or_default().lines().map(str::to_string).collect();
+            if !lines.is_empty() {
+                break;
+            }
+            sleep(Duration::from_millis(20)).await;
+        }
+        assert_eq!(lines.len(), 1, "{:?}", lines);
+        assert!(lines[0].contains("keep") && lines[0].contains("seen"), "{}", lines[0]);
+        assert!(!sinks.is_finished());
+        sinks.abort();
+        std::fs::remove_dir_all(dir).ok();
+    }
+}
@@ -0,0 +1,135 @@
+//! History fetching for `backtest` against the mock RPC, and the archive it writes replayed through the monitor.
+
+mod support;
+
+use serde_json::{json, Value};
+use tokio::sync::mpsc;
+
+use blazing_monitor::backtest::{BacktestRange, HistoryFetcher};
+use blazing_monitor::block_clock::BlockClock;
+use blazing_monitor::config::Config;
+use blazing_monitor::pump_monitor::PumpFunMonitor;
+use blazing_monitor::types::MonitorEvent;
+use blazing_monitor::ws_recording::{self, read_frames, FrameSource, ReplayTargets};
+
+use support::*;
+
+const RANGE: BacktestRange = BacktestRange::Slots { from: 100, to: Some(120) };
+
+fn signature_entry(seed: u8, slot: u64, err: Value) -> Value {
+    json!({ "signature": signature(seed), "slot": slot, "blockTime": 1_700_000_000 + slot, "err": err })
+}
+
+/// pump.fun history, newest first: one after the range, a failed one, two launches, one before the range.
+fn pump_history(server: &MockSolana) -> Vec<String> {
+    let failed = json!({ "InstructionError": [0, { "Custom": 6000 }] });
+    server.answer("getSignaturesForAddress", json!([
+        signature_entry(5, 130, Value::Null),
+        signature_entry(4, 120, failed),
+        signature_entry(3, 110, Value::Null),
+        signature_entry(2, 100, Value::Null),
+        signature_entry(1, 90, Value::Null),
+    ]));
+    server.answer("getBlocksWithLimit", json!([121]));
+    server.answer("getBlock", json!({ "signatures": [signature(6)] }));
+
+    let mints = vec![pubkey(), pubkey()];
+    for (seed, slot, mint) in [(2, 100, &mints[0]), (3, 110, &mints[1])] {
+        let logs = pump_create_logs("Old Token", "OLD", "", mint, &pubkey());
+        server.add_transaction(&signature(seed), json!({
+            "slot": slot,
+            "blockTime": 1_700_000_000 + slot,
+            "meta": { "err": null, "logMessages": logs },
+        }));
+    }
+    mints
+}
+
+fn pump_only(server: &MockSolana) -> Config {
+    let mut config = server.config();
+    config.platforms.raydium = false;
+    config
+}
+
+fn temp_archive(name: &str) -> std::path::PathBuf {
+    std::env::temp_dir().join(format!("backtest-{}-{}.jsonl.gz", std::process::id(), name))
+}
+
+#[tokio::test]
+async fn fetches_successful_transactions_in_range_oldest_first() {
+    let server = MockSolana::start().await;
+    pump_history(&server);
+    let path = temp_archive("range");
+
+    let stats = HistoryFetcher::new(&pump_only(&server)).unwrap().fetch(RANGE, &path).await.unwrap();
+    assert_eq!((stats.signatures, stats.failed_transactions, stats.fetched_transactions), (2, 1, 2));
+    assert_eq!((stats.first_slot, stats.last_slot, stats.truncated), (Some(100), Some(110), false));
+    assert_eq!(server.calls("getTransaction"), 2);
+
+    let frames: Vec<_> = read_frames(&path).unwrap().collect();
+    assert_eq!(frames.len(), 2);
+    assert!(frames.iter().all(|frame| frame.source == FrameSource::PumpFun));
+    assert_eq!(frames[0].at_ms, 1_700_000_100_000);
+    assert!(frames[0].text.contains(&signature(2)) && frames[1].text.contains(&signature(3)));
+    std::fs::remove_file(path).ok();
+}
+
+#[tokio::test]
+async fn retries_rate_limited_requests() {
+    let server = MockSolana::start().await;
+    pump_history(&server);
+    server.inject(Fault::TooManyRequests);
+    server.inject(Fault::RpcError(429));
+    let path = temp_archive("retries");
+
+    let stats = HistoryFetcher::new(&pump_only(&server)).unwrap().fetch(RANGE, &path).await.unwrap();
+    assert_eq!((stats.fetched_transactions, stats.fetch_errors), (2, 0));
+    assert_eq!(server.calls("getBlocksWithLimit"), 3);
+    std::fs::remove_file(path).ok();
+}
+
+#[tokio::test]
+async fn stops_at_max_transactions() {
+    let server = MockSolana::start().await;
+    pump_history(&server);
+    let mut config = pump_only(&server);
+    config.backtest.max_transactions = 1;
+    let path = temp_archive("truncated");
+
+    let stats = HistoryFetcher::new(&config).unwrap().fetch(RANGE, &path).await.unwrap();
+    assert!(stats.truncated);
+    // Walking back from the end keeps the newest transaction
+    assert_eq!((stats.signatures, stats.first_slot), (1, Some(110)));
+    std::fs::remove_file(path).ok();
+}
+
+#[tokio::test]
+async fn archive_replays_into_launches() {
+    let server = MockSolana::start().await;
+    let mints = pump_history(&server);
+    let config = pump_only(&server);
+    let path = temp_archive("replay");
+    HistoryFetcher::new(&config).unwrap().fetch(RANGE, &path).await.unwrap();
+
+    let (events, mut received) = event_hub();
+    let clock = BlockClock::new(&config).unwrap();
+    let mut monitor = PumpFunMonitor::new(&config, events, clock).await.unwrap();
+    let (tx, rx) = mpsc::channel(8);
+    let replaying = tokio::spawn(async move { monitor.replay(rx).await });
+    let targets = ReplayTargets { pump_fun: Some(tx), raydium: None };
+    ws_recording::replay_filtered(path.clone(), 0.0, targets, move |frame| RANGE.contains_frame(frame)).await.unwrap();
+    replaying.await.unwrap();
+
+    for (mint, slot) in mints.iter().zip([100, 110]) {
+        match next_event(&mut received).await {
+            MonitorEvent::Launch(launch) => {
+                assert_eq!(&launch.contract_address, mint);
+                // Stamped with the recorded frame time, not when the replay ran
+                assert_eq!(launch.timing.detected_at_ms, (1_700_000_000 + slot) * 1000);
+                assert_eq!(launch.timestamp, 1_700_000_000 + slot);
+            }
+            other => panic!("expected a launch, got {:?}", other),
+        }
+    }
+    std::fs::remove_file(path).ok();
+}
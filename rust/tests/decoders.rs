@@ -0,0 +1,162 @@
+//! Golden-output tests for the decoders, over the payloads in `tests/fixtures`.
+//!
+//! `pump_*.json` hold a transaction's logs and the events decoded from them;
+//! `launchlab_*.json` hold a `getTransaction` result and the swap read from it.
+
+use std::fs;
+use std::path::Path;
+
+use base64::{engine::general_purpose, Engine as _};
+use proptest::prelude::*;
+use serde_json::Value;
+
+use blazing_monitor::pump_monitor::{
+    decode_complete_event, decode_create_event, decode_migration_event, decode_trade_event, parse_event_from_log,
+};
+use blazing_monitor::raydium_launchpad_monitor::{decode_swap, LaunchLabSwap};
+use blazing_monitor::types::{
+    EventTiming, PUMP_COMPLETE_EVENT_DISCRIMINATOR, PUMP_CREATE_EVENT_DISCRIMINATOR, PUMP_MIGRATION_EVENT_DISCRIMINATOR,
+    PUMP_TRADE_EVENT_DISCRIMINATOR,
+};
+
+fn fixtures(prefix: &str) -> Vec<(String, Value)> {
+    let dir = Path::new(env!("CARGO_MANIFEST_DIR")).join("tests/fixtures");
+    let mut fixtures: Vec<(String, Value)> = fs::read_dir(dir).unwrap()
+        .map(|entry| entry.unwrap().path())
+        .filter(|path| path.file_name().unwrap().to_string_lossy().starts_with(prefix))
+        .map(|path| {
+            let fixture = serde_json::from_str(&fs::read_to_string(&path).unwrap()).unwrap();
+            (path.file_name().unwrap().to_string_lossy().into_owned(), fixture)
+        })
+        .collect();
+    fixtures.sort_by(|a, b| a.0.cmp(&b.0));
+    assert!(!fixtures.is_empty(), "no {}* fixtures", prefix);
+    fixtures
+}
+
+fn logs(fixture: &Value) -> impl Iterator<Item = &str> {
+    fixture["logs"].as_array().unwrap().iter().map(|log| log.as_str().unwrap())
+}
+
+// Events without an on-chain timestamp (the legacy CreateEvent) fall back to this block time
+const BLOCK_TIME_MS: u64 = 1_760_000_000_000;
+
+// Timing is the caller's, not the payload's
+fn without_timing(mut event: Value) -> Value {
+    event["data"].as_object_mut().unwrap().remove("timing");
+    event
+}
+
+#[test]
+fn pump_fixtures_decode_to_golden_events() {
+    for (name, fixture) in fixtures("pump_") {
+        let signature = fixture["signature"].as_str().unwrap();
+        let timing = EventTiming { slot: fixture["slot"].as_u64(), block_time_ms: Some(BLOCK_TIME_MS), ..Default::default() };
+
+        let decoded: Vec<Value> = logs(&fixture)
+            .filter_map(|log| parse_event_from_log(log, signature.to_string(), &timing).unwrap())
+            .map(|event| without_timing(serde_json::to_value(event).unwrap()))
+            .collect();
+        assert_eq!(Value::Array(decoded), fixture["expected"], "{}", name);
+    }
+}
+
+#[test]
+fn launchlab_fixtures_decode_to_golden_swaps() {
+    for (name, fixture) in fixtures("launchlab_") {
+        let expected = fixture["expected"].as_object().map(|swap| LaunchLabSwap {
+            mint: swap["mint"].as_str().unwrap().to_string(),
+            trader: swap["trader"].as_str().map(str::to_string),
+            is_buy: swap["is_buy"].as_bool().unwrap(),
+            sol_amount: swap["sol_amount"].as_u64(),
+            token_amount: swap["token_amount"].as_u64(),
+        });
+        assert_eq!(decode_swap(&fixture["transaction"]), expected, "{}", name);
+    }
+}
+
+// Every prefix of a real payload is the shape a truncated log line takes
+#[test]
+fn truncated_pump_payloads_fail_cleanly() {
+    for (name, fixture) in fixtures("pump_") {
+        for log in logs(&fixture) {
+            let Some(data) = log.strip_prefix("Program data: ") else { continue };
+            let Ok(data) = general_purpose::STANDARD.decode(data) else { continue };
+            for len in 0..data.len() {
+                let line = format!("Program data: {}", general_purpose::STANDARD.encode(&data[..len]));
+                let _ = parse_event_from_log(&line, "sig".to_string(), &EventTiming::default());
+            }
+            // The fixed-size events fail at every cut short of their last field
+            if data.starts_with(&PUMP_COMPLETE_EVENT_DISCRIMINATOR) {
+                for len in 8..8 + 96 {
+                    assert!(decode_complete_event(&data[..len], String::new(), EventTiming::default()).is_err(), "{} at {}", name, len);
+                }
+            }
+        }
+    }
+}
+
+#[test]
+fn launchlab_fixtures_survive_missing_fields() {
+    // Drop each object key in turn - decoding may fail, never panic
+    fn paths(value: &Value, path: &mut Vec<String>, out: &mut Vec<Vec<String>>) {
+        if let Some(object) = value.as_object() {
+            for (key, child) in object {
+                path.push(key.clone());
+                out.push(path.clone());
+                paths(child, path, out);
+                path.pop();
+            }
+        } else if let Some(array) = value.as_array() {
+            for (index, child) in array.iter().enumerate() {
+                path.push(index.to_string());
+                paths(child, path, out);
+                path.pop();
+            }
+        }
+    }
+
+    for (_, fixture) in fixtures("launchlab_") {
+        let mut all = Vec::new();
+        paths(&fixture["transaction"], &mut Vec::new(), &mut all);
+        for path in all {
+            let mut transaction = fixture["transaction"].clone();
+            let (key, parents) = path.split_last().unwrap();
+            let mut parent = &mut transaction;
+            for step in parents {
+                parent = match step.parse::<usize>() {
+                    Ok(index) if parent.is_array() => &mut parent[index],
+                    _ => &mut parent[step.as_str()],
+                };
+            }
+            parent.as_object_mut().unwrap().remove(key);
+            let _ = decode_swap(&transaction);
+        }
+    }
+}
+
+proptest! {
+    #[test]
+    fn pump_decoders_never_panic(discriminator in 0usize..4, body in proptest::collection::vec(any::<u8>(), 0..400)) {
+        let prefix = [
+            PUMP_CREATE_EVENT_DISCRIMINATOR,
+            PUMP_TRADE_EVENT_DISCRIMINATOR,
+            PUMP_COMPLETE_EVENT_DISCRIMINATOR,
+            PUMP_MIGRATION_EVENT_DISCRIMINATOR,
+        ][discriminator];
+        let data = [&prefix[..], &body].concat();
+        let (signature, timing) = (String::new(), EventTiming::default());
+
+        let _ = decode_create_event(&data, signature.clone(), timing.clone());
+        let _ = decode_trade_event(&data, signature.clone(), timing.clone());
+        let _ = decode_complete_event(&data, signature.clone(), timing.clone());
+        let _ = decode_migration_event(&data, signature.clone(), timing.clone());
+        let line = format!("Program data: {}", general_purpose::STANDARD.encode(&data));
+        let _ = parse_event_from_log(&line, signature, &timing);
+    }
+
+    #[test]
+    fn pump_log_lines_never_panic(line in "\\PC*") {
+        let _ = parse_event_from_log(&line, String::new(), &EventTiming::default());
+    }
+}
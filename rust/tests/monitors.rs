@@ -0,0 +1,331 @@
+//! Both monitors end to end against the mock RPC/WebSocket server in `support`.
+
+mod support;
+
+use serde_json::Value;
+use tokio::task::JoinHandle;
+use tokio::time::Duration;
+
+use blazing_monitor::block_clock::BlockClock;
+use blazing_monitor::config::Config;
+use blazing_monitor::event_store::EventHub;
+use blazing_monitor::pump_monitor::PumpFunMonitor;
+use blazing_monitor::raydium_launchpad_monitor::RaydiumBuyMonitor;
+use blazing_monitor::types::{MonitorEvent, Platform};
+
+use support::*;
+
+const QUIET: Duration = Duration::from_millis(300);
+
+async fn spawn_pump(config: &Config, events: EventHub) -> JoinHandle<()> {
+    let clock = BlockClock::new(config).unwrap();
+    let mut monitor = PumpFunMonitor::new(config, events, clock).await.unwrap();
+    tokio::spawn(async move {
+        let _ = monitor.start_monitoring().await;
+    })
+}
+
+async fn spawn_raydium(config: &Config, events: EventHub) -> JoinHandle<()> {
+    let clock = BlockClock::new(config).unwrap();
+    let mut monitor = RaydiumBuyMonitor::new(config, events, clock).await.unwrap();
+    tokio::spawn(async move {
+        let _ = monitor.start_monitoring().await;
+    })
+}
+
+fn pump_launch(server: &MockSolana, seed: u8, mint: &str) {
+    let logs = pump_create_logs("Mock Token", "MOCK", "https://example.com/mock.json", mint, &pubkey());
+    server.push(&logs_notification(&signature(seed), &logs, 1000 + seed as u64, Value::Null));
+}
+
+fn launchlab_buy(server: &MockSolana, seed: u8) {
+    server.push(&logs_notification(&signature(seed), &launchlab_buy_logs(), 2000 + seed as u64, Value::Null));
+}
+
+// ========================================================================
+// PUMP.FUN
+// ========================================================================
+
+#[tokio::test]
+async fn pump_decodes_launch_from_create_logs() {
+    let server = MockSolana::start().await;
+    let (events, mut received) = event_hub();
+    let monitor = spawn_pump(&server.config(), events).await;
+    server.wait_for_subscriptions(1).await;
+
+    let subscription = &server.subscriptions()[0];
+    assert_eq!(subscription[0]["mentions"][0], "6EF8rrecthR5Dkzon8Nwu78hRvfCKubJ14M5uBEwF6P");
+
+    let mint = pubkey();
+    pump_launch(&server, 1, &mint);
+    match next_event(&mut received).await {
+        MonitorEvent::Launch(launch) => {
+            assert_eq!(launch.contract_address, mint);
+            assert_eq!(launch.symbol.as_deref(), Some("MOCK"));
+            assert_eq!(launch.uri.as_deref(), Some("https://example.com/mock.json"));
+            assert_eq!(launch.signature, signature(1));
+            assert_eq!(launch.platform, Platform::PumpFun);
+        },
+        other => panic!("expected a launch, got {:?}", other),
+    }
+    monitor.abort();
+}
+
+#[tokio::test]
+async fn pump_skips_duplicates_and_failed_transactions() {
+    let server = MockSolana::start().await;
+    let (events, mut received) = event_hub();
+    let monitor = spawn_pump(&server.config(), events).await;
+    server.wait_for_subscriptions(1).await;
+
+    let mint = pubkey();
+    pump_launch(&server, 1, &mint);
+    pump_launch(&server, 1, &mint);
+    assert!(matches!(next_event(&mut received).await, MonitorEvent::Launch(_)));
+    assert_quiet(&mut received, QUIET).await;
+
+    let logs = pump_create_logs("Failed", "FAIL", "", &pubkey(), &pubkey());
+    let failed = serde_json::json!({ "InstructionError": [0, { "Custom": 6000 }] });
+    server.push(&logs_notification(&signature(2), &logs, 1002, failed));
+    assert_quiet(&mut received, QUIET).await;
+    monitor.abort();
+}
+
+#[tokio::test]
+async fn pump_survives_malformed_frames() {
+    let server = MockSolana::start().await;
+    let (events, mut received) = event_hub();
+    let monitor = spawn_pump(&server.config(), events).await;
+    server.wait_for_subscriptions(1).await;
+
+    server.push_raw("{not json");
+    server.push_raw("[]");
+    let mint = pubkey();
+    pump_launch(&server, 1, &mint);
+
+    assert!(matches!(next_event(&mut received).await, MonitorEvent::Launch(launch) if launch.contract_address == mint));
+    assert_eq!(server.connections(), 1);
+    monitor.abort();
+}
+
+#[tokio::test]
+async fn pump_skips_undecodable_event_without_dropping_the_launch() {
+    let server = MockSolana::start().await;
+    let (events, mut received) = event_hub();
+    let monitor = spawn_pump(&server.config(), events).await;
+    server.wait_for_subscriptions(1).await;
+
+    let mint = pubkey();
+    let mut logs = pump_create_logs("Mock Token", "MOCK", "https://example.com/mock.json", &mint, &pubkey());
+    logs.insert(2, pump_truncated_trade_log());
+    server.push(&logs_notification(&signature(1), &logs, 1001, Value::Null));
+
+    assert!(matches!(next_event(&mut received).await, MonitorEvent::Launch(launch) if launch.contract_address == mint));
+    monitor.abort();
+}
+
+#[tokio::test]
+async fn pump_resubscribes_after_disconnect() {
+    let server = MockSolana::start().await;
+    let (events, mut received) = event_hub();
+    let monitor = spawn_pump(&server.config(), events).await;
+    server.wait_for_subscriptions(1).await;
+
+    server.disconnect_all();
+    server.wait_for_subscriptions(2).await;
+    assert_eq!(server.connections(), 2);
+
+    let mint = pubkey();
+    pump_launch(&server, 1, &mint);
+    assert!(matches!(next_event(&mut received).await, MonitorEvent::Launch(launch) if launch.contract_address == mint));
+    monitor.abort();
+}
+
+// ========================================================================
+// RAYDIUM LAUNCHLAB
+// ========================================================================
+
+#[tokio::test]
+async fn raydium_extracts_mint_from_fetched_buy() {
+    let server = MockSolana::start().await;
+    let (events, mut received) = event_hub();
+    let monitor = spawn_raydium(&server.config(), events).await;
+    server.wait_for_subscriptions(1).await;
+
+    let (mint, trader) = (pubkey(), pubkey());
+    server.add_transaction(&signature(1), launchlab_buy_transaction(&mint, &trader, 500_000_000, 1_234_567));
+    server.add_transaction(&signature(2), launchlab_buy_transaction(&mint, &trader, 100_000_000, 200_000));
+    launchlab_buy(&server, 1);
+
+    match next_event(&mut received).await {
+        MonitorEvent::Launch(launch) => {
+            assert_eq!(launch.contract_address, mint);
+            assert_eq!(launch.platform, Platform::Raydium);
+        },
+        other => panic!("expected a launch, got {:?}", other),
+    }
+    match next_event(&mut received).await {
+        MonitorEvent::Trade(trade) => {
+            assert_eq!(trade.mint, mint);
+            assert_eq!(trade.trader.as_deref(), Some(trader.as_str()));
+            assert!(trade.is_buy);
+            assert_eq!((trade.sol_amount, trade.token_amount), (Some(500_000_000), Some(1_234_567)));
+        },
+        other => panic!("expected a trade, got {:?}", other),
+    }
+
+    // Second buy of a known mint is only a trade
+    launchlab_buy(&server, 2);
+    assert!(matches!(next_event(&mut received).await, MonitorEvent::Trade(trade) if trade.signature == signature(2)));
+    assert_quiet(&mut received, QUIET).await;
+    monitor.abort();
+}
+
+#[tokio::test]
+async fn raydium_fetches_each_signature_once() {
+    let server = MockSolana::start().await;
+    let (events, mut received) = event_hub();
+    let monitor = spawn_raydium(&server.config(), events).await;
+    server.wait_for_subscriptions(1).await;
+
+    server.add_transaction(&signature(1), launchlab_buy_transaction(&pubkey(), &pubkey(), 1, 1));
+    launchlab_buy(&server, 1);
+    launchlab_buy(&server, 1);
+
+    assert!(matches!(next_event(&mut received).await, MonitorEvent::Launch(_)));
+    assert!(matches!(next_event(&mut received).await, MonitorEvent::Trade(_)));
+    assert_quiet(&mut received, QUIET).await;
+    assert_eq!(server.calls("getTransaction"), 1);
+    monitor.abort();
+}
+
+#[tokio::test]
+async fn raydium_throttles_fetches() {
+    let server = MockSolana::start().await;
+    let mut config = server.config();
+    // One token, then nothing for a minute: the first goes, the second waits, the third is dropped
+    config.rpc.burst = 1;
+    config.rpc.requests_per_sec = 1.0 / 60.0;
+    config.rpc.max_queued = 1;
+    let (events, mut received) = event_hub();
+    let monitor = spawn_raydium(&config, events).await;
+    server.wait_for_subscriptions(1).await;
+
+    for seed in 1..=3 {
+        server.add_transaction(&signature(seed), launchlab_buy_transaction(&pubkey(), &pubkey(), 1, 1));
+        launchlab_buy(&server, seed);
+    }
+
+    assert!(matches!(next_event(&mut received).await, MonitorEvent::Launch(launch) if launch.signature == signature(1)));
+    assert!(matches!(next_event(&mut received).await, MonitorEvent::Trade(_)));
+    assert_quiet(&mut received, QUIET).await;
+    assert_eq!(server.calls("getTransaction"), 1);
+    monitor.abort();
+}
+
+#[tokio::test]
+async fn raydium_recovers_from_rpc_faults() {
+    let server = MockSolana::start().await;
+    let (events, mut received) = event_hub();
+    let monitor = spawn_raydium(&server.config(), events).await;
+    server.wait_for_subscriptions(1).await;
+
+    // 429s are retried after a backoff; a malformed body costs only the fetch it hit
+    server.inject(Fault::TooManyRequests);
+    server.inject(Fault::RpcError(429));
+    server.inject(Fault::Malformed);
+    for seed in 1..=4 {
+        server.add_transaction(&signature(seed), launchlab_buy_transaction(&pubkey(), &pubkey(), 1, 1));
+        launchlab_buy(&server, seed);
+    }
+
+    let mut launches = Vec::new();
+    for _ in 0..6 {
+        if let MonitorEvent::Launch(launch) = next_event(&mut received).await {
+            launches.push(launch.signature);
+        }
+    }
+    assert_eq!(launches.len(), 3);
+    assert_quiet(&mut received, QUIET).await;
+    assert_eq!(server.calls("getTransaction"), 6);
+    monitor.abort();
+}
+
+#[tokio::test]
+async fn raydium_fetches_concurrently() {
+    let server = MockSolana::start().await;
+    let (events, mut received) = event_hub();
+    let monitor = spawn_raydium(&server.config(), events).await;
+    server.wait_for_subscriptions(1).await;
+
+    server.inject(Fault::Stall(Duration::from_millis(1500)));
+    for seed in 1..=2 {
+        server.add_transaction(&signature(seed), launchlab_buy_transaction(&pubkey(), &pubkey(), 1, 1));
+        launchlab_buy(&server, seed);
+    }
+
+    // The stalled lookup doesn't hold up the one behind it
+    assert!(matches!(next_event(&mut received).await, MonitorEvent::Launch(launch) if launch.signature == signature(2)));
+    monitor.abort();
+}
+
+#[tokio::test]
+async fn raydium_gives_up_on_stalled_fetch() {
+    let server = MockSolana::start().await;
+    let mut config = server.config();
+    config.rpc.max_retries = 0;
+    let (events, mut received) = event_hub();
+    let monitor = spawn_raydium(&config, events).await;
+    server.wait_for_subscriptions(1).await;
+
+    server.inject(Fault::Stall(Duration::from_secs(3)));
+    server.add_transaction(&signature(1), launchlab_buy_transaction(&pubkey(), &pubkey(), 1, 1));
+    launchlab_buy(&server, 1);
+
+    // request_timeout_secs = 1 abandons the fetch well before the stall ends
+    assert_quiet(&mut received, Duration::from_secs(2)).await;
+    assert_eq!(server.calls("getTransaction"), 1);
+    monitor.abort();
+}
+
+#[tokio::test]
+async fn raydium_resubscribes_after_disconnect() {
+    let server = MockSolana::start().await;
+    let (events, mut received) = event_hub();
+    let monitor = spawn_raydium(&server.config(), events).await;
+    server.wait_for_subscriptions(1).await;
+
+    server.disconnect_all();
+    server.wait_for_subscriptions(2).await;
+
+    server.add_transaction(&signature(1), launchlab_buy_transaction(&pubkey(), &pubkey(), 1, 1));
+    launchlab_buy(&server, 1);
+    assert!(matches!(next_event(&mut received).await, MonitorEvent::Launch(_)));
+    monitor.abort();
+}
+
+// ========================================================================
+// UNTRUSTED INPUT
+// ========================================================================
+
+#[tokio::test]
+async fn short_signatures_do_not_panic() {
+    let server = MockSolana::start().await;
+    let (events, mut received) = event_hub();
+    let pump = spawn_pump(&server.config(), events.clone()).await;
+    let raydium = spawn_raydium(&server.config(), events).await;
+    server.wait_for_subscriptions(2).await;
+
+    let logs = pump_create_logs("Short", "SHRT", "", &pubkey(), &pubkey());
+    server.push(&logs_notification("abc", &logs, 1, Value::Null));
+    assert!(matches!(next_event(&mut received).await, MonitorEvent::Launch(launch) if launch.signature == "abc"));
+
+    server.add_transaction("é", launchlab_buy_transaction(&pubkey(), &pubkey(), 1, 1));
+    server.push(&logs_notification("é", &launchlab_buy_logs(), 2, Value::Null));
+    assert!(matches!(next_event(&mut received).await, MonitorEvent::Launch(launch) if launch.signature == "é"));
+    assert!(matches!(next_event(&mut received).await, MonitorEvent::Trade(_)));
+
+    assert!(!pump.is_finished() && !raydium.is_finished());
+    pump.abort();
+    raydium.abort();
+}
@@ -0,0 +1,78 @@
+//! The shared RPC scheduler against the mock RPC: coalescing, concurrency and 429 handling.
+
+mod support;
+
+use serde_json::json;
+use tokio::time::{Duration, Instant};
+
+use blazing_monitor::rpc_scheduler::{Priority, RpcError, RpcScheduler};
+
+use support::*;
+
+#[tokio::test]
+async fn coalesces_identical_requests() {
+    let server = MockSolana::start().await;
+    server.add_transaction(&signature(1), json!({ "slot": 7 }));
+    server.inject(Fault::Stall(Duration::from_millis(200)));
+    let rpc = RpcScheduler::shared(&server.config()).unwrap();
+
+    let params = || json!([signature(1), { "encoding": "jsonParsed" }]);
+    let (first, second, other) = tokio::join!(
+        rpc.result(Priority::Trade, "getTransaction", params()),
+        rpc.result(Priority::Launch, "getTransaction", params()),
+        rpc.result(Priority::Trade, "getTransaction", json!([signature(2), { "encoding": "jsonParsed" }])),
+    );
+    assert_eq!(first.unwrap(), json!({ "slot": 7 }));
+    assert_eq!(second.unwrap(), json!({ "slot": 7 }));
+    assert!(other.unwrap().is_null());
+    assert_eq!(server.calls("getTransaction"), 2);
+}
+
+#[tokio::test]
+async fn runs_requests_concurrently() {
+    let server = MockSolana::start().await;
+    server.answer("getSlot", json!(42));
+    for _ in 0..4 {
+        server.inject(Fault::Stall(Duration::from_millis(500)));
+    }
+    let rpc = RpcScheduler::shared(&server.config()).unwrap();
+
+    let started = Instant::now();
+    let calls = (0..4).map(|i| rpc.result(Priority::Launch, "getSlot", json!([{ "minContextSlot": i }])));
+    for result in futures_util::future::join_all(calls).await {
+        assert_eq!(result.unwrap(), json!(42));
+    }
+    assert!(started.elapsed() < Duration::from_millis(1000), "took {:?}", started.elapsed());
+}
+
+#[tokio::test]
+async fn honors_retry_after() {
+    let server = MockSolana::start().await;
+    server.answer("getSlot", json!(42));
+    server.inject(Fault::RetryAfter(1));
+    let rpc = RpcScheduler::shared(&server.config()).unwrap();
+
+    let started = Instant::now();
+    assert_eq!(rpc.result(Priority::Launch, "getSlot", json!([])).await.unwrap(), json!(42));
+    assert!(started.elapsed() >= Duration::from_secs(1));
+    assert_eq!(server.calls("getSlot"), 2);
+}
+
+#[tokio::test]
+async fn gives_up_after_max_retries() {
+    let server = MockSolana::start().await;
+    let mut config = server.config();
+    config.rpc.max_retries = 1;
+    for _ in 0..3 {
+        server.inject(Fault::TooManyRequests);
+    }
+    let rpc = RpcScheduler::shared(&config).unwrap();
+
+    assert_eq!(rpc.call(Priority::Launch, "getSlot", json!([])).await.unwrap_err(), RpcError::RateLimited);
+    assert_eq!(server.calls("getSlot"), 2);
+
+    // Other JSON-RPC errors are the caller's to read
+    server.inject(Fault::RpcError(-32602));
+    let response = rpc.call(Priority::Launch, "getSlot", json!([])).await.unwrap();
+    assert_eq!(response["error"]["code"], -32602);
+}
@@ -0,0 +1,371 @@
+//! In-process mock of the Solana RPC/WebSocket endpoints the monitors talk to.
+//!
+//! One port serves both sides, like a real RPC node: `GET /` upgrades to the
+//! PubSub WebSocket, `POST /` is JSON-RPC. Tests script the WebSocket frames,
+//! the `getTransaction` fixtures and the faults the RPC side injects.
+
+#![allow(dead_code)]
+
+use std::collections::{HashMap, VecDeque};
+use std::net::SocketAddr;
+use std::sync::atomic::{AtomicU64, AtomicUsize, Ordering};
+use std::sync::{Arc, Mutex};
+
+use axum::extract::ws::{Message, WebSocket, WebSocketUpgrade};
+use axum::extract::State;
+use axum::http::{header, StatusCode};
+use axum::response::{IntoResponse, Response};
+use axum::routing::get;
+use axum::{Json, Router};
+use base64::{engine::general_purpose, Engine as _};
+use serde_json::{json, Value};
+use solana_sdk::pubkey::Pubkey;
+use tokio::sync::broadcast;
+use tokio::task::JoinHandle;
+use tokio::time::{sleep, Duration, Instant};
+
+use blazing_monitor::config::Config;
+use blazing_monitor::event_store::EventHub;
+use blazing_monitor::types::{
+    MonitorEvent, PUMP_CREATE_EVENT_DISCRIMINATOR, PUMP_TRADE_EVENT_DISCRIMINATOR, RAYDIUM_LAUNCHPAD_AUTHORITY, RAYDIUM_PROGRAM_ID,
+};
+
+// ========================================================================
+// MOCK SERVER
+// ========================================================================
+
+/// What the RPC side does with the next request instead of answering it.
+#[derive(Debug, Clone)]
+pub enum Fault {
+    /// HTTP 429 with an empty body
+    TooManyRequests,
+    /// HTTP 429 asking to come back after this many seconds
+    RetryAfter(u64),
+    /// HTTP 200 carrying a JSON-RPC error object
+    RpcError(i64),
+    /// Sleep before answering normally
+    Stall(Duration),
+    /// HTTP 200 with a body that isn't JSON
+    Malformed,
+}
+
+#[derive(Debug, Clone)]
+enum Push {
+    Frame(String),
+    // Drop every connection without a close frame
+    Disconnect,
+}
+
+#[derive(Default)]
+struct MockState {
+    subscriptions: Mutex<Vec<Value>>,
+    connections: AtomicUsize,
+    next_subscription: AtomicU64,
+    transactions: Mutex<HashMap<String, Value>>,
+    // Fixed `result` for any other method
+    answers: Mutex<HashMap<String, Value>>,
+    faults: Mutex<VecDeque<Fault>>,
+    calls: Mutex<Vec<String>>,
+}
+
+pub struct MockSolana {
+    addr: SocketAddr,
+    state: Arc<MockState>,
+    pushes: broadcast::Sender<Push>,
+    server: JoinHandle<()>,
+}
+
+impl MockSolana {
+    pub async fn start() -> Self {
+        init_tracing();
+        let state = Arc::new(MockState::default());
+        let (pushes, _) = broadcast::channel(256);
+
+        let router = Router::new()
+            .route("/", get(ws_upgrade).post(rpc))
+            .with_state((state.clone(), pushes.clone()));
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let addr = listener.local_addr().unwrap();
+        let server = tokio::spawn(async move {
+            axum::serve(listener, router).await.unwrap();
+        });
+
+        Self { addr, state, pushes, server }
+    }
+
+    pub fn ws_url(&self) -> String {
+        format!("ws://{}/", self.addr)
+    }
+
+    pub fn rpc_url(&self) -> String {
+        format!("http://{}/", self.addr)
+    }
+
+    /// Config pointing both endpoints here, with every delay the monitors sleep on zeroed
+    /// and RPC backoff cut to milliseconds.
+    pub fn config(&self) -> Config {
+        let mut config = Config::default();
+        config.endpoints.ws_url = self.ws_url();
+        config.endpoints.rpc_url = Some(self.rpc_url());
+        config.pump.reconnect_delay_secs = 0;
+        config.pump.error_reconnect_delay_secs = 0;
+        config.raydium.request_timeout_secs = 1;
+        config.rpc.backoff_ms = 10;
+        config.raydium.reconnect_delay_secs = 0;
+        config.raydium.error_reconnect_delay_secs = 0;
+        config
+    }
+
+    /// Send a text frame to every connected client.
+    pub fn push(&self, frame: &Value) {
+        self.push_raw(&frame.to_string());
+    }
+
+    pub fn push_raw(&self, frame: &str) {
+        let _ = self.pushes.send(Push::Frame(frame.to_string()));
+    }
+
+    pub fn disconnect_all(&self) {
+        let _ = self.pushes.send(Push::Disconnect);
+    }
+
+    /// Answer `getTransaction` for `signature` with this `result`.
+    pub fn add_transaction(&self, signature: &str, result: Value) {
+        self.state.transactions.lock().unwrap().insert(signature.to_string(), result);
+    }
+
+    /// Answer every `method` call with this `result`.
+    pub fn answer(&self, method: &str, result: Value) {
+        self.state.answers.lock().unwrap().insert(method.to_string(), result);
+    }
+
+    /// Queue a fault for the next RPC request (faults are used up in order).
+    pub fn inject(&self, fault: Fault) {
+        self.state.faults.lock().unwrap().push_back(fault);
+    }
+
+    pub fn connections(&self) -> usize {
+        self.state.connections.load(Ordering::SeqCst)
+    }
+
+    pub fn subscriptions(&self) -> Vec<Value> {
+        self.state.subscriptions.lock().unwrap().clone()
+    }
+
+    pub fn calls(&self, method: &str) -> usize {
+        self.state.calls.lock().unwrap().iter().filter(|call| *call == method).count()
+    }
+
+    /// Wait until `count` logsSubscribe requests have arrived.
+    pub async fn wait_for_subscriptions(&self, count: usize) {
+        wait_until(|| self.subscriptions().len() >= count, "logsSubscribe").await;
+    }
+
+    pub async fn wait_for_calls(&self, method: &str, count: usize) {
+        wait_until(|| self.calls(method) >= count, method).await;
+    }
+}
+
+impl Drop for MockSolana {
+    fn drop(&mut self) {
+        self.server.abort();
+    }
+}
+
+type Shared = (Arc<MockState>, broadcast::Sender<Push>);
+
+async fn ws_upgrade(ws: WebSocketUpgrade, State((state, pushes)): State<Shared>) -> Response {
+    ws.on_upgrade(move |socket| serve_socket(socket, state, pushes.subscribe()))
+}
+
+async fn serve_socket(mut socket: WebSocket, state: Arc<MockState>, mut pushes: broadcast::Receiver<Push>) {
+    state.connections.fetch_add(1, Ordering::SeqCst);
+    loop {
+        tokio::select! {
+            message = socket.recv() => {
+                let Some(Ok(Message::Text(text))) = message else { break };
+                let Ok(request) = serde_json::from_str::<Value>(&text) else { continue };
+                if request["method"] == "logsSubscribe" {
+                    let id = state.next_subscription.fetch_add(1, Ordering::SeqCst);
+                    state.subscriptions.lock().unwrap().push(request["params"].clone());
+                    let reply = json!({ "jsonrpc": "2.0", "result": id, "id": request["id"] });
+                    if socket.send(Message::Text(reply.to_string().into())).await.is_err() {
+                        break;
+                    }
+                }
+            }
+            push = pushes.recv() => match push {
+                Ok(Push::Frame(frame)) => {
+                    if socket.send(Message::Text(frame.into())).await.is_err() {
+                        break;
+                    }
+                }
+                // Dropping the socket skips the close handshake, like a dead node
+                Ok(Push::Disconnect) | Err(broadcast::error::RecvError::Closed) => break,
+                Err(broadcast::error::RecvError::Lagged(_)) => {}
+            }
+        }
+    }
+}
+
+async fn rpc(State((state, _)): State<Shared>, Json(request): Json<Value>) -> Response {
+    let method = request["method"].as_str().unwrap_or_default().to_string();
+    state.calls.lock().unwrap().push(method.clone());
+
+    let fault = state.faults.lock().unwrap().pop_front();
+    match fault {
+        Some(Fault::TooManyRequests) => return StatusCode::TOO_MANY_REQUESTS.into_response(),
+        Some(Fault::RetryAfter(secs)) => {
+            return (StatusCode::TOO_MANY_REQUESTS, [(header::RETRY_AFTER, secs.to_string())]).into_response();
+        }
+        Some(Fault::RpcError(code)) => {
+            let body = json!({ "jsonrpc": "2.0", "error": { "code": code, "message": "mock error" }, "id": request["id"] });
+            return Json(body).into_response();
+        }
+        Some(Fault::Malformed) => return (StatusCode::OK, "{\"jsonrpc\": \"2.0\", \"resu").into_response(),
+        Some(Fault::Stall(duration)) => sleep(duration).await,
+        None => {}
+    }
+
+    let result = match method.as_str() {
+        "getTransaction" => request["params"][0].as_str()
+            .and_then(|signature| state.transactions.lock().unwrap().get(signature).cloned())
+            .unwrap_or(Value::Null),
+        other => state.answers.lock().unwrap().get(other).cloned().unwrap_or(Value::Null),
+    };
+    Json(json!({ "jsonrpc": "2.0", "result": result, "id": request["id"] })).into_response()
+}
+
+// Log lines format their arguments only when a subscriber is listening
+fn init_tracing() {
+    let _ = tracing_subscriber::fmt()
+        .with_max_level(tracing::Level::DEBUG)
+        .with_test_writer()
+        .try_init();
+}
+
+// ========================================================================
+// WAITING
+// ========================================================================
+
+const WAIT: Duration = Duration::from_secs(5);
+
+pub async fn wait_until(mut condition: impl FnMut() -> bool, what: &str) {
+    let deadline = Instant::now() + WAIT;
+    while !condition() {
+        assert!(Instant::now() < deadline, "timed out waiting for {}", what);
+        sleep(Duration::from_millis(10)).await;
+    }
+}
+
+/// Next event on `events`, panicking if none arrives in time.
+pub async fn next_event(events: &mut broadcast::Receiver<MonitorEvent>) -> MonitorEvent {
+    tokio::time::timeout(WAIT, events.recv()).await
+        .expect("timed out waiting for an event")
+        .expect("event hub closed")
+}
+
+/// Panic if any event arrives within `window`.
+pub async fn assert_quiet(events: &mut broadcast::Receiver<MonitorEvent>, window: Duration) {
+    if let Ok(event) = tokio::time::timeout(window, events.recv()).await {
+        panic!("unexpected event: {:?}", event);
+    }
+}
+
+pub fn event_hub() -> (EventHub, broadcast::Receiver<MonitorEvent>) {
+    let events = EventHub::new();
+    let receiver = events.subscribe();
+    (events, receiver)
+}
+
+// ========================================================================
+// FIXTURES
+// ========================================================================
+
+/// Signatures are 64-byte base58 strings; any unique one will do.
+pub fn signature(seed: u8) -> String {
+    solana_sdk::bs58::encode([seed; 64]).into_string()
+}
+
+pub fn pubkey() -> String {
+    Pubkey::new_unique().to_string()
+}
+
+/// A `logsNotification` frame as sent for `logsSubscribe`.
+pub fn logs_notification(signature: &str, logs: &[String], slot: u64, err: Value) -> Value {
+    json!({
+        "jsonrpc": "2.0",
+        "method": "logsNotification",
+        "params": {
+            "result": {
+                "context": { "slot": slot },
+                "value": { "signature": signature, "err": err, "logs": logs }
+            },
+            "subscription": 0
+        }
+    })
+}
+
+/// Logs of a pump.fun `create`, with its CreateEvent as `Program data`.
+pub fn pump_create_logs(name: &str, symbol: &str, uri: &str, mint: &str, creator: &str) -> Vec<String> {
+    let mut data = PUMP_CREATE_EVENT_DISCRIMINATOR.to_vec();
+    for field in [name, symbol, uri] {
+        data.extend((field.len() as u32).to_le_bytes());
+        data.extend(field.as_bytes());
+    }
+    data.extend(mint.parse::<Pubkey>().unwrap().to_bytes());
+    data.extend(Pubkey::new_unique().to_bytes()); // bonding curve
+    data.extend(creator.parse::<Pubkey>().unwrap().to_bytes());
+
+    vec![
+        "Program 6EF8rrecthR5Dkzon8Nwu78hRvfCKubJ14M5uBEwF6P invoke [1]".to_string(),
+        "Program log: Instruction: Create".to_string(),
+        format!("Program data: {}", general_purpose::STANDARD.encode(data)),
+        "Program 6EF8rrecthR5Dkzon8Nwu78hRvfCKubJ14M5uBEwF6P success".to_string(),
+    ]
+}
+
+/// A pump.fun TradeEvent `Program data` line cut off right after its mint.
+pub fn pump_truncated_trade_log() -> String {
+    let mut data = PUMP_TRADE_EVENT_DISCRIMINATOR.to_vec();
+    data.extend(Pubkey::new_unique().to_bytes());
+    format!("Program data: {}", general_purpose::STANDARD.encode(data))
+}
+
+/// Logs the Raydium monitor's pre-filter accepts as a LaunchLab buy.
+pub fn launchlab_buy_logs() -> Vec<String> {
+    vec![
+        format!("Program {} invoke [1]", RAYDIUM_PROGRAM_ID),
+        "Program log: Instruction: buy_exact_in".to_string(),
+        format!("Program {} success", RAYDIUM_PROGRAM_ID),
+    ]
+}
+
+/// `getTransaction` (jsonParsed) result for a LaunchLab buy_exact_in.
+pub fn launchlab_buy_transaction(mint: &str, trader: &str, lamports: u64, tokens: u64) -> Value {
+    const BUY_EXACT_IN: [u8; 8] = [250, 234, 13, 123, 213, 156, 19, 236];
+    let transfer = |mint: &str, authority: &str, amount: u64| json!({
+        "program": "spl-token",
+        "programId": "TokenkegQfeZyiNwAJbNbGKPFXCWuBvf9Ss623VQ5DA",
+        "parsed": {
+            "type": "transferChecked",
+            "info": { "mint": mint, "authority": authority, "tokenAmount": { "amount": amount.to_string() } }
+        }
+    });
+
+    json!({
+        "slot": 1,
+        "blockTime": 1_700_000_000,
+        "transaction": { "message": {
+            "accountKeys": [{ "pubkey": trader, "signer": true, "writable": true }],
+            "instructions": [{ "programId": RAYDIUM_PROGRAM_ID, "data": solana_sdk::bs58::encode(BUY_EXACT_IN).into_string() }]
+        }},
+        "meta": {
+            "err": null,
+            "innerInstructions": [{ "index": 0, "instructions": [
+                transfer("So11111111111111111111111111111111111111112", trader, lamports),
+                transfer(mint, RAYDIUM_LAUNCHPAD_AUTHORITY, tokens),
+            ]}]
+        }
+    })
+}
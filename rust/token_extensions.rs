@@ -0,0 +1,402 @@
+//! Token-2022 mint extensions and the risk flags derived from them.
+//! Pure decoding over raw account data, no I/O.
+
+use anyhow::{anyhow, Result};
+use solana_sdk::pubkey::Pubkey;
+
+use crate::token_identity::MintAccount;
+use crate::types::{RiskFlag, RiskSeverity};
+
+// Token-2022 pads the 82-byte mint to the token account size, then an
+// account type byte, then type-length-value extensions
+const ACCOUNT_TYPE_OFFSET: usize = 165;
+const ACCOUNT_TYPE_MINT: u8 = 1;
+const TLV_START: usize = ACCOUNT_TYPE_OFFSET + 1;
+
+// Transfer fees above this are treated as a trap rather than a tax
+const HIGH_TRANSFER_FEE_BPS: u16 = 500;
+
+// ========================================================================
+// EXTENSIONS
+// ========================================================================
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum Extension {
+    TransferFeeConfig {
+        authority: Option<Pubkey>,
+        // Larger of the older/newer fee - either may apply depending on the epoch
+        basis_points: u16,
+        maximum_fee: u64,
+    },
+    MintCloseAuthority(Option<Pubkey>),
+    ConfidentialTransferMint,
+    DefaultAccountState { frozen: bool },
+    NonTransferable,
+    InterestBearingConfig,
+    PermanentDelegate(Option<Pubkey>),
+    TransferHook { authority: Option<Pubkey>, program: Option<Pubkey> },
+    MetadataPointer(Option<Pubkey>),
+    TokenMetadata {
+        update_authority: Option<Pubkey>,
+        name: String,
+        symbol: String,
+        uri: String,
+    },
+    ScaledUiAmount,
+    Pausable { authority: Option<Pubkey>, paused: bool },
+    Other(u16),
+}
+
+impl Extension {
+    pub fn name(&self) -> String {
+        match self {
+            Extension::TransferFeeConfig { .. } => "transfer_fee_config".to_string(),
+            Extension::MintCloseAuthority(_) => "mint_close_authority".to_string(),
+            Extension::ConfidentialTransferMint => "confidential_transfer_mint".to_string(),
+            Extension::DefaultAccountState { .. } => "default_account_state".to_string(),
+            Extension::NonTransferable => "non_transferable".to_string(),
+            Extension::InterestBearingConfig => "interest_bearing_config".to_string(),
+            Extension::PermanentDelegate(_) => "permanent_delegate".to_string(),
+            Extension::TransferHook { .. } => "transfer_hook".to_string(),
+            Extension::MetadataPointer(_) => "metadata_pointer".to_string(),
+            Extension::TokenMetadata { .. } => "token_metadata".to_string(),
+            Extension::ScaledUiAmount => "scaled_ui_amount".to_string(),
+            Extension::Pausable { .. } => "pausable".to_string(),
+            Extension::Other(extension_type) => format!("unknown_{}", extension_type),
+        }
+    }
+}
+
+/// Extensions on a Token-2022 mint. A plain 82-byte mint has none.
+pub fn parse_extensions(data: &[u8]) -> Result<Vec<Extension>> {
+    if data.len() <= ACCOUNT_TYPE_OFFSET {
+        return Ok(Vec::new());
+    }
+    if data[ACCOUNT_TYPE_OFFSET] != ACCOUNT_TYPE_MINT {
+        return Err(anyhow!("Not a mint account (account type {})", data[ACCOUNT_TYPE_OFFSET]));
+    }
+
+    let mut extensions = Vec::new();
+    let mut offset = TLV_START;
+    while offset + 4 <= data.len() {
+        let extension_type = u16::from_le_bytes([data[offset], data[offset + 1]]);
+        let length = u16::from_le_bytes([data[offset + 2], data[offset + 3]]) as usize;
+        // Type 0 is zeroed space reserved for future extensions
+        if extension_type == 0 {
+            break;
+        }
+        let value = data.get(offset + 4..offset + 4 + length)
+            .ok_or_else(|| anyhow!("Extension {} truncated", extension_type))?;
+        extensions.push(decode_extension(extension_type, value)?);
+        offset += 4 + length;
+    }
+    Ok(extensions)
+}
+
+fn decode_extension(extension_type: u16, value: &[u8]) -> Result<Extension> {
+    let need = |len: usize| {
+        if value.len() < len {
+            Err(anyhow!("Extension {} too short: {} bytes", extension_type, value.len()))
+        } else {
+            Ok(())
+        }
+    };
+
+    Ok(match extension_type {
+        1 => {
+            // authority | withdraw authority | withheld | older (epoch, max, bps) | newer (epoch, max, bps)
+            need(108)?;
+            let older_maximum = u64_at(value, 80);
+            let older_bps = u16::from_le_bytes([value[88], value[89]]);
+            let newer_maximum = u64_at(value, 98);
+            let newer_bps = u16::from_le_bytes([value[106], value[107]]);
+            Extension::TransferFeeConfig {
+                authority: optional_pubkey(value, 0),
+                basis_points: older_bps.max(newer_bps),
+                maximum_fee: older_maximum.max(newer_maximum),
+            }
+        },
+        3 => {
+            need(32)?;
+            Extension::MintCloseAuthority(optional_pubkey(value, 0))
+        },
+        4 => Extension::ConfidentialTransferMint,
+        6 => {
+            need(1)?;
+            Extension::DefaultAccountState { frozen: value[0] == 2 }
+        },
+        9 => Extension::NonTransferable,
+        10 => Extension::InterestBearingConfig,
+        12 => {
+            need(32)?;
+            Extension::PermanentDelegate(optional_pubkey(value, 0))
+        },
+        14 => {
+            need(64)?;
+            Extension::TransferHook {
+                authority: optional_pubkey(value, 0),
+                program: optional_pubkey(value, 32),
+            }
+        },
+        18 => {
+            need(64)?;
+            Extension::MetadataPointer(optional_pubkey(value, 32))
+        },
+        19 => decode_token_metadata(value)?,
+        25 => Extension::ScaledUiAmount,
+        26 => {
+            need(33)?;
+            Extension::Pausable {
+                authority: optional_pubkey(value, 0),
+                paused: value[32] != 0,
+            }
+        },
+        other => Extension::Other(other),
+    })
+}
+
+// update_authority | mint | name | symbol | uri | additional metadata
+fn decode_token_metadata(value: &[u8]) -> Result<Extension> {
+    let mut offset = 64;
+    let mut read_string = || -> Result<String> {
+        let len_bytes = value.get(offset..offset + 4).ok_or_else(|| anyhow!("Token metadata truncated"))?;
+        let len = u32::from_le_bytes(len_bytes.try_into()?) as usize;
+        let bytes = value.get(offset + 4..offset + 4 + len).ok_or_else(|| anyhow!("Token metadata truncated"))?;
+        offset += 4 + len;
+        Ok(String::from_utf8_lossy(bytes).trim().to_string())
+    };
+
+    let name = read_string()?;
+    let symbol = read_string()?;
+    let uri = read_string()?;
+    Ok(Extension::TokenMetadata {
+        update_authority: optional_pubkey(value, 0),
+        name,
+        symbol,
+        uri,
+    })
+}
+
+// OptionalNonZeroPubkey: all zeros means unset
+fn optional_pubkey(value: &[u8], offset: usize) -> Option<Pubkey> {
+    let bytes: [u8; 32] = value.get(offset..offset + 32)?.try_into().ok()?;
+    (bytes != [0u8; 32]).then(|| Pubkey::new_from_array(bytes))
+}
+
+fn u64_at(value: &[u8], offset: usize) -> u64 {
+    u64::from_le_bytes(value[offset..offset + 8].try_into().expect("length checked"))
+}
+
+// ========================================================================
+// RISK FLAGS
+// ========================================================================
+
+/// Everything about a mint that lets someone other than the holder move,
+/// freeze, tax or inflate it.
+pub fn risk_flags(mint: &MintAccount, extensions: &[Extension]) -> Vec<RiskFlag> {
+    let mut flags = Vec::new();
+    if let Some(authority) = mint.mint_authority {
+        flags.push(RiskFlag::MintAuthority { authority: authority.to_string() });
+    }
+    if let Some(authority) = mint.freeze_authority {
+        flags.push(RiskFlag::FreezeAuthority { authority: authority.to_string() });
+    }
+
+    for extension in extensions {
+        let flag = match extension {
+            Extension::TransferFeeConfig { authority, basis_points, maximum_fee } => {
+                (*basis_points > 0 || authority.is_some()).then(|| RiskFlag::TransferFee {
+                    basis_points: *basis_points,
+                    maximum_fee: *maximum_fee,
+                    authority: authority.map(|a| a.to_string()),
+                })
+            },
+            Extension::PermanentDelegate(Some(delegate)) => {
+                Some(RiskFlag::PermanentDelegate { delegate: delegate.to_string() })
+            },
+            Extension::TransferHook { authority, program } if authority.is_some() || program.is_some() => {
+                Some(RiskFlag::TransferHook {
+                    program: program.map(|p| p.to_string()),
+                    authority: authority.map(|a| a.to_string()),
+                })
+            },
+            Extension::NonTransferable => Some(RiskFlag::NonTransferable),
+            Extension::DefaultAccountState { frozen: true } => Some(RiskFlag::DefaultFrozen),
+            Extension::MintCloseAuthority(Some(authority)) => {
+                Some(RiskFlag::MintCloseAuthority { authority: authority.to_string() })
+            },
+            Extension::Pausable { authority, paused } if authority.is_some() || *paused => {
+                Some(RiskFlag::Pausable { paused: *paused })
+            },
+            Extension::ConfidentialTransferMint => Some(RiskFlag::ConfidentialTransfers),
+            Extension::ScaledUiAmount => Some(RiskFlag::ScaledUiAmount),
+            Extension::InterestBearingConfig => Some(RiskFlag::InterestBearing),
+            _ => None,
+        };
+        flags.extend(flag);
+    }
+    flags
+}
+
+impl RiskFlag {
+    pub fn severity(&self) -> RiskSeverity {
+        match self {
+            RiskFlag::MintAuthority { .. }
+            | RiskFlag::FreezeAuthority { .. }
+            | RiskFlag::PermanentDelegate { .. }
+            | RiskFlag::NonTransferable
+            | RiskFlag::DefaultFrozen => RiskSeverity::High,
+            RiskFlag::TransferFee { basis_points, .. } if *basis_points >= HIGH_TRANSFER_FEE_BPS => RiskSeverity::High,
+            RiskFlag::TransferHook { program: Some(_), .. } => RiskSeverity::High,
+            RiskFlag::Pausable { paused: true } => RiskSeverity::High,
+            RiskFlag::TransferFee { .. }
+            | RiskFlag::TransferHook { .. }
+            | RiskFlag::Pausable { .. }
+            | RiskFlag::MintCloseAuthority { .. }
+            | RiskFlag::ScaledUiAmount => RiskSeverity::Medium,
+            RiskFlag::ConfidentialTransfers | RiskFlag::InterestBearing => RiskSeverity::Low,
+        }
+    }
+}
+
+/// Highest severity among the flags, `None` for a clean mint.
+pub fn risk_level(flags: &[RiskFlag]) -> Option<RiskSeverity> {
+    flags.iter().map(RiskFlag::severity).max()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::TokenProgram;
+
+    // Base mint (no authorities), padding, account type, then the given TLVs
+    fn mint_data(extensions: &[(u16, Vec<u8>)]) -> Vec<u8> {
+        let mut data = vec![0u8; ACCOUNT_TYPE_OFFSET];
+        data[44] = 6;
+        data[45] = 1;
+        data.push(ACCOUNT_TYPE_MINT);
+        for (extension_type, value) in extensions {
+            data.extend_from_slice(&extension_type.to_le_bytes());
+            data.extend_from_slice(&(value.len() as u16).to_le_bytes());
+            data.extend_from_slice(value);
+        }
+        data
+    }
+
+    fn mint(data: &[u8]) -> MintAccount {
+        MintAccount::decode(data, crate::types::TOKEN_2022_PROGRAM_ID).unwrap()
+    }
+
+    fn transfer_fee(bps: u16, maximum_fee: u64, authority: Option<Pubkey>) -> Vec<u8> {
+        let mut value = vec![0u8; 108];
+        if let Some(authority) = authority {
+            value[..32].copy_from_slice(authority.as_ref());
+        }
+        value[98..106].copy_from_slice(&maximum_fee.to_le_bytes());
+        value[106..108].copy_from_slice(&bps.to_le_bytes());
+        value
+    }
+
+    #[test]
+    fn plain_mint_is_clean() {
+        let data = vec![0u8; 82];
+        let account = MintAccount::decode(&data, crate::types::TOKEN_PROGRAM_ID).unwrap();
+        assert_eq!(account.token_program, TokenProgram::Token);
+        assert!(parse_extensions(&data).unwrap().is_empty());
+        assert!(risk_flags(&account, &[]).is_empty());
+        assert_eq!(risk_level(&[]), None);
+    }
+
+    #[test]
+    fn flags_dangerous_extensions() {
+        let delegate = Pubkey::new_unique();
+        let hook_program = Pubkey::new_unique();
+        let mut hook = vec![0u8; 64];
+        hook[32..].copy_from_slice(hook_program.as_ref());
+
+        let data = mint_data(&[
+            (1, transfer_fee(1_000, 5_000_000, None)),
+            (12, delegate.to_bytes().to_vec()),
+            (14, hook),
+            (9, Vec::new()),
+            (6, vec![2]),
+        ]);
+        let extensions = parse_extensions(&data).unwrap();
+        assert_eq!(extensions.len(), 5);
+
+        let flags = risk_flags(&mint(&data), &extensions);
+        assert_eq!(flags, vec![
+            RiskFlag::TransferFee { basis_points: 1_000, maximum_fee: 5_000_000, authority: None },
+            RiskFlag::PermanentDelegate { delegate: delegate.to_string() },
+            RiskFlag::TransferHook { program: Some(hook_program.to_string()), authority: None },
+            RiskFlag::NonTransferable,
+            RiskFlag::DefaultFrozen,
+        ]);
+        assert!(flags.iter().all(|flag| flag.severity() == RiskSeverity::High));
+    }
+
+    #[test]
+    fn unset_extensions_are_not_flagged() {
+        // Zero fee with no authority, zeroed delegate and hook, initialized default state
+        let data = mint_data(&[
+            (1, transfer_fee(0, 0, None)),
+            (12, vec![0u8; 32]),
+            (14, vec![0u8; 64]),
+            (6, vec![1]),
+        ]);
+        let extensions = parse_extensions(&data).unwrap();
+        assert!(risk_flags(&mint(&data), &extensions).is_empty());
+    }
+
+    #[test]
+    fn small_fee_with_authority_is_medium() {
+        let authority = Pubkey::new_unique();
+        let data = mint_data(&[(1, transfer_fee(100, 1_000, Some(authority)))]);
+        let flags = risk_flags(&mint(&data), &parse_extensions(&data).unwrap());
+        assert_eq!(risk_level(&flags), Some(RiskSeverity::Medium));
+    }
+
+    #[test]
+    fn flags_active_authorities() {
+        let authority = Pubkey::new_unique();
+        let mut data = mint_data(&[]);
+        data[0] = 1;
+        data[4..36].copy_from_slice(authority.as_ref());
+        data[46] = 1;
+        data[50..82].copy_from_slice(authority.as_ref());
+
+        let flags = risk_flags(&mint(&data), &[]);
+        assert_eq!(flags, vec![
+            RiskFlag::MintAuthority { authority: authority.to_string() },
+            RiskFlag::FreezeAuthority { authority: authority.to_string() },
+        ]);
+    }
+
+    #[test]
+    fn reads_token_metadata_and_skips_unknown() {
+        let mut metadata = vec![0u8; 64];
+        for value in ["Hook Cat", "HCAT", "https://example.com/h.json"] {
+            metadata.extend_from_slice(&(value.len() as u32).to_le_bytes());
+            metadata.extend_from_slice(value.as_bytes());
+        }
+        metadata.extend_from_slice(&0u32.to_le_bytes());
+
+        let data = mint_data(&[(99, vec![7, 7]), (19, metadata)]);
+        let extensions = parse_extensions(&data).unwrap();
+        assert_eq!(extensions[0], Extension::Other(99));
+        assert_eq!(extensions[1], Extension::TokenMetadata {
+            update_authority: None,
+            name: "Hook Cat".to_string(),
+            symbol: "HCAT".to_string(),
+            uri: "https://example.com/h.json".to_string(),
+        });
+        assert!(risk_flags(&mint(&data), &extensions).is_empty());
+    }
+
+    #[test]
+    fn rejects_truncated_extensions() {
+        let mut data = mint_data(&[(12, vec![0u8; 32])]);
+        data.truncate(data.len() - 1);
+        assert!(parse_extensions(&data).is_err());
+    }
+}
@@ -0,0 +1,438 @@
+use anyhow::{anyhow, Result};
+use base64::{Engine as _, engine::general_purpose};
+use serde_json::{json, Value};
+use solana_sdk::pubkey::Pubkey;
+use std::collections::{HashMap, VecDeque};
+use std::str::FromStr;
+use tokio::sync::broadcast::error::RecvError;
+use tokio::time::{interval, Duration, MissedTickBehavior};
+use tracing::{info, warn, debug};
+
+use crate::block_clock::now_ms;
+use crate::config::{Commitment, Config, IdentityConfig};
+use crate::event_store::{EventFeed, EventHub};
+use crate::launchlab_curve::{sol_pool_address, PoolState};
+use crate::metrics::metrics;
+use crate::rpc_scheduler::{Priority, RpcScheduler};
+use crate::token_extensions::{self, Extension};
+use crate::types::{
+    EventTiming, MonitorEvent, Platform, TokenIdentity, TokenLaunch, TokenProgram, TxStatus,
+    METAPLEX_PROGRAM_ID, TOKEN_2022_PROGRAM_ID, TOKEN_PROGRAM_ID,
+};
+
+// getMultipleAccounts accepts at most 100 keys per call
+const MAX_ACCOUNTS_PER_REQUEST: usize = 100;
+// SPL mint layout; Token-2022 mints start with the same 82 bytes
+const MINT_ACCOUNT_LEN: usize = 82;
+
+// ========================================================================
+// ACCOUNT DECODING
+// ========================================================================
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct MintAccount {
+    pub token_program: TokenProgram,
+    pub mint_authority: Option<Pubkey>,
+    pub supply: u64,
+    pub decimals: u8,
+    pub freeze_authority: Option<Pubkey>,
+}
+
+impl MintAccount {
+    /// `owner` is the account's owning program, which tells Token from Token-2022.
+    pub fn decode(data: &[u8], owner: &str) -> Result<Self> {
+        let token_program = match owner {
+            TOKEN_PROGRAM_ID => TokenProgram::Token,
+            TOKEN_2022_PROGRAM_ID => TokenProgram::Token2022,
+            other => return Err(anyhow!("Mint owned by unknown program: {}", other)),
+        };
+        if data.len() < MINT_ACCOUNT_LEN {
+            return Err(anyhow!("Invalid data length for mint: {}", data.len()));
+        }
+
+        Ok(Self {
+            token_program,
+            mint_authority: read_coption_pubkey(data, 0),
+            supply: u64::from_le_bytes(data[36..44].try_into()?),
+            decimals: data[44],
+            freeze_authority: read_coption_pubkey(data, 46),
+        })
+    }
+}
+
+// COption<Pubkey>: u32 tag + 32 bytes
+fn read_coption_pubkey(data: &[u8], offset: usize) -> Option<Pubkey> {
+    let tag = u32::from_le_bytes(data[offset..offset + 4].try_into().ok()?);
+    (tag == 1).then(|| Pubkey::new_from_array(data[offset + 4..offset + 36].try_into().expect("length checked")))
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct MetaplexMetadata {
+    pub update_authority: Pubkey,
+    pub mint: Pubkey,
+    pub name: String,
+    pub symbol: String,
+    pub uri: String,
+    pub is_mutable: bool,
+}
+
+impl MetaplexMetadata {
+    pub fn decode(data: &[u8]) -> Result<Self> {
+        // key | update_authority | mint | name | symbol | uri | seller_fee_bps
+        // | creators: Option<Vec<(pubkey, verified, share)>> | primary_sale_happened | is_mutable
+        let mut offset = 1;
+        let update_authority = read_pubkey(data, &mut offset)?;
+        let mint = read_pubkey(data, &mut offset)?;
+        let name = read_string(data, &mut offset)?;
+        let symbol = read_string(data, &mut offset)?;
+        let uri = read_string(data, &mut offset)?;
+        offset += 2;
+
+        if read_byte(data, &mut offset)? == 1 {
+            let count = u32::from_le_bytes(take(data, &mut offset, 4)?.try_into()?) as usize;
+            take(data, &mut offset, count * 34)?;
+        }
+        read_byte(data, &mut offset)?;
+        let is_mutable = read_byte(data, &mut offset)? != 0;
+
+        Ok(Self { update_authority, mint, name, symbol, uri, is_mutable })
+    }
+}
+
+fn take<'a>(data: &'a [u8], offset: &mut usize, len: usize) -> Result<&'a [u8]> {
+    let bytes = data.get(*offset..*offset + len).ok_or_else(|| anyhow!("Metadata truncated at {}", offset))?;
+    *offset += len;
+    Ok(bytes)
+}
+
+fn read_byte(data: &[u8], offset: &mut usize) -> Result<u8> {
+    Ok(take(data, offset, 1)?[0])
+}
+
+fn read_pubkey(data: &[u8], offset: &mut usize) -> Result<Pubkey> {
+    Ok(Pubkey::new_from_array(take(data, offset, 32)?.try_into()?))
+}
+
+// Borsh string, padded with NULs to a fixed size by the program
+fn read_string(data: &[u8], offset: &mut usize) -> Result<String> {
+    let len = u32::from_le_bytes(take(data, offset, 4)?.try_into()?) as usize;
+    let bytes = take(data, offset, len)?;
+    Ok(String::from_utf8_lossy(bytes).trim_matches('\0').trim().to_string())
+}
+
+/// Metaplex metadata PDA for a mint.
+pub fn metadata_address(mint: &Pubkey) -> Pubkey {
+    let program_id = Pubkey::from_str(METAPLEX_PROGRAM_ID).expect("valid program id");
+    Pubkey::find_program_address(&[b"metadata", program_id.as_ref(), mint.as_ref()], &program_id).0
+}
+
+// ========================================================================
+// ENRICHER
+// ========================================================================
+
+struct PendingMint {
+    mint: String,
+    key: Pubkey,
+    platform: Platform,
+    // Known from the launch (pump.fun), otherwise read from the LaunchLab pool
+    creator: Option<String>,
+    attempts: u32,
+}
+
+/// Reads the mint account and Metaplex metadata for every launch, batched
+/// with `getMultipleAccounts`, and publishes an `Identity` event per mint.
+pub struct TokenIdentityEnricher {
+    rpc: RpcScheduler,
+    commitment: Commitment,
+    settings: IdentityConfig,
+    events: EventHub,
+    receiver: EventFeed,
+    queue: Vec<PendingMint>,
+    cache: HashMap<String, TokenIdentity>,
+    cache_order: VecDeque<String>,
+}
+
+impl TokenIdentityEnricher {
+    /// Subscribes immediately so no launch is missed before `run`.
+    pub fn new(config: &Config, events: EventHub, feed: EventFeed) -> Result<Self> {
+        Ok(Self {
+            rpc: RpcScheduler::shared(config)?.with_timeout(Duration::from_secs(5)),
+            commitment: config.commitment,
+            settings: config.identity.clone(),
+            receiver: feed,
+            events,
+            queue: Vec::new(),
+            cache: HashMap::new(),
+            cache_order: VecDeque::new(),
+        })
+    }
+
+    pub async fn run(mut self) {
+        info!("🪪 Token identity enricher started (batching every {}ms)", self.settings.batch_window_ms);
+
+        let mut ticker = interval(Duration::from_millis(self.settings.batch_window_ms.max(10)));
+        ticker.set_missed_tick_behavior(MissedTickBehavior::Delay);
+
+        loop {
+            tokio::select! {
+                event = self.receiver.recv() => match event {
+                    Ok(MonitorEvent::Launch(launch)) => self.enqueue(&launch),
+                    Ok(_) => {},
+                    Err(RecvError::Lagged(skipped)) => warn!("⚠️ Identity enricher lagging, skipped {} events", skipped),
+                    Err(RecvError::Closed) => break,
+                },
+                _ = ticker.tick() => {
+                    if !self.queue.is_empty() {
+                        self.flush().await;
+                    }
+                }
+            }
+        }
+    }
+
+    fn enqueue(&mut self, launch: &TokenLaunch) {
+        if let Some(cached) = self.cache.get(&launch.contract_address) {
+            let mut identity = cached.clone();
+            identity.timing = identity_timing();
+            identity.timestamp = identity.timing.detected_at_ms / 1000;
+            self.events.publish(MonitorEvent::Identity(identity));
+            return;
+        }
+        if self.queue.iter().any(|pending| pending.mint == launch.contract_address) {
+            return;
+        }
+        let Ok(key) = Pubkey::from_str(&launch.contract_address) else {
+            debug!("🪪 Skipping identity for unparsable mint {}", launch.contract_address);
+            return;
+        };
+        self.queue.push(PendingMint {
+            mint: launch.contract_address.clone(),
+            key,
+            platform: launch.platform,
+            creator: launch.creator.clone(),
+            attempts: 0,
+        });
+    }
+
+    async fn flush(&mut self) {
+        let batch = std::mem::take(&mut self.queue);
+        // Mint + metadata PDA, plus the pool for LaunchLab: at most 3 keys per mint
+        for chunk in batch.chunks(MAX_ACCOUNTS_PER_REQUEST / 3) {
+            let keys: Vec<Vec<Pubkey>> = chunk.iter().map(accounts_for).collect();
+            let flat: Vec<String> = keys.iter().flatten().map(|key| key.to_string()).collect();
+
+            let accounts = match self.fetch_accounts(&flat).await {
+                Ok(accounts) => accounts,
+                Err(e) => {
+                    warn!("⚠️ getMultipleAccounts failed: {}", e);
+                    self.retry(chunk);
+                    continue;
+                },
+            };
+
+            let mut accounts = accounts.into_iter();
+            for (pending, keys) in chunk.iter().zip(&keys) {
+                let fetched: Vec<Value> = accounts.by_ref().take(keys.len()).collect();
+                match build_identity(pending, &fetched) {
+                    Ok(Some(identity)) => self.publish(identity),
+                    // Mint not visible yet at our commitment
+                    Ok(None) => self.retry(std::slice::from_ref(pending)),
+                    Err(e) => {
+                        metrics().decode_error(pending.platform, &e);
+                        debug!("🪪 Couldn't decode identity for {}: {}", pending.mint, e);
+                    },
+                }
+            }
+        }
+    }
+
+    fn retry(&mut self, pending: &[PendingMint]) {
+        for mint in pending {
+            if mint.attempts + 1 >= self.settings.max_attempts {
+                debug!("🪪 Giving up on identity for {}", mint.mint);
+                continue;
+            }
+            self.queue.push(PendingMint {
+                mint: mint.mint.clone(),
+                key: mint.key,
+                platform: mint.platform,
+                creator: mint.creator.clone(),
+                attempts: mint.attempts + 1,
+            });
+        }
+    }
+
+    async fn fetch_accounts(&self, keys: &[String]) -> Result<Vec<Value>> {
+        let result = self.rpc.result(Priority::Launch, "getMultipleAccounts", json!([
+            keys, { "encoding": "base64", "commitment": self.commitment.as_str() }
+        ])).await?;
+
+        let accounts = result
+            .get("value")
+            .and_then(|v| v.as_array())
+            .ok_or_else(|| anyhow!("missing result.value"))?;
+        if accounts.len() != keys.len() {
+            return Err(anyhow!("expected {} accounts, got {}", keys.len(), accounts.len()));
+        }
+        Ok(accounts.clone())
+    }
+
+    fn publish(&mut self, identity: TokenIdentity) {
+        info!(
+            "🪪 [{}] {} ({}) | CA: {} | {:?} | Mint auth: {} | Freeze auth: {} | Risk: {}",
+            identity.platform,
+            identity.name.as_deref().unwrap_or("Unknown"),
+            identity.symbol.as_deref().unwrap_or("?"),
+            identity.mint,
+            identity.token_program,
+            identity.mint_authority.as_deref().unwrap_or("none"),
+            identity.freeze_authority.as_deref().unwrap_or("none"),
+            identity.risk_level.map_or("none".to_string(), |level| format!("{:?}", level))
+        );
+        for flag in &identity.risk_flags {
+            debug!("🪪 {} | {:?} ({:?})", identity.mint, flag, flag.severity());
+        }
+
+        if self.cache_order.len() >= self.settings.cache_size {
+            if let Some(oldest) = self.cache_order.pop_front() {
+                self.cache.remove(&oldest);
+            }
+        }
+        self.cache_order.push_back(identity.mint.clone());
+        self.cache.insert(identity.mint.clone(), identity.clone());
+        metrics().cache_size(identity.platform, "identities", self.cache.len());
+
+        self.events.publish(MonitorEvent::Identity(identity));
+    }
+}
+
+fn accounts_for(pending: &PendingMint) -> Vec<Pubkey> {
+    let mut keys = vec![pending.key, metadata_address(&pending.key)];
+    if pending.platform == Platform::Raydium && pending.creator.is_none() {
+        keys.push(sol_pool_address(&pending.key));
+    }
+    keys
+}
+
+/// `None` when the mint account doesn't exist (yet).
+fn build_identity(pending: &PendingMint, accounts: &[Value]) -> Result<Option<TokenIdentity>> {
+    let Some((data, owner)) = accounts.first().and_then(account_data) else { return Ok(None) };
+    let mint = MintAccount::decode(&data, &owner)?;
+    let extensions = token_extensions::parse_extensions(&data)?;
+    let risk_flags = token_extensions::risk_flags(&mint, &extensions);
+
+    // Missing Metaplex metadata is normal - Token-2022 tokens often carry it in an extension
+    let metadata = accounts.get(1)
+        .and_then(account_data)
+        .and_then(|(data, _)| MetaplexMetadata::decode(&data).ok())
+        .or_else(|| embedded_metadata(&extensions, &pending.key));
+    let pool_creator = accounts.get(2)
+        .and_then(account_data)
+        .and_then(|(data, _)| PoolState::decode(&data).ok())
+        .map(|pool| pool.creator.to_string());
+
+    let non_empty = |value: &str| (!value.is_empty()).then(|| value.to_string());
+    let timing = identity_timing();
+    Ok(Some(TokenIdentity {
+        mint: pending.mint.clone(),
+        token_program: mint.token_program,
+        decimals: mint.decimals,
+        supply: mint.supply,
+        mint_authority: mint.mint_authority.map(|key| key.to_string()),
+        freeze_authority: mint.freeze_authority.map(|key| key.to_string()),
+        name: metadata.as_ref().and_then(|m| non_empty(&m.name)),
+        symbol: metadata.as_ref().and_then(|m| non_empty(&m.symbol)),
+        uri: metadata.as_ref().and_then(|m| non_empty(&m.uri)),
+        update_authority: metadata.as_ref().map(|m| m.update_authority.to_string()),
+        is_mutable: metadata.as_ref().map(|m| m.is_mutable),
+        creator: pending.creator.clone().or(pool_creator),
+        extensions: extensions.iter().map(Extension::name).collect(),
+        risk_level: token_extensions::risk_level(&risk_flags),
+        risk_flags,
+        platform: pending.platform,
+        timestamp: timing.detected_at_ms / 1000,
+        timing,
+    }))
+}
+
+// Token-2022 `TokenMetadata` extension, in Metaplex shape
+fn embedded_metadata(extensions: &[Extension], mint: &Pubkey) -> Option<MetaplexMetadata> {
+    extensions.iter().find_map(|extension| match extension {
+        Extension::TokenMetadata { update_authority, name, symbol, uri } => Some(MetaplexMetadata {
+            update_authority: update_authority.unwrap_or_default(),
+            mint: *mint,
+            name: name.clone(),
+            symbol: symbol.clone(),
+            uri: uri.clone(),
+            is_mutable: update_authority.is_some(),
+        }),
+        _ => None,
+    })
+}
+
+// (data, owner) for an existing account in a getMultipleAccounts result
+fn account_data(account: &Value) -> Option<(Vec<u8>, String)> {
+    let encoded = account.get("data")?.get(0)?.as_str()?;
+    let owner = account.get("owner")?.as_str()?.to_string();
+    Some((general_purpose::STANDARD.decode(encoded).ok()?, owner))
+}
+
+fn identity_timing() -> EventTiming {
+    EventTiming {
+        slot: None,
+        block_time_ms: None,
+        detected_at_ms: now_ms(),
+        status: TxStatus::default(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn decodes_mint_authorities() {
+        let authority = Pubkey::new_unique();
+        let mut data = vec![0u8; MINT_ACCOUNT_LEN];
+        data[0] = 1;
+        data[4..36].copy_from_slice(authority.as_ref());
+        data[36..44].copy_from_slice(&1_000_000_000_000_000u64.to_le_bytes());
+        data[44] = 6;
+        data[45] = 1;
+
+        let mint = MintAccount::decode(&data, TOKEN_2022_PROGRAM_ID).unwrap();
+        assert_eq!(mint.token_program, TokenProgram::Token2022);
+        assert_eq!(mint.mint_authority, Some(authority));
+        assert_eq!(mint.freeze_authority, None);
+        assert_eq!((mint.supply, mint.decimals), (1_000_000_000_000_000, 6));
+        assert!(MintAccount::decode(&data, "11111111111111111111111111111111").is_err());
+    }
+
+    #[test]
+    fn decodes_padded_metaplex_strings() {
+        let update_authority = Pubkey::new_unique();
+        let mint = Pubkey::new_unique();
+        let mut data = vec![4u8];
+        data.extend_from_slice(update_authority.as_ref());
+        data.extend_from_slice(mint.as_ref());
+        for (value, padded) in [("Bonk Cat", 32), ("BCAT", 10), ("https://example.com/m.json", 200)] {
+            data.extend_from_slice(&(padded as u32).to_le_bytes());
+            let mut bytes = value.as_bytes().to_vec();
+            bytes.resize(padded, 0);
+            data.extend_from_slice(&bytes);
+        }
+        data.extend_from_slice(&0u16.to_le_bytes());
+        // One creator, primary sale not happened, mutable
+        data.push(1);
+        data.extend_from_slice(&1u32.to_le_bytes());
+        data.extend_from_slice(&[0u8; 34]);
+        data.extend_from_slice(&[0, 1]);
+
+        let metadata = MetaplexMetadata::decode(&data).unwrap();
+        assert_eq!(metadata.name, "Bonk Cat");
+        assert_eq!(metadata.symbol, "BCAT");
+        assert_eq!(metadata.uri, "https://example.com/m.json");
+        assert_eq!(metadata.mint, mint);
+        assert!(metadata.is_mutable);
+    }
+}
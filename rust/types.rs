@@ -4,23 +4,81 @@ use serde::{Deserialize, Serialize};
 // BASIC TOKEN LAUNCH TYPES
 // ========================================================================
 
+// How final the transaction behind an event was when it was emitted
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum TxStatus {
+    Processed,
+    #[default]
+    Confirmed,
+    Finalized,
+}
+
+impl TxStatus {
+    pub fn label(&self) -> &'static str {
+        match self {
+            TxStatus::Processed => "processed",
+            TxStatus::Confirmed => "confirmed",
+            TxStatus::Finalized => "finalized",
+        }
+    }
+}
+
+// Where an event sits in time: slot + on-chain time vs. when we saw it
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct EventTiming {
+    pub slot: Option<u64>,
+    pub block_time_ms: Option<u64>,
+    pub detected_at_ms: u64,
+    #[serde(default)]
+    pub status: TxStatus,
+}
+
+impl EventTiming {
+    /// Detection latency against block time, if the block time is known.
+    pub fn latency_ms(&self) -> Option<i64> {
+        self.block_time_ms.map(|block_time| self.detected_at_ms as i64 - block_time as i64)
+    }
+
+    /// Unix seconds for an event without an on-chain timestamp: block time, else detection time.
+    pub fn unix_secs(&self) -> u64 {
+        self.block_time_ms.unwrap_or(self.detected_at_ms) / 1000
+    }
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct TokenLaunch {
     pub contract_address: String,
     pub name: Option<String>,
     pub symbol: Option<String>,
     pub creator: Option<String>,
+    // Off-chain metadata JSON, resolved later into a `Metadata` event
+    #[serde(default)]
+    pub uri: Option<String>,
     pub signature: String,
     pub platform: Platform,
     pub timestamp: u64,
+    pub timing: EventTiming,
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
 pub enum Platform {
+    #[serde(alias = "pump_fun")]
     PumpFun,
+    #[serde(alias = "raydium")]
     Raydium,
 }
 
+impl Platform {
+    // Stable lowercase name for metric labels and file paths
+    pub fn label(&self) -> &'static str {
+        match self {
+            Platform::PumpFun => "pump_fun",
+            Platform::Raydium => "raydium",
+        }
+    }
+}
+
 impl std::fmt::Display for Platform {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         match self {
@@ -30,15 +88,537 @@ impl std::fmt::Display for Platform {
     }
 }
 
+// ========================================================================
+// MONITOR EVENTS
+// ========================================================================
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TradeEvent {
+    pub mint: String,
+    pub trader: Option<String>,
+    pub is_buy: bool,
+    pub sol_amount: Option<u64>,
+    pub token_amount: Option<u64>,
+    pub signature: String,
+    pub platform: Platform,
+    pub timestamp: u64,
+    pub timing: EventTiming,
+}
+
+// Bonding curve finished - liquidity is about to migrate
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CompletionEvent {
+    pub mint: String,
+    pub bonding_curve: Option<String>,
+    pub user: Option<String>,
+    pub signature: String,
+    pub platform: Platform,
+    pub timestamp: u64,
+    pub timing: EventTiming,
+}
+
+// Liquidity moved from the launchpad to an AMM pool
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct MigrationEvent {
+    pub mint: String,
+    pub pool: Option<String>,
+    pub sol_amount: Option<u64>,
+    pub token_amount: Option<u64>,
+    pub signature: String,
+    pub platform: Platform,
+    pub timestamp: u64,
+    pub timing: EventTiming,
+}
+
+// Commitment of an already-emitted transaction advanced (processed -> confirmed -> finalized)
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct StatusUpdate {
+    pub mint: String,
+    pub signature: String,
+    pub platform: Platform,
+    pub status: TxStatus,
+    pub timestamp: u64,
+    pub timing: EventTiming,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum RetractReason {
+    // Landed but the transaction errored
+    Failed,
+    // Never reached the tracked commitment (fork / expired blockhash)
+    Dropped,
+}
+
+// An earlier event must be discarded - its transaction never landed
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Retraction {
+    pub mint: String,
+    pub signature: String,
+    pub platform: Platform,
+    pub reason: RetractReason,
+    pub timestamp: u64,
+    pub timing: EventTiming,
+}
+
+// Live bonding curve (pump.fun curve or LaunchLab pool), pushed on every account change.
+// For LaunchLab: virtual_a/b, base tokens left for sale and quote raised (SOL fields hold the quote token)
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BondingCurveState {
+    pub mint: String,
+    pub bonding_curve: String,
+    pub virtual_token_reserves: u64,
+    pub virtual_sol_reserves: u64,
+    pub real_token_reserves: u64,
+    pub real_sol_reserves: u64,
+    pub token_total_supply: u64,
+    pub complete: bool,
+    // 0-100, share of the curve's sellable tokens already bought
+    pub progress_pct: f64,
+    // Spot price of one whole token and fully diluted market cap, in SOL
+    pub price_sol: f64,
+    pub market_cap_sol: f64,
+    // Lamports still needed to buy out the curve (fees excluded)
+    pub sol_to_complete: u64,
+    pub platform: Platform,
+    pub timestamp: u64,
+    pub timing: EventTiming,
+}
+
+// Recent buying pressure on a curve (mirrors `CurveVelocity` in types/pump.ts)
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CurveVelocity {
+    pub mint: String,
+    // Net SOL in (buys - sells)
+    pub sol_per_minute: f64,
+    // Both sides, token base units
+    pub token_volume_per_minute: f64,
+    pub trades_per_minute: f64,
+    // Minutes of data used
+    pub calculated_over: f64,
+}
+
+// Mirrors `CompletionPrediction` in types/pump.ts
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CompletionPrediction {
+    pub mint: String,
+    // None while SOL isn't flowing in
+    pub estimated_minutes: Option<f64>,
+    // 0-100
+    pub confidence: f64,
+    // false when there weren't enough recent trades and the curve's overall fill rate was used
+    pub based_on_recent_velocity: bool,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum NearCompletionTrigger {
+    Progress,
+    Eta,
+}
+
+// A curve crossed the progress or ETA threshold - emitted once per mint
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct NearCompletion {
+    pub mint: String,
+    pub platform: Platform,
+    pub trigger: NearCompletionTrigger,
+    pub progress_pct: f64,
+    pub sol_to_complete: u64,
+    pub velocity: CurveVelocity,
+    pub prediction: CompletionPrediction,
+    pub timestamp: u64,
+    pub timing: EventTiming,
+}
+
+// Off-chain metadata behind a launch's URI, published once it resolves
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TokenMetadata {
+    pub mint: String,
+    pub uri: String,
+    pub name: Option<String>,
+    pub symbol: Option<String>,
+    pub description: Option<String>,
+    // Gateway URL when the JSON points at ipfs:// or ar://
+    pub image: Option<String>,
+    pub twitter: Option<String>,
+    pub telegram: Option<String>,
+    pub website: Option<String>,
+    pub platform: Platform,
+    pub timestamp: u64,
+    pub timing: EventTiming,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum TokenProgram {
+    Token,
+    Token2022,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum RiskSeverity {
+    Low,
+    Medium,
+    High,
+}
+
+// Something other than the holder can move, freeze, tax or inflate the token
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "flag", rename_all = "snake_case")]
+pub enum RiskFlag {
+    MintAuthority { authority: String },
+    FreezeAuthority { authority: String },
+    TransferFee { basis_points: u16, maximum_fee: u64, authority: Option<String> },
+    PermanentDelegate { delegate: String },
+    // program None = no hook yet, but the authority can install one
+    TransferHook { program: Option<String>, authority: Option<String> },
+    NonTransferable,
+    DefaultFrozen,
+    MintCloseAuthority { authority: String },
+    Pausable { paused: bool },
+    ConfidentialTransfers,
+    ScaledUiAmount,
+    InterestBearing,
+}
+
+// On-chain identity: mint account + Metaplex metadata, read once per mint
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TokenIdentity {
+    pub mint: String,
+    pub token_program: TokenProgram,
+    pub decimals: u8,
+    pub supply: u64,
+    // None = renounced
+    pub mint_authority: Option<String>,
+    pub freeze_authority: Option<String>,
+    // From the Metaplex metadata PDA, when it exists
+    pub name: Option<String>,
+    pub symbol: Option<String>,
+    pub uri: Option<String>,
+    pub update_authority: Option<String>,
+    pub is_mutable: Option<bool>,
+    // Launch creator (LaunchLab: pool creator)
+    pub creator: Option<String>,
+    // Token-2022 extensions present on the mint
+    #[serde(default)]
+    pub extensions: Vec<String>,
+    #[serde(default)]
+    pub risk_flags: Vec<RiskFlag>,
+    // Highest flag severity, None when clean
+    #[serde(default)]
+    pub risk_level: Option<RiskSeverity>,
+    pub platform: Platform,
+    pub timestamp: u64,
+    pub timing: EventTiming,
+}
+
+// Creator's history before this launch, published once the creator is known
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CreatorReputation {
+    pub mint: String,
+    pub creator: String,
+    pub prior_launches: u64,
+    // Prior launches within the serial-launcher window
+    pub launches_in_window: u64,
+    pub completion_rate_pct: Option<f64>,
+    pub avg_time_to_completion_secs: Option<u64>,
+    // Share of their own buys the creator sold again, averaged over tokens
+    pub avg_share_sold_pct: Option<f64>,
+    // Tokens the creator sold out of before they completed
+    pub dumped_tokens: u64,
+    pub serial_launcher: bool,
+    pub platform: Platform,
+    pub timestamp: u64,
+    pub timing: EventTiming,
+}
+
+// The creator traded their own token shortly after launch
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DevActivity {
+    pub mint: String,
+    pub creator: String,
+    pub token_amount: u64,
+    pub sol_amount: Option<u64>,
+    // This trade as a share of supply
+    pub supply_pct: Option<f64>,
+    // Creator's bought - sold since launch, as a share of supply
+    pub net_position_pct: Option<f64>,
+    pub secs_since_launch: u64,
+    pub signature: String,
+    pub platform: Platform,
+    pub timestamp: u64,
+    pub timing: EventTiming,
+}
+
+// Early buyer in a launch's first slots
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BundleWallet {
+    pub wallet: String,
+    pub slot: Option<u64>,
+    // Bought inside the create transaction
+    pub same_tx: bool,
+    pub same_slot: bool,
+    pub is_creator: bool,
+    // Net tokens from the early trades
+    pub token_amount: u64,
+    pub supply_pct: Option<f64>,
+    // Sender of the wallet's funding SOL, when it could be looked up
+    pub funder: Option<String>,
+}
+
+// Early wallets funded from the same source
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct FundingCluster {
+    pub funder: String,
+    pub creator_funded: bool,
+    pub wallets: Vec<String>,
+    pub supply_pct: Option<f64>,
+}
+
+// Buys in the first slots after a launch, grouped and scored
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BundleReport {
+    pub mint: String,
+    pub creator: Option<String>,
+    pub launch_slot: Option<u64>,
+    pub same_slot_buyers: u32,
+    pub same_tx_buyers: u32,
+    pub wallets: Vec<BundleWallet>,
+    pub clusters: Vec<FundingCluster>,
+    // Held by same-slot/tx wallets and everything clustered with them
+    pub bundled_supply_pct: f64,
+    // 0-100
+    pub bundle_score: f64,
+    pub bundled: bool,
+    pub platform: Platform,
+    pub timestamp: u64,
+    pub timing: EventTiming,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum HolderSource {
+    // Every token account (getProgramAccounts) - holder_count is exact
+    AllAccounts,
+    // The 20 largest accounts only
+    LargestAccounts,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Holder {
+    pub owner: String,
+    pub amount: u64,
+    // Share of total supply
+    pub pct: f64,
+}
+
+// Holder distribution at a fixed delay after launch, curve and pool vaults excluded
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct HolderSnapshot {
+    pub mint: String,
+    pub delay_secs: u64,
+    pub source: HolderSource,
+    pub holder_count: Option<u64>,
+    pub top10_share_pct: f64,
+    pub gini: f64,
+    // Share of supply outside the curve/vaults
+    pub circulating_pct: f64,
+    pub top_holders: Vec<Holder>,
+    pub platform: Platform,
+    pub timestamp: u64,
+    pub timing: EventTiming,
+}
+
+// Raised by a script hook about the event it was handling
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ScriptAlert {
+    pub mint: String,
+    pub script: String,
+    pub message: String,
+    // Kind of the event the script was handling
+    pub source_kind: String,
+    pub platform: Platform,
+    pub timestamp: u64,
+    pub timing: EventTiming,
+}
+
+// Everything the monitors emit goes through this enum (API server, stream consumers)
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(tag = "type", content = "data", rename_all = "snake_case")]
+pub enum MonitorEvent {
+    Launch(TokenLaunch),
+    Trade(TradeEvent),
+    Completion(CompletionEvent),
+    Migration(MigrationEvent),
+    Status(StatusUpdate),
+    Retraction(Retraction),
+    Curve(BondingCurveState),
+    NearCompletion(NearCompletion),
+    Metadata(TokenMetadata),
+    Identity(TokenIdentity),
+    Creator(CreatorReputation),
+    DevBought(DevActivity),
+    DevSold(DevActivity),
+    Bundle(BundleReport),
+    Holders(HolderSnapshot),
+    Alert(ScriptAlert),
+}
+
+impl MonitorEvent {
+    pub fn mint(&self) -> &str {
+        match self {
+            MonitorEvent::Launch(launch) => &launch.contract_address,
+            MonitorEvent::Trade(trade) => &trade.mint,
+            MonitorEvent::Completion(completion) => &completion.mint,
+            MonitorEvent::Migration(migration) => &migration.mint,
+            MonitorEvent::Status(update) => &update.mint,
+            MonitorEvent::Retraction(retraction) => &retraction.mint,
+            MonitorEvent::Curve(curve) => &curve.mint,
+            MonitorEvent::NearCompletion(alert) => &alert.mint,
+            MonitorEvent::Metadata(metadata) => &metadata.mint,
+            MonitorEvent::Identity(identity) => &identity.mint,
+            MonitorEvent::Creator(reputation) => &reputation.mint,
+            MonitorEvent::DevBought(activity) | MonitorEvent::DevSold(activity) => &activity.mint,
+            MonitorEvent::Bundle(report) => &report.mint,
+            MonitorEvent::Holders(snapshot) => &snapshot.mint,
+            MonitorEvent::Alert(alert) => &alert.mint,
+        }
+    }
+
+    pub fn kind(&self) -> &'static str {
+        match self {
+            MonitorEvent::Launch(_) => "launch",
+            MonitorEvent::Trade(_) => "trade",
+            MonitorEvent::Completion(_) => "completion",
+            MonitorEvent::Migration(_) => "migration",
+            MonitorEvent::Status(_) => "status",
+            MonitorEvent::Retraction(_) => "retraction",
+            MonitorEvent::Curve(_) => "curve",
+            MonitorEvent::NearCompletion(_) => "near_completion",
+            MonitorEvent::Metadata(_) => "metadata",
+            MonitorEvent::Identity(_) => "identity",
+            MonitorEvent::Creator(_) => "creator",
+            MonitorEvent::DevBought(_) => "dev_bought",
+            MonitorEvent::DevSold(_) => "dev_sold",
+            MonitorEvent::Bundle(_) => "bundle",
+            MonitorEvent::Holders(_) => "holders",
+            MonitorEvent::Alert(_) => "alert",
+        }
+    }
+
+    pub fn timing(&self) -> &EventTiming {
+        match self {
+            MonitorEvent::Launch(launch) => &launch.timing,
+            MonitorEvent::Trade(trade) => &trade.timing,
+            MonitorEvent::Completion(completion) => &completion.timing,
+            MonitorEvent::Migration(migration) => &migration.timing,
+            MonitorEvent::Status(update) => &update.timing,
+            MonitorEvent::Retraction(retraction) => &retraction.timing,
+            MonitorEvent::Curve(curve) => &curve.timing,
+            MonitorEvent::NearCompletion(alert) => &alert.timing,
+            MonitorEvent::Metadata(metadata) => &metadata.timing,
+            MonitorEvent::Identity(identity) => &identity.timing,
+            MonitorEvent::Creator(reputation) => &reputation.timing,
+            MonitorEvent::DevBought(activity) | MonitorEvent::DevSold(activity) => &activity.timing,
+            MonitorEvent::Bundle(report) => &report.timing,
+            MonitorEvent::Holders(snapshot) => &snapshot.timing,
+            MonitorEvent::Alert(alert) => &alert.timing,
+        }
+    }
+
+    pub fn platform(&self) -> Platform {
+        match self {
+            MonitorEvent::Launch(launch) => launch.platform,
+            MonitorEvent::Trade(trade) => trade.platform,
+            MonitorEvent::Completion(completion) => completion.platform,
+            MonitorEvent::Migration(migration) => migration.platform,
+            MonitorEvent::Status(update) => update.platform,
+            MonitorEvent::Retraction(retraction) => retraction.platform,
+            MonitorEvent::Curve(curve) => curve.platform,
+            MonitorEvent::NearCompletion(alert) => alert.platform,
+            MonitorEvent::Metadata(metadata) => metadata.platform,
+            MonitorEvent::Identity(identity) => identity.platform,
+            MonitorEvent::Creator(reputation) => reputation.platform,
+            MonitorEvent::DevBought(activity) | MonitorEvent::DevSold(activity) => activity.platform,
+            MonitorEvent::Bundle(report) => report.platform,
+            MonitorEvent::Holders(snapshot) => snapshot.platform,
+            MonitorEvent::Alert(alert) => alert.platform,
+        }
+    }
+
+    // Account updates and analytics aren't tied to a transaction
+    pub fn signature(&self) -> Option<&str> {
+        match self {
+            MonitorEvent::Launch(launch) => Some(&launch.signature),
+            MonitorEvent::Trade(trade) => Some(&trade.signature),
+            MonitorEvent::Completion(completion) => Some(&completion.signature),
+            MonitorEvent::Migration(migration) => Some(&migration.signature),
+            MonitorEvent::Status(update) => Some(&update.signature),
+            MonitorEvent::Retraction(retraction) => Some(&retraction.signature),
+            MonitorEvent::DevBought(activity) | MonitorEvent::DevSold(activity) => Some(&activity.signature),
+            MonitorEvent::Curve(_) | MonitorEvent::NearCompletion(_)
+            | MonitorEvent::Metadata(_) | MonitorEvent::Identity(_) | MonitorEvent::Creator(_)
+            | MonitorEvent::Bundle(_) | MonitorEvent::Holders(_) | MonitorEvent::Alert(_) => None,
+        }
+    }
+
+    pub fn set_status(&mut self, status: TxStatus) {
+        match self {
+            MonitorEvent::Launch(launch) => launch.timing.status = status,
+            MonitorEvent::Trade(trade) => trade.timing.status = status,
+            MonitorEvent::Completion(completion) => completion.timing.status = status,
+            MonitorEvent::Migration(migration) => migration.timing.status = status,
+            MonitorEvent::DevBought(activity) | MonitorEvent::DevSold(activity) => activity.timing.status = status,
+            MonitorEvent::Status(_) | MonitorEvent::Retraction(_)
+            | MonitorEvent::Curve(_) | MonitorEvent::NearCompletion(_)
+            | MonitorEvent::Metadata(_) | MonitorEvent::Identity(_) | MonitorEvent::Creator(_)
+            | MonitorEvent::Bundle(_) | MonitorEvent::Holders(_) | MonitorEvent::Alert(_) => {},
+        }
+    }
+
+    // Lifecycle events describe earlier events rather than new on-chain activity
+    pub fn is_lifecycle(&self) -> bool {
+        matches!(self, MonitorEvent::Status(_) | MonitorEvent::Retraction(_))
+    }
+}
+
+/// Signature prefix for log lines. Signatures come off the wire, so this
+/// must not assume they are long enough (or ASCII) to slice.
+pub fn short_signature(signature: &str) -> &str {
+    signature.get(..8).unwrap_or(signature)
+}
+
 // ========================================================================
 // ESSENTIAL DISCRIMINATORS & PROGRAM IDS
 // ========================================================================
 
-// Pump.fun discriminator
+// Pump.fun event discriminators
 pub const PUMP_CREATE_EVENT_DISCRIMINATOR: [u8; 8] = [27, 114, 169, 77, 222, 235, 99, 118];
+pub const PUMP_TRADE_EVENT_DISCRIMINATOR: [u8; 8] = [189, 219, 127, 211, 78, 230, 97, 238];
+pub const PUMP_COMPLETE_EVENT_DISCRIMINATOR: [u8; 8] = [95, 114, 97, 156, 212, 46, 152, 8];
+pub const PUMP_MIGRATION_EVENT_DISCRIMINATOR: [u8; 8] = [189, 233, 93, 185, 92, 148, 234, 148];
+
+// Pump.fun account discriminators
+pub const PUMP_BONDING_CURVE_ACCOUNT_DISCRIMINATOR: [u8; 8] = [23, 183, 248, 55, 96, 216, 172, 96];
+pub const PUMP_GLOBAL_ACCOUNT_DISCRIMINATOR: [u8; 8] = [167, 232, 232, 177, 200, 108, 114, 127];
+
+// Raydium LaunchLab account discriminators
+pub const LAUNCHLAB_POOL_STATE_DISCRIMINATOR: [u8; 8] = [247, 237, 227, 245, 215, 195, 222, 70];
+pub const LAUNCHLAB_GLOBAL_CONFIG_DISCRIMINATOR: [u8; 8] = [149, 8, 156, 202, 160, 252, 176, 217];
+pub const LAUNCHLAB_PLATFORM_CONFIG_DISCRIMINATOR: [u8; 8] = [160, 78, 128, 0, 248, 83, 230, 160];
 
 // Program IDs
 pub const PUMP_FUN_PROGRAM_ID: &str = "6EF8rrecthR5Dkzon8Nwu78hRvfCKubJ14M5uBEwF6P";
 
+// SPL Token programs and Metaplex Token Metadata
+pub const TOKEN_PROGRAM_ID: &str = "TokenkegQfeZyiNwAJbNbGKPFXCWuBvf9Ss623VQ5DA";
+pub const TOKEN_2022_PROGRAM_ID: &str = "TokenzQdBNbLqP5VEhdkAS6EPFLC1PHnBqCXEpPxuEb";
+pub const METAPLEX_PROGRAM_ID: &str = "metaqbxxUerdq28cj1RbAWkYQm3ybzjb6a8bt518x1s";
+
 // Raydium LaunchLab program ID (poprawny!)
-pub const RAYDIUM_PROGRAM_ID: &str = "LanMV9sAd7wArD4vJFi2qDdfnVhFxYSUg6eADduJ3uj";
\ No newline at end of file
+pub const RAYDIUM_PROGRAM_ID: &str = "LanMV9sAd7wArD4vJFi2qDdfnVhFxYSUg6eADduJ3uj";
+
+// Raydium LaunchPad Authority - owns the pool vaults
+pub const RAYDIUM_LAUNCHPAD_AUTHORITY: &str = "WLHv2UAZm6z4KyaaELi5pjdbJh6RESMva1Rnn8pJVVh";
\ No newline at end of file
@@ -0,0 +1,371 @@
+use anyhow::{Context, Result};
+use flate2::read::MultiGzDecoder;
+use flate2::write::GzEncoder;
+use flate2::Compression;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::collections::HashMap;
+use std::fs::File;
+use std::io::{BufRead, BufReader, BufWriter, Write};
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::mpsc::{self as std_mpsc, RecvTimeoutError, SyncSender, TrySendError};
+use std::sync::Arc;
+use std::time::Instant;
+use tokio::sync::mpsc;
+use tokio::time::{sleep_until, Duration};
+use tracing::{info, warn, error};
+
+use crate::block_clock::now_ms;
+
+// Frames waiting for the writer thread before new ones are dropped
+const RECORDER_QUEUE: usize = 10_000;
+// A crash loses at most this much of the recording
+const FLUSH_INTERVAL: Duration = Duration::from_secs(1);
+const REPLAY_QUEUE: usize = 1024;
+
+// ========================================================================
+// FRAMES
+// ========================================================================
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum FrameSource {
+    PumpFun,
+    Raydium,
+    // getTransaction response the Raydium monitor fetched for a notification
+    RaydiumTransaction,
+}
+
+/// One line of a recording: a raw WebSocket text frame (or fetched transaction)
+/// and the wall clock time it arrived.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Frame {
+    pub at_ms: u64,
+    pub source: FrameSource,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub signature: Option<String>,
+    pub text: String,
+}
+
+// ========================================================================
+// RECORDER
+// ========================================================================
+
+/// Appends frames to a gzip'd JSON lines file from a background thread, so the
+/// WebSocket loops never wait on disk. Frames are dropped (and counted) if the
+/// writer falls behind.
+#[derive(Clone)]
+pub struct FrameRecorder {
+    sender: SyncSender<Frame>,
+    dropped: Arc<AtomicU64>,
+}
+
+impl FrameRecorder {
+    pub fn start(path: &Path) -> Result<Self> {
+        let file = File::create(path).with_context(|| format!("creating recording {}", path.display()))?;
+        let (sender, receiver) = std_mpsc::sync_channel::<Frame>(RECORDER_QUEUE);
+        let location = path.display().to_string();
+
+        std::thread::Builder::new()
+            .name("ws-recorder".to_string())
+            .spawn(move || {
+                let mut writer = GzEncoder::new(BufWriter::new(file), Compression::default());
+                let mut frames = 0u64;
+                let mut last_flush = Instant::now();
+                loop {
+                    let frame = match receiver.recv_timeout(FLUSH_INTERVAL) {
+                        Ok(frame) => Some(frame),
+                        Err(RecvTimeoutError::Timeout) => None,
+                        Err(RecvTimeoutError::Disconnected) => break,
+                    };
+                    if let Some(frame) = frame {
+                        let line = serde_json::to_string(&frame).map_err(std::io::Error::other);
+                        if let Err(e) = line.and_then(|line| writeln!(writer, "{}", line)) {
+                            error!("❌ Recording to {} failed: {}", location, e);
+                            return;
+                        }
+                        frames += 1;
+                    }
+                    // Sync flush keeps everything so far readable if we're killed
+                    if last_flush.elapsed() >= FLUSH_INTERVAL {
+                        if let Err(e) = writer.flush() {
+                            error!("❌ Recording to {} failed: {}", location, e);
+                            return;
+                        }
+                        last_flush = Instant::now();
+                    }
+                }
+                match writer.finish().and_then(|mut inner| inner.flush()) {
+                    Ok(()) => info!("⏺️ Recording closed: {} frames in {}", frames, location),
+                    Err(e) => error!("❌ Closing recording {} failed: {}", location, e),
+                }
+            })
+            .context("starting recorder thread")?;
+
+        info!("⏺️ Recording WebSocket frames to {}", path.display());
+        Ok(Self { sender, dropped: Arc::new(AtomicU64::new(0)) })
+    }
+
+    pub fn record(&self, source: FrameSource, text: &str) {
+        self.push(Frame { at_ms: now_ms(), source, signature: None, text: text.to_string() });
+    }
+
+    pub fn record_transaction(&self, signature: &str, text: &str) {
+        self.push(Frame {
+            at_ms: now_ms(),
+            source: FrameSource::RaydiumTransaction,
+            signature: Some(signature.to_string()),
+            text: text.to_string(),
+        });
+    }
+
+    fn push(&self, frame: Frame) {
+        match self.sender.try_send(frame) {
+            Ok(()) => {},
+            Err(TrySendError::Full(_)) => {
+                let dropped = self.dropped.fetch_add(1, Ordering::Relaxed) + 1;
+                if dropped.is_power_of_two() {
+                    warn!("⚠️ Recorder falling behind - {} frames dropped", dropped);
+                }
+            },
+            Err(TrySendError::Disconnected(_)) => {},
+        }
+    }
+}
+
+/// Synchronous writer for frames that must all land on disk (backtest
+/// archives), unlike the recorder which drops frames rather than block.
+pub struct RecordingWriter {
+    writer: GzEncoder<BufWriter<File>>,
+    frames: u64,
+}
+
+impl RecordingWriter {
+    pub fn create(path: &Path) -> Result<Self> {
+        let file = File::create(path).with_context(|| format!("creating recording {}", path.display()))?;
+        Ok(Self { writer: GzEncoder::new(BufWriter::new(file), Compression::default()), frames: 0 })
+    }
+
+    pub fn write(&mut self, frame: &Frame) -> Result<()> {
+        writeln!(self.writer, "{}", serde_json::to_string(frame)?)?;
+        self.frames += 1;
+        Ok(())
+    }
+
+    /// Frames written.
+    pub fn finish(self) -> Result<u64> {
+        self.writer.finish()?.flush()?;
+        Ok(self.frames)
+    }
+}
+
+// ========================================================================
+// READING
+// ========================================================================
+
+/// Frames of a recording in order. A file cut off mid-write (the process was
+/// killed) ends at the last complete frame.
+pub fn read_frames(path: &Path) -> Result<impl Iterator<Item = Frame>> {
+    let file = File::open(path).with_context(|| format!("opening recording {}", path.display()))?;
+    let location = path.display().to_string();
+    let mut lines = BufReader::new(MultiGzDecoder::new(file)).lines();
+    let mut done = false;
+
+    Ok(std::iter::from_fn(move || {
+        while !done {
+            let line = match lines.next()? {
+                Ok(line) => line,
+                Err(e) => {
+                    warn!("⚠️ Recording {} ends early: {}", location, e);
+                    done = true;
+                    return None;
+                },
+            };
+            if line.trim().is_empty() {
+                continue;
+            }
+            match serde_json::from_str::<Frame>(&line) {
+                Ok(frame) => return Some(frame),
+                Err(e) => {
+                    warn!("⚠️ Recording {} ends with a bad frame: {}", location, e);
+                    done = true;
+                },
+            }
+        }
+        None
+    }))
+}
+
+/// getTransaction responses by signature, for replaying the Raydium monitor offline.
+pub fn read_transactions(path: &Path) -> Result<HashMap<String, Value>> {
+    let mut transactions = HashMap::new();
+    for frame in read_frames(path)? {
+        if frame.source != FrameSource::RaydiumTransaction {
+            continue;
+        }
+        if let (Some(signature), Ok(response)) = (frame.signature, serde_json::from_str(&frame.text)) {
+            transactions.insert(signature, response);
+        }
+    }
+    Ok(transactions)
+}
+
+// ========================================================================
+// REPLAY
+// ========================================================================
+
+/// Where replayed WebSocket frames go - each monitor's replay loop. Frames keep
+/// their `at_ms`, which the monitors stamp as the detection time.
+#[derive(Default)]
+pub struct ReplayTargets {
+    pub pump_fun: Option<mpsc::Sender<Frame>>,
+    pub raydium: Option<mpsc::Sender<Frame>>,
+}
+
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub struct ReplayStats {
+    pub pump_fun_frames: u64,
+    pub raydium_frames: u64,
+    // Frames whose monitor isn't running
+    pub skipped_frames: u64,
+    // Recorded time between the first and last frame
+    pub recorded_span_ms: u64,
+}
+
+/// Feed a recording to the monitors. `speed` 1.0 keeps the original gaps,
+/// 10.0 plays ten times faster, 0 sends frames as fast as the monitors take them.
+pub async fn replay(path: PathBuf, speed: f64, targets: ReplayTargets) -> Result<ReplayStats> {
+    replay_filtered(path, speed, targets, |_| true).await
+}
+
+/// `replay`, skipping frames `keep` rejects (they aren't counted).
+pub async fn replay_filtered(
+    path: PathBuf,
+    speed: f64,
+    targets: ReplayTargets,
+    mut keep: impl FnMut(&Frame) -> bool + Send + 'static,
+) -> Result<ReplayStats> {
+    // Decompression is blocking - read on a blocking thread, pace here
+    let (sender, mut frames) = mpsc::channel::<Frame>(REPLAY_QUEUE);
+    let reader = tokio::task::spawn_blocking(move || -> Result<()> {
+        for frame in read_frames(&path)?.filter(|frame| keep(frame)) {
+            if sender.blocking_send(frame).is_err() {
+                break;
+            }
+        }
+        Ok(())
+    });
+
+    let mut stats = ReplayStats::default();
+    let mut first: Option<(u64, tokio::time::Instant)> = None;
+    while let Some(frame) = frames.recv().await {
+        let target = match frame.source {
+            FrameSource::PumpFun => targets.pump_fun.as_ref(),
+            FrameSource::Raydium => targets.raydium.as_ref(),
+            // Looked up by the Raydium monitor, not replayed as frames
+            FrameSource::RaydiumTransaction => continue,
+        };
+
+        let (first_ms, started) = *first.get_or_insert((frame.at_ms, tokio::time::Instant::now()));
+        let offset_ms = frame.at_ms.saturating_sub(first_ms);
+        stats.recorded_span_ms = offset_ms;
+        if speed > 0.0 {
+            sleep_until(started + Duration::from_secs_f64(offset_ms as f64 / 1000.0 / speed)).await;
+        }
+
+        let Some(target) = target else {
+            stats.skipped_frames += 1;
+            continue;
+        };
+        // Backpressure: a slow monitor slows the replay rather than losing frames
+        let source = frame.source;
+        if target.send(frame).await.is_err() {
+            stats.skipped_frames += 1;
+            continue;
+        }
+        match source {
+            FrameSource::PumpFun => stats.pump_fun_frames += 1,
+            _ => stats.raydium_frames += 1,
+        }
+    }
+
+    reader.await.context("recording reader panicked")??;
+    Ok(stats)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn temp_path(name: &str) -> PathBuf {
+        std::env::temp_dir().join(format!("ws-recording-{}-{}", std::process::id(), name))
+    }
+
+    // The writer thread finishes the gzip stream once every sender is gone
+    fn read_when_closed(recorder: FrameRecorder, path: &Path, expected: usize) -> Vec<Frame> {
+        drop(recorder);
+        for _ in 0..50 {
+            let frames: Vec<Frame> = read_frames(path).unwrap().collect();
+            if frames.len() == expected {
+                return frames;
+            }
+            std::thread::sleep(std::time::Duration::from_millis(100));
+        }
+        panic!("recording never reached {} frames", expected);
+    }
+
+    #[test]
+    fn records_and_reads_back_frames() {
+        let path = temp_path("roundtrip.jsonl.gz");
+        let recorder = FrameRecorder::start(&path).unwrap();
+        recorder.record(FrameSource::PumpFun, r#"{"params":1}"#);
+        recorder.record_transaction("sig", r#"{"result":{"slot":5}}"#);
+        recorder.record(FrameSource::Raydium, r#"{"params":2}"#);
+        let frames = read_when_closed(recorder, &path, 3);
+        assert_eq!(frames[0].source, FrameSource::PumpFun);
+        assert_eq!(frames[2].text, r#"{"params":2}"#);
+
+        let transactions = read_transactions(&path).unwrap();
+        assert_eq!(transactions["sig"]["result"]["slot"], 5);
+        std::fs::remove_file(path).ok();
+    }
+
+    #[test]
+    fn truncated_recording_keeps_complete_frames() {
+        let path = temp_path("truncated.jsonl.gz");
+        let mut writer = GzEncoder::new(Vec::new(), Compression::default());
+        for index in 0..50 {
+            let frame = Frame { at_ms: index, source: FrameSource::PumpFun, signature: None, text: format!("frame {}", index) };
+            writeln!(writer, "{}", serde_json::to_string(&frame).unwrap()).unwrap();
+        }
+        // Sync flush, then "crash" before the gzip trailer is written
+        writer.flush().unwrap();
+        std::fs::write(&path, writer.get_ref()).unwrap();
+
+        assert_eq!(read_frames(&path).unwrap().count(), 50);
+        std::fs::remove_file(path).ok();
+    }
+
+    #[tokio::test]
+    async fn replays_in_order_and_routes_by_source() {
+        let path = temp_path("replay.jsonl.gz");
+        let mut writer = GzEncoder::new(File::create(&path).unwrap(), Compression::default());
+        for (at_ms, source, text) in [(1_000, FrameSource::PumpFun, "a"), (1_500, FrameSource::Raydium, "b"), (2_000, FrameSource::PumpFun, "c")] {
+            let frame = Frame { at_ms, source, signature: None, text: text.to_string() };
+            writeln!(writer, "{}", serde_json::to_string(&frame).unwrap()).unwrap();
+        }
+        writer.finish().unwrap();
+
+        let (pump_tx, mut pump_rx) = mpsc::channel(8);
+        let targets = ReplayTargets { pump_fun: Some(pump_tx), raydium: None };
+        let stats = replay(path.clone(), 0.0, targets).await.unwrap();
+
+        assert_eq!(stats, ReplayStats { pump_fun_frames: 2, raydium_frames: 0, skipped_frames: 1, recorded_span_ms: 1_000 });
+        let first = pump_rx.recv().await.unwrap();
+        assert_eq!((first.at_ms, first.text.as_str()), (1_000, "a"));
+        let second = pump_rx.recv().await.unwrap();
+        assert_eq!((second.at_ms, second.text.as_str()), (2_000, "c"));
+        std::fs::remove_file(path).ok();
+    }
+}